@@ -128,6 +128,7 @@ impl<S> DecodeMut<'_, '_, S> for u8 {
 
 rpc_encode_decode!(le u32);
 rpc_encode_decode!(le usize);
+rpc_encode_decode!(le u128);
 
 impl<S> Encode<S> for bool {
     fn encode(self, w: &mut Writer, s: &mut S) {
@@ -222,6 +223,18 @@ impl<S> DecodeMut<'a, '_, S> for &'a [u8] {
     }
 }
 
+impl<S> Encode<S> for Vec<u8> {
+    fn encode(self, w: &mut Writer, s: &mut S) {
+        self[..].encode(w, s);
+    }
+}
+
+impl<S> DecodeMut<'_, '_, S> for Vec<u8> {
+    fn decode(r: &mut Reader<'_>, s: &mut S) -> Self {
+        <&[u8]>::decode(r, s).to_vec()
+    }
+}
+
 impl<S> Encode<S> for &str {
     fn encode(self, w: &mut Writer, s: &mut S) {
         self.as_bytes().encode(w, s);
@@ -15,6 +15,56 @@ pub enum Level {
     Help,
 }
 
+/// Indicates how confident a macro is that a suggested change is what the user
+/// intended, which lets tools like `rustfix` decide whether to apply it.
+#[unstable(feature = "proc_macro_diagnostic", issue = "54140")]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Applicability {
+    /// The suggestion is definitely what the user intended and can be applied
+    /// automatically.
+    MachineApplicable,
+    /// The suggestion may be what the user intended, but it is uncertain.
+    /// Applying it should still result in valid Rust code.
+    MaybeIncorrect,
+    /// The suggestion contains placeholders like `(...)` that the user needs
+    /// to fill in, so it cannot be applied automatically.
+    HasPlaceholders,
+    /// The applicability of the suggestion is unknown.
+    Unspecified,
+}
+
+/// A suggested change to the user's code: a message describing the change,
+/// and the text that should replace each of a set of spans.
+#[unstable(feature = "proc_macro_diagnostic", issue = "54140")]
+#[derive(Clone, Debug)]
+pub struct Suggestion {
+    message: String,
+    parts: Vec<(Span, String)>,
+    applicability: Applicability,
+}
+
+#[unstable(feature = "proc_macro_diagnostic", issue = "54140")]
+impl Suggestion {
+    /// Returns the message describing the suggested change.
+    #[unstable(feature = "proc_macro_diagnostic", issue = "54140")]
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// Returns the spans to replace, along with their replacement text.
+    #[unstable(feature = "proc_macro_diagnostic", issue = "54140")]
+    pub fn parts(&self) -> &[(Span, String)] {
+        &self.parts
+    }
+
+    /// Returns the `Applicability` of the suggestion.
+    #[unstable(feature = "proc_macro_diagnostic", issue = "54140")]
+    pub fn applicability(&self) -> Applicability {
+        self.applicability
+    }
+}
+
 /// Trait implemented by types that can be converted into a set of `Span`s.
 #[unstable(feature = "proc_macro_diagnostic", issue = "54140")]
 pub trait MultiSpan {
@@ -51,7 +101,8 @@ pub struct Diagnostic {
     level: Level,
     message: String,
     spans: Vec<Span>,
-    children: Vec<Diagnostic>
+    children: Vec<Diagnostic>,
+    suggestions: Vec<Suggestion>,
 }
 
 macro_rules! diagnostic_child_methods {
@@ -77,6 +128,20 @@ macro_rules! diagnostic_child_methods {
     )
 }
 
+/// Iterator over the suggestions of a `Diagnostic`.
+#[derive(Debug, Clone)]
+#[unstable(feature = "proc_macro_diagnostic", issue = "54140")]
+pub struct Suggestions<'a>(std::slice::Iter<'a, Suggestion>);
+
+#[unstable(feature = "proc_macro_diagnostic", issue = "54140")]
+impl<'a> Iterator for Suggestions<'a> {
+    type Item = &'a Suggestion;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next()
+    }
+}
+
 /// Iterator over the children diagnostics of a `Diagnostic`.
 #[derive(Debug, Clone)]
 #[unstable(feature = "proc_macro_diagnostic", issue = "54140")]
@@ -100,7 +165,8 @@ impl Diagnostic {
             level: level,
             message: message.into(),
             spans: vec![],
-            children: vec![]
+            children: vec![],
+            suggestions: vec![],
         }
     }
 
@@ -114,7 +180,8 @@ impl Diagnostic {
             level: level,
             message: message.into(),
             spans: spans.into_spans(),
-            children: vec![]
+            children: vec![],
+            suggestions: vec![],
         }
     }
 
@@ -123,6 +190,38 @@ impl Diagnostic {
     diagnostic_child_methods!(span_note, note, Level::Note);
     diagnostic_child_methods!(span_help, help, Level::Help);
 
+    /// Adds a suggestion to replace the source text at `span` with
+    /// `replacement`, described by `message`.
+    #[unstable(feature = "proc_macro_diagnostic", issue = "54140")]
+    pub fn span_suggestion<T, U>(
+        self,
+        span: Span,
+        message: T,
+        replacement: U,
+        applicability: Applicability,
+    ) -> Diagnostic
+        where T: Into<String>, U: Into<String>
+    {
+        self.multipart_suggestion(message, vec![(span, replacement.into())], applicability)
+    }
+
+    /// Adds a suggestion that replaces the source text of several spans at
+    /// once, e.g. adding a `#[derive]` and removing a manual `impl`.
+    #[unstable(feature = "proc_macro_diagnostic", issue = "54140")]
+    pub fn multipart_suggestion<T: Into<String>>(
+        mut self,
+        message: T,
+        parts: Vec<(Span, String)>,
+        applicability: Applicability,
+    ) -> Diagnostic {
+        self.suggestions.push(Suggestion {
+            message: message.into(),
+            parts,
+            applicability,
+        });
+        self
+    }
+
     /// Returns the diagnostic `level` for `self`.
     #[unstable(feature = "proc_macro_diagnostic", issue = "54140")]
     pub fn level(&self) -> Level {
@@ -165,6 +264,12 @@ impl Diagnostic {
         Children(self.children.iter())
     }
 
+    /// Returns an iterator over the suggestions of `self`.
+    #[unstable(feature = "proc_macro_diagnostic", issue = "54140")]
+    pub fn suggestions(&self) -> Suggestions<'_> {
+        Suggestions(self.suggestions.iter())
+    }
+
     /// Emit the diagnostic.
     #[unstable(feature = "proc_macro_diagnostic", issue = "54140")]
     pub fn emit(self) {
@@ -184,6 +289,12 @@ impl Diagnostic {
         for c in self.children {
             diag.sub(c.level, &c.message[..], to_internal(c.spans));
         }
+        for s in self.suggestions {
+            diag.suggestion(&s.message[..], s.applicability);
+            for (span, replacement) in s.parts {
+                diag.suggestion_part(span.0, &replacement[..]);
+            }
+        }
         diag.emit();
     }
 }
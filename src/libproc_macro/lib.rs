@@ -38,7 +38,7 @@ pub mod bridge;
 mod diagnostic;
 
 #[unstable(feature = "proc_macro_diagnostic", issue = "54140")]
-pub use diagnostic::{Diagnostic, Level, MultiSpan};
+pub use diagnostic::{Applicability, Diagnostic, Level, MultiSpan, Suggestion};
 
 use std::{fmt, iter, mem};
 use std::ops::{Bound, RangeBounds};
@@ -73,6 +73,18 @@ impl !Send for LexError {}
 #[stable(feature = "proc_macro_lib", since = "1.15.0")]
 impl !Sync for LexError {}
 
+/// Error returned from `TokenStream::expand_expr`.
+#[unstable(feature = "proc_macro_expand", issue = "0")]
+#[derive(Debug)]
+pub struct ExpandError {
+    _inner: (),
+}
+
+#[unstable(feature = "proc_macro_expand", issue = "0")]
+impl !Send for ExpandError {}
+#[unstable(feature = "proc_macro_expand", issue = "0")]
+impl !Sync for ExpandError {}
+
 impl TokenStream {
     /// Returns an empty `TokenStream` containing no token trees.
     #[stable(feature = "proc_macro_lib2", since = "1.29.0")]
@@ -85,6 +97,22 @@ impl TokenStream {
     pub fn is_empty(&self) -> bool {
         self.0.is_empty()
     }
+
+    /// Parses this `TokenStream` as an expression and eagerly expands the
+    /// macros within it, e.g. turning `concat!("a", env!("B"))` into the
+    /// string literal it produces.
+    ///
+    /// Only expressions that expand to a literal, possibly negated, succeed;
+    /// anything else returns an `ExpandError`. Errors from the expanded macros
+    /// themselves, such as `env!` of a missing variable, are reported by the
+    /// compiler as usual, and also make this method return an `ExpandError`.
+    #[unstable(feature = "proc_macro_expand", issue = "0")]
+    pub fn expand_expr(&self) -> Result<TokenStream, ExpandError> {
+        match bridge::client::TokenStream::expand_expr(&self.0) {
+            Ok(stream) => Ok(TokenStream(stream)),
+            Err(()) => Err(ExpandError { _inner: () }),
+        }
+    }
 }
 
 /// Attempts to break the string into tokens and parse those tokens into a token stream.
@@ -1098,6 +1126,79 @@ impl Literal {
             cloned_bound(range.end_bound()),
         ).map(Span)
     }
+
+    /// Parses a single literal token, such as `"a\nb"`, `b'x'` or `0x10u8`.
+    ///
+    /// A leading `-` is accepted for integer and floating point literals. Returns
+    /// an error if the string is not exactly one literal, or if the compiler would
+    /// reject the literal, e.g. because of an invalid escape or an unknown suffix.
+    /// The literal gets the `Span::call_site()` span.
+    #[unstable(feature = "proc_macro_literal_value", issue = "0")]
+    pub fn from_str(src: &str) -> Result<Literal, LexError> {
+        match bridge::client::Literal::from_str(src) {
+            Ok(literal) => Ok(Literal(literal)),
+            Err(()) => Err(LexError { _inner: () }),
+        }
+    }
+
+    /// Returns the suffix of the literal, e.g. `u8` for `1u8`, if it has one.
+    #[unstable(feature = "proc_macro_literal_value", issue = "0")]
+    pub fn suffix(&self) -> Option<String> {
+        self.0.suffix()
+    }
+
+    /// Returns the value of a string or raw string literal, with escapes
+    /// decoded the same way the compiler does.
+    ///
+    /// Returns `None` if `self` is not a string literal.
+    #[unstable(feature = "proc_macro_literal_value", issue = "0")]
+    pub fn str_value(&self) -> Option<String> {
+        self.0.str_value()
+    }
+
+    /// Returns the value of a byte string or raw byte string literal.
+    ///
+    /// Returns `None` if `self` is not a byte string literal.
+    #[unstable(feature = "proc_macro_literal_value", issue = "0")]
+    pub fn byte_str_value(&self) -> Option<Vec<u8>> {
+        self.0.byte_str_value()
+    }
+
+    /// Returns the value of a character literal.
+    ///
+    /// Returns `None` if `self` is not a character literal.
+    #[unstable(feature = "proc_macro_literal_value", issue = "0")]
+    pub fn char_value(&self) -> Option<char> {
+        self.0.char_value()
+    }
+
+    /// Returns the value of a byte literal such as `b'a'`.
+    ///
+    /// Returns `None` if `self` is not a byte literal.
+    #[unstable(feature = "proc_macro_literal_value", issue = "0")]
+    pub fn byte_value(&self) -> Option<u8> {
+        self.0.byte_value()
+    }
+
+    /// Returns the value of an integer literal in any base, ignoring its suffix.
+    ///
+    /// Returns `None` if `self` is not an integer literal, if its value does
+    /// not fit in a `u128`, or if it was created from a negative number, since
+    /// such literals turn into a `-` followed by a positive literal in the
+    /// token stream.
+    #[unstable(feature = "proc_macro_literal_value", issue = "0")]
+    pub fn int_value(&self) -> Option<u128> {
+        self.0.int_value()
+    }
+
+    /// Returns the value of a floating point literal, or of an integer
+    /// literal with an `f32` or `f64` suffix.
+    ///
+    /// Returns `None` if `self` is not a floating point literal.
+    #[unstable(feature = "proc_macro_literal_value", issue = "0")]
+    pub fn float_value(&self) -> Option<f64> {
+        self.0.float_value().and_then(|s| s.parse().ok())
+    }
 }
 
 // N.B., the bridge only provides `to_string`, implement `fmt::Display`
@@ -1125,3 +1226,45 @@ impl fmt::Debug for Literal {
         self.0.fmt(f)
     }
 }
+
+/// Tracked access to environment variables.
+#[unstable(feature = "proc_macro_tracked_env", issue = "0")]
+pub mod tracked_env {
+    use std::env::{self, VarError};
+    use std::ffi::OsStr;
+
+    /// Retrieves an environment variable and adds it to build dependency info.
+    ///
+    /// The build system executing the compiler will know that the variable was
+    /// accessed during compilation, and will be able to rerun the build when
+    /// the value of that variable changes. Besides the dependency tracking this
+    /// function should be equivalent to `env::var` from the standard library,
+    /// except that the argument must be UTF-8.
+    #[unstable(feature = "proc_macro_tracked_env", issue = "0")]
+    pub fn var<K: AsRef<OsStr> + AsRef<str>>(key: K) -> Result<String, VarError> {
+        let key: &str = key.as_ref();
+        let value = env::var(key);
+        crate::bridge::client::FreeFunctions::track_env_var(
+            key,
+            value.as_ref().map(|v| v.as_str()).ok(),
+        );
+        value
+    }
+}
+
+/// Tracked access to additional files.
+#[unstable(feature = "track_path", issue = "0")]
+pub mod tracked_path {
+    /// Tracks a file explicitly.
+    ///
+    /// Commonly used for files that a macro reads without going through the
+    /// compiler, e.g. a schema that is turned into Rust code. The path is
+    /// added to the dep-info file, so that the build system reruns the
+    /// compiler when the file changes. Relative paths are taken relative to
+    /// the compiler's working directory.
+    #[unstable(feature = "track_path", issue = "0")]
+    pub fn path<P: AsRef<str>>(path: P) {
+        let path: &str = path.as_ref();
+        crate::bridge::client::FreeFunctions::track_path(path);
+    }
+}
@@ -908,6 +908,8 @@ pub enum WorkProductFileKind {
     Object,
     Bytecode,
     BytecodeCompressed,
+    /// The `.dwo` file with the debuginfo of the CGU, with `-C split-debuginfo`.
+    DwarfObject,
 }
 
 #[derive(Clone)]
@@ -24,6 +24,12 @@ declare_lint! {
     "constant evaluation detected erroneous expression"
 }
 
+declare_lint! {
+    pub LONG_RUNNING_CONST_EVAL,
+    Warn,
+    "constant evaluation is taking a long time"
+}
+
 declare_lint! {
     pub UNUSED_IMPORTS,
     Warn,
@@ -434,6 +440,7 @@ declare_lint_pass! {
         PUB_USE_OF_PRIVATE_EXTERN_CRATE,
         INVALID_TYPE_PARAM_DEFAULT,
         CONST_ERR,
+        LONG_RUNNING_CONST_EVAL,
         RENAMED_AND_REMOVED_LINTS,
         SAFE_EXTERN_STATICS,
         SAFE_PACKED_BORROWS,
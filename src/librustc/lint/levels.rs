@@ -4,7 +4,7 @@ use crate::hir::HirId;
 use crate::ich::StableHashingContext;
 use crate::lint::builtin;
 use crate::lint::context::CheckLintNameResult;
-use crate::lint::{self, Lint, LintId, Level, LintSource};
+use crate::lint::{self, Lint, LintId, Level, LintSource, LintStore};
 use crate::session::Session;
 use crate::util::nodemap::FxHashMap;
 use errors::{Applicability, DiagnosticBuilder};
@@ -14,6 +14,9 @@ use syntax::attr;
 use syntax::feature_gate;
 use syntax::source_map::MultiSpan;
 use syntax::symbol::{Symbol, sym};
+use syntax_pos::{BytePos, Span};
+
+use std::path::Path;
 
 pub struct LintLevelSets {
     list: Vec<LintSet>,
@@ -23,7 +26,7 @@ pub struct LintLevelSets {
 enum LintSet {
     CommandLine {
         // -A,-W,-D flags, a `Symbol` for the flag itself and `Level` for which
-        // flag.
+        // flag, along with any levels read from `-Z lint-config`.
         specs: FxHashMap<LintId, (Level, LintSource)>,
     },
 
@@ -52,6 +55,13 @@ impl LintLevelSets {
         let mut specs = FxHashMap::default();
         self.lint_cap = sess.opts.lint_cap.unwrap_or(Level::Forbid);
 
+        // Levels from the configuration file are inserted first so that the
+        // `-A/-W/-D/-F` flags below override them. Attributes in the source
+        // override both, as they are pushed as child `LintSet::Node`s.
+        if let Some(ref path) = sess.opts.debugging_opts.lint_config {
+            self.process_lint_config(sess, &store, path, &mut specs);
+        }
+
         for &(ref lint_name, level) in &sess.opts.lint_opts {
             store.check_lint_name_cmdline(sess, &lint_name, level);
 
@@ -76,6 +86,133 @@ impl LintLevelSets {
         });
     }
 
+    /// Reads lint levels from the file given with `-Z lint-config`.
+    ///
+    /// The file contains one `lint_name = "level"` entry per line, where the
+    /// level is one of `allow`, `warn`, `deny` or `forbid` (the quotes are
+    /// optional). Lint groups and tool lints such as `clippy::pedantic` are
+    /// accepted. Blank lines and lines starting with `#` are ignored.
+    ///
+    /// The file is loaded into the source map, so diagnostics can point to
+    /// the line that set a level and the file is listed in dep-info.
+    fn process_lint_config(&self,
+                           sess: &Session,
+                           store: &LintStore,
+                           path: &Path,
+                           specs: &mut FxHashMap<LintId, (Level, LintSource)>) {
+        let file = match sess.source_map().load_file(path) {
+            Ok(file) => file,
+            Err(e) => sess.fatal(&format!("failed to read lint configuration file `{}`: {}",
+                                          path.display(), e)),
+        };
+        let src = file.src.as_ref().expect("freshly loaded file has no source");
+
+        let mut line_start = 0;
+        for line in src.split('\n') {
+            let lo = file.start_pos + BytePos(line_start as u32);
+            line_start += line.len() + 1;
+
+            let entry = line.trim();
+            if entry.is_empty() || entry.starts_with('#') {
+                continue;
+            }
+            let leading = line.len() - line.trim_start().len();
+            let span = Span::with_root_ctxt(
+                lo + BytePos(leading as u32),
+                lo + BytePos((leading + entry.len()) as u32),
+            );
+
+            let (lint_name, level) = match parse_lint_config_entry(entry) {
+                Some(parsed) => parsed,
+                None => {
+                    sess.struct_span_err(span, "malformed lint configuration entry")
+                        .span_label(span, "expected `lint_name = \"level\"`")
+                        .emit();
+                    continue;
+                }
+            };
+            let level = match Level::from_str(level) {
+                Some(level) => cmp::min(level, self.lint_cap),
+                None => {
+                    sess.struct_span_err(span, &format!("unknown lint level: `{}`", level))
+                        .help("expected one of `allow`, `warn`, `deny` or `forbid`")
+                        .emit();
+                    continue;
+                }
+            };
+
+            let (tool_name, name) = match lint_name.find("::") {
+                Some(idx) => {
+                    let tool = &lint_name[..idx];
+                    if !attr::is_known_lint_tool(ast::Ident::from_str(tool)) {
+                        span_err!(sess, span, E0710,
+                                  "an unknown tool name found in scoped lint: `{}`", lint_name);
+                        continue;
+                    }
+                    (Some(Symbol::intern(tool)), &lint_name[idx + 2..])
+                }
+                None => (None, lint_name),
+            };
+            let src = LintSource::ConfigFile(Symbol::intern(lint_name), span);
+            match store.check_lint_name(name, tool_name) {
+                CheckLintNameResult::Ok(ids) |
+                CheckLintNameResult::Tool(Ok(ids)) => {
+                    for id in ids {
+                        specs.insert(*id, (level, src));
+                    }
+                }
+                CheckLintNameResult::Tool(Err((Some(ids), new_name))) => {
+                    sess.struct_span_warn(span, &format!(
+                        "lint name `{}` is deprecated and may not have an effect in the future",
+                        name,
+                    )).span_suggestion(
+                        span,
+                        "change it to",
+                        format!("{} = \"{}\"", new_name, level.as_str()),
+                        Applicability::MachineApplicable,
+                    ).emit();
+                    for id in ids {
+                        specs.insert(*id, (level, src));
+                    }
+                }
+                CheckLintNameResult::Tool(Err((None, _))) => {
+                    // The tool isn't running, so its lints aren't registered;
+                    // checking them is the responsibility of the tool.
+                }
+                CheckLintNameResult::Warning(msg, renamed) => {
+                    let mut err = sess.struct_span_warn(span, &msg);
+                    if let Some(new_name) = renamed {
+                        err.span_suggestion(
+                            span,
+                            "use the new name",
+                            format!("{} = \"{}\"", new_name, level.as_str()),
+                            Applicability::MachineApplicable,
+                        );
+                    }
+                    err.emit();
+                    if let Ok(ids) = store.find_lints(lint_name) {
+                        for id in ids {
+                            specs.insert(id, (level, src));
+                        }
+                    }
+                }
+                CheckLintNameResult::NoLint(suggestion) => {
+                    let mut err = struct_span_err!(sess, span, E0602,
+                                                   "unknown lint: `{}`", lint_name);
+                    if let Some(suggestion) = suggestion {
+                        err.span_suggestion(
+                            span,
+                            "did you mean",
+                            format!("{} = \"{}\"", suggestion, level.as_str()),
+                            Applicability::MachineApplicable,
+                        );
+                    }
+                    err.emit();
+                }
+            }
+        }
+    }
+
     fn get_lint_level(&self,
                       lint: &'static Lint,
                       idx: u32,
@@ -420,6 +557,7 @@ impl<'a> LintLevelsBuilder<'a> {
                 LintSource::Default => id.to_string(),
                 LintSource::Node(name, _, _) => name.to_string(),
                 LintSource::CommandLine(name) => name.to_string(),
+                LintSource::ConfigFile(name, _) => name.to_string(),
             };
             let (lint_attr_name, lint_attr_span) = match *src {
                 LintSource::Node(name, span, _) => (name, span),
@@ -445,6 +583,10 @@ impl<'a> LintLevelsBuilder<'a> {
                 LintSource::CommandLine(_) => {
                     diag_builder.note("`forbid` lint level was set on command line");
                 }
+                LintSource::ConfigFile(_, forbid_source_span) => {
+                    diag_builder.span_label(forbid_source_span,
+                                            "`forbid` level set here");
+                }
             }
             diag_builder.emit();
             // don't set a separate error for every lint in the group
@@ -562,6 +704,22 @@ impl<'a> HashStable<StableHashingContext<'a>> for LintLevelMap {
     }
 }
 
+/// Splits a `lint_name = "level"` line of a lint configuration file.
+fn parse_lint_config_entry(entry: &str) -> Option<(&str, &str)> {
+    let mut parts = entry.splitn(2, '=');
+    let name = parts.next()?.trim();
+    let level = parts.next()?.trim();
+    let level = if level.len() >= 2 && level.starts_with('"') && level.ends_with('"') {
+        &level[1..level.len() - 1]
+    } else {
+        level
+    };
+    if name.is_empty() || level.is_empty() || name.contains(char::is_whitespace) {
+        return None;
+    }
+    Some((name, level))
+}
+
 impl<HCX> HashStable<HCX> for LintId {
     #[inline]
     fn hash_stable(&self, hcx: &mut HCX, hasher: &mut StableHasher) {
@@ -578,12 +578,16 @@ pub enum LintSource {
 
     /// Lint level was set by a command-line flag.
     CommandLine(Symbol),
+
+    /// Lint level was set by an entry in the `-Z lint-config` file.
+    ConfigFile(Symbol, Span),
 }
 
 impl_stable_hash_for!(enum self::LintSource {
     Default,
     Node(name, span, reason),
-    CommandLine(text)
+    CommandLine(text),
+    ConfigFile(name, span)
 });
 
 pub type LevelSource = (Level, LintSource);
@@ -704,6 +708,16 @@ pub fn struct_lint_level<'a>(sess: &'a Session,
                              hyphen_case_flag_val));
             }
         }
+        LintSource::ConfigFile(lint_config_name, src) => {
+            sess.diag_span_note_once(&mut err, DiagnosticMessageId::from(lint),
+                                     src, "lint level defined in the lint configuration file");
+            if lint_config_name.as_str() != name {
+                let level_str = level.as_str();
+                sess.diag_note_once(&mut err, DiagnosticMessageId::from(lint),
+                                    &format!("`{} = \"{}\"` implied by `{} = \"{}\"`",
+                                             name, level_str, lint_config_name, level_str));
+            }
+        }
         LintSource::Node(lint_attr_name, src, reason) => {
             if let Some(rationale) = reason {
                 err.note(&rationale.as_str());
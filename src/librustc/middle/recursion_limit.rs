@@ -4,6 +4,9 @@
 // on how deeply they recurse to prevent stack overflow. Users can override
 // this via an attribute on the crate like `#![recursion_limit="22"]`. This pass
 // just peeks and looks for that attribute.
+//
+// The number of steps const evaluation may take is limited the same way, by
+// `#![const_eval_limit="N"]`, which `-Z const-eval-limit=N` overrides.
 
 use crate::session::Session;
 use syntax::ast;
@@ -14,6 +17,10 @@ use rustc_data_structures::sync::Once;
 pub fn update_limits(sess: &Session, krate: &ast::Crate) {
     update_limit(krate, &sess.recursion_limit, sym::recursion_limit, 128);
     update_limit(krate, &sess.type_length_limit, sym::type_length_limit, 1048576);
+    match sess.opts.debugging_opts.const_eval_limit {
+        Some(n) => sess.const_eval_limit.set(n),
+        None => update_limit(krate, &sess.const_eval_limit, sym::const_eval_limit, 20_000_000),
+    }
 }
 
 fn update_limit(krate: &ast::Crate, limit: &Once<usize>, name: Symbol, default: usize) {
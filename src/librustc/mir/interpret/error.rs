@@ -568,8 +568,8 @@ impl fmt::Debug for UnsupportedOpInfo<'tcx> {
 pub enum ResourceExhaustionInfo {
     /// The stack grew too big.
     StackFrameLimitReached,
-    /// The program ran into an infinite loop.
-    InfiniteLoop,
+    /// The program took more steps than the configured `const_eval_limit`.
+    StepLimitReached,
 }
 
 impl fmt::Debug for ResourceExhaustionInfo {
@@ -578,9 +578,8 @@ impl fmt::Debug for ResourceExhaustionInfo {
         match self {
             StackFrameLimitReached =>
                 write!(f, "reached the configured maximum number of stack frames"),
-            InfiniteLoop =>
-                write!(f, "duplicate interpreter state observed here, const evaluation will never \
-                    terminate"),
+            StepLimitReached =>
+                write!(f, "reached the configured maximum number of steps for const evaluation"),
         }
     }
 }
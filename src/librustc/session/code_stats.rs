@@ -1,6 +1,10 @@
 use rustc_target::abi::{Align, Size};
-use rustc_data_structures::fx::{FxHashSet};
+use rustc_data_structures::fx::{FxHashMap, FxHashSet};
+use rustc_serialize::json::as_pretty_json;
 use std::cmp::{self, Ordering};
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
 
 #[derive(Clone, PartialEq, Eq, Hash, Debug)]
 pub struct VariantInfo {
@@ -44,9 +48,54 @@ pub struct TypeSizeInfo {
     pub variants: Vec<VariantInfo>,
 }
 
+/// The LLVM IR generated for a single monomorphized function.
+#[derive(PartialEq, Eq, Hash, Debug)]
+pub struct FnSizeInfo {
+    pub symbol_name: String,
+    /// The name of the crate that defines the originating item.
+    pub crate_name: String,
+    /// The path of the (possibly generic) item this function was instantiated from.
+    pub item_path: String,
+    pub instruction_count: u64,
+}
+
 #[derive(PartialEq, Eq, Debug, Default)]
 pub struct CodeStats {
     type_sizes: FxHashSet<TypeSizeInfo>,
+    /// Keyed by symbol name. Local copies of the same function in several
+    /// CGUs are added up.
+    fn_sizes: FxHashMap<String, FnSizeInfo>,
+}
+
+// The following data types are the JSON output of `--emit=llvm-stats`.
+
+#[derive(RustcEncodable)]
+struct LlvmStatsReport<'a> {
+    /// Bumped whenever the layout of the report changes.
+    format_version: u32,
+    crates: Vec<LlvmStatsCrate<'a>>,
+}
+
+#[derive(RustcEncodable)]
+struct LlvmStatsCrate<'a> {
+    name: &'a str,
+    instruction_count: u64,
+    items: Vec<LlvmStatsItem<'a>>,
+}
+
+#[derive(RustcEncodable)]
+struct LlvmStatsItem<'a> {
+    path: &'a str,
+    /// The number of monomorphizations of this item in the current crate.
+    instantiations: usize,
+    instruction_count: u64,
+    functions: Vec<LlvmStatsFn<'a>>,
+}
+
+#[derive(RustcEncodable)]
+struct LlvmStatsFn<'a> {
+    symbol_name: &'a str,
+    instruction_count: u64,
 }
 
 impl CodeStats {
@@ -76,6 +125,74 @@ impl CodeStats {
         self.type_sizes.insert(info);
     }
 
+    pub fn record_fn_size(&mut self,
+                          symbol_name: String,
+                          crate_name: String,
+                          item_path: String,
+                          instruction_count: u64) {
+        // Copies of the same `#[inline]` function in several CGUs are all
+        // separately optimized and emitted, so they all count.
+        self.fn_sizes.entry(symbol_name.clone())
+            .and_modify(|info| info.instruction_count += instruction_count)
+            .or_insert(FnSizeInfo {
+                symbol_name,
+                crate_name,
+                item_path,
+                instruction_count,
+            });
+    }
+
+    /// Writes the function sizes recorded with `record_fn_size` as JSON,
+    /// grouped by source crate and then by originating item. Crates, items
+    /// and functions are sorted by decreasing instruction count, with ties
+    /// broken by name, so that the output is deterministic.
+    pub fn write_llvm_stats(&self, path: &Path) -> io::Result<()> {
+        let mut by_crate: FxHashMap<&str, FxHashMap<&str, Vec<&FnSizeInfo>>> =
+            FxHashMap::default();
+        for info in self.fn_sizes.values() {
+            by_crate.entry(&info.crate_name[..])
+                .or_default()
+                .entry(&info.item_path[..])
+                .or_default()
+                .push(info);
+        }
+
+        let mut crates: Vec<_> = by_crate.into_iter().map(|(name, items)| {
+            let mut items: Vec<_> = items.into_iter().map(|(path, fns)| {
+                let mut functions: Vec<_> = fns.iter().map(|info| LlvmStatsFn {
+                    symbol_name: &info.symbol_name,
+                    instruction_count: info.instruction_count,
+                }).collect();
+                functions.sort_by(|a, b| {
+                    b.instruction_count.cmp(&a.instruction_count)
+                        .then_with(|| a.symbol_name.cmp(b.symbol_name))
+                });
+                LlvmStatsItem {
+                    path,
+                    instantiations: functions.len(),
+                    instruction_count: functions.iter().map(|f| f.instruction_count).sum(),
+                    functions,
+                }
+            }).collect();
+            items.sort_by(|a, b| {
+                b.instruction_count.cmp(&a.instruction_count).then_with(|| a.path.cmp(b.path))
+            });
+            LlvmStatsCrate {
+                name,
+                instruction_count: items.iter().map(|i| i.instruction_count).sum(),
+                items,
+            }
+        }).collect();
+        crates.sort_by(|a, b| {
+            b.instruction_count.cmp(&a.instruction_count).then_with(|| a.name.cmp(b.name))
+        });
+
+        let report = LlvmStatsReport { format_version: 1, crates };
+        let mut file = BufWriter::new(File::create(path)?);
+        writeln!(file, "{}", as_pretty_json(&report))?;
+        file.flush()
+    }
+
     pub fn print_type_sizes(&self) {
         let mut sorted: Vec<_> = self.type_sizes.iter().collect();
 
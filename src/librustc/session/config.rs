@@ -139,6 +139,30 @@ pub enum SymbolManglingVersion {
 
 impl_stable_hash_via_hash!(SymbolManglingVersion);
 
+/// How debuginfo is split out of the object files, with `-C split-debuginfo`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SplitDebuginfo {
+    /// The debuginfo stays in the object files and is linked into the output.
+    Off,
+    /// The debuginfo of each codegen unit is written to a `.dwo` file, and the `.dwo` files are
+    /// packaged into a `.dwp` file next to the linked output.
+    Packed,
+    /// The debuginfo of each codegen unit is written to a `.dwo` file, which the linked output
+    /// refers to.
+    Unpacked,
+}
+
+/// What the linker strips from the output, with `-C strip`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Strip {
+    /// Nothing is stripped.
+    None,
+    /// The debuginfo is stripped, the symbol table is kept.
+    Debuginfo,
+    /// The debuginfo and the symbol table are stripped.
+    Symbols,
+}
+
 #[derive(Clone, Copy, PartialEq, Hash)]
 pub enum DebugInfo {
     None,
@@ -156,6 +180,7 @@ pub enum OutputType {
     Object,
     Exe,
     DepInfo,
+    LlvmStats,
 }
 
 impl_stable_hash_via_hash!(OutputType);
@@ -163,7 +188,10 @@ impl_stable_hash_via_hash!(OutputType);
 impl OutputType {
     fn is_compatible_with_codegen_units_and_single_output_file(&self) -> bool {
         match *self {
-            OutputType::Exe | OutputType::DepInfo | OutputType::Metadata => true,
+            OutputType::Exe
+            | OutputType::DepInfo
+            | OutputType::Metadata
+            | OutputType::LlvmStats => true,
             OutputType::Bitcode
             | OutputType::Assembly
             | OutputType::LlvmAssembly
@@ -182,6 +210,7 @@ impl OutputType {
             OutputType::Metadata => "metadata",
             OutputType::Exe => "link",
             OutputType::DepInfo => "dep-info",
+            OutputType::LlvmStats => "llvm-stats",
         }
     }
 
@@ -195,13 +224,14 @@ impl OutputType {
             "metadata" => OutputType::Metadata,
             "link" => OutputType::Exe,
             "dep-info" => OutputType::DepInfo,
+            "llvm-stats" => OutputType::LlvmStats,
             _ => return None,
         })
     }
 
     fn shorthands_display() -> String {
         format!(
-            "`{}`, `{}`, `{}`, `{}`, `{}`, `{}`, `{}`, `{}`",
+            "`{}`, `{}`, `{}`, `{}`, `{}`, `{}`, `{}`, `{}`, `{}`",
             OutputType::Bitcode.shorthand(),
             OutputType::Assembly.shorthand(),
             OutputType::LlvmAssembly.shorthand(),
@@ -210,6 +240,7 @@ impl OutputType {
             OutputType::Metadata.shorthand(),
             OutputType::Exe.shorthand(),
             OutputType::DepInfo.shorthand(),
+            OutputType::LlvmStats.shorthand(),
         )
     }
 
@@ -222,6 +253,7 @@ impl OutputType {
             OutputType::Object => "o",
             OutputType::Metadata => "rmeta",
             OutputType::DepInfo => "d",
+            OutputType::LlvmStats => "llvm-stats.json",
             OutputType::Exe => "",
         }
     }
@@ -240,6 +272,11 @@ pub enum ErrorOutputType {
         /// human output.
         json_rendered: HumanReadableErrorType,
     },
+    /// A single SARIF 2.1.0 log for the whole compilation, consumed by code-scanning tools.
+    Sarif {
+        /// Render the SARIF log in a human readable way (with indents and newlines).
+        pretty: bool,
+    },
 }
 
 impl Default for ErrorOutputType {
@@ -291,7 +328,8 @@ impl OutputTypes {
             | OutputType::LlvmAssembly
             | OutputType::Mir
             | OutputType::Object
-            | OutputType::Exe => true,
+            | OutputType::Exe
+            | OutputType::LlvmStats => true,
             OutputType::Metadata | OutputType::DepInfo => false,
         })
     }
@@ -461,6 +499,7 @@ pub enum PrintRequest {
     TlsModels,
     TargetSpec,
     NativeStaticLibs,
+    PublicApi,
 }
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -570,6 +609,12 @@ impl OutputFilenames {
         path
     }
 
+    /// The `.dwo` file that the debuginfo of a codegen unit is written to with
+    /// `-C split-debuginfo`.
+    pub fn split_dwarf_path(&self, codegen_unit_name: Option<&str>) -> PathBuf {
+        self.temp_path_ext("dwo", codegen_unit_name)
+    }
+
     pub fn with_extension(&self, extension: &str) -> PathBuf {
         self.out_directory
             .join(&self.filestem())
@@ -837,12 +882,16 @@ macro_rules! options {
             Some("one of: `disabled`, `trampolines`, or `aliases`");
         pub const parse_symbol_mangling_version: Option<&str> =
             Some("either `legacy` or `v0` (RFC 2603)");
+        pub const parse_split_debuginfo: Option<&str> =
+            Some("one of: `off`, `packed`, or `unpacked`");
+        pub const parse_strip: Option<&str> =
+            Some("one of: `none`, `debuginfo`, or `symbols`");
     }
 
     #[allow(dead_code)]
     mod $mod_set {
         use super::{$struct_name, Passes, Sanitizer, LtoCli, LinkerPluginLto, SwitchWithOptPath,
-            SymbolManglingVersion};
+            SymbolManglingVersion, SplitDebuginfo, Strip};
         use rustc_target::spec::{LinkerFlavor, MergeFunctions, PanicStrategy, RelroLevel};
         use std::path::PathBuf;
         use std::str::FromStr;
@@ -1105,6 +1154,26 @@ macro_rules! options {
             true
         }
 
+        fn parse_split_debuginfo(slot: &mut SplitDebuginfo, v: Option<&str>) -> bool {
+            *slot = match v {
+                Some("off") => SplitDebuginfo::Off,
+                Some("packed") => SplitDebuginfo::Packed,
+                Some("unpacked") => SplitDebuginfo::Unpacked,
+                _ => return false,
+            };
+            true
+        }
+
+        fn parse_strip(slot: &mut Strip, v: Option<&str>) -> bool {
+            *slot = match v {
+                Some("none") => Strip::None,
+                Some("debuginfo") => Strip::Debuginfo,
+                Some("symbols") => Strip::Symbols,
+                _ => return false,
+            };
+            true
+        }
+
         fn parse_switch_with_opt_path(slot: &mut SwitchWithOptPath, v: Option<&str>) -> bool {
             *slot = match v {
                 None => SwitchWithOptPath::Enabled(None),
@@ -1219,6 +1288,12 @@ options! {CodegenOptions, CodegenSetter, basic_codegen_options,
         "compile the program with profiling instrumentation"),
     profile_use: Option<PathBuf> = (None, parse_opt_pathbuf, [TRACKED],
         "use the given `.profdata` file for profile-guided optimization"),
+    split_debuginfo: SplitDebuginfo = (SplitDebuginfo::Off, parse_split_debuginfo, [TRACKED],
+        "write the debuginfo of each codegen unit to a `.dwo` file (`unpacked`), and package \
+         them into a `.dwp` file (`packed`), on ELF targets"),
+    strip: Strip = (Strip::None, parse_strip, [UNTRACKED],
+        "tell the linker to strip the debuginfo (`debuginfo`), or the debuginfo and the \
+         symbol table (`symbols`), from the output"),
 }
 
 options! {DebuggingOptions, DebuggingSetter, basic_debugging_options,
@@ -1304,6 +1379,9 @@ options! {DebuggingOptions, DebuggingSetter, basic_debugging_options,
         "enable incremental compilation support for queries (experimental)"),
     incremental_info: bool = (false, parse_bool, [UNTRACKED],
         "print high-level information about incremental reuse (or the lack thereof)"),
+    incremental_cache_limit: Option<usize> = (None, parse_opt_uint, [UNTRACKED],
+        "maximum size of the incremental compilation directory in megabytes; the least \
+        recently used cache entries are evicted once it grows beyond this size"),
     incremental_dump_hash: bool = (false, parse_bool, [UNTRACKED],
         "dump hash information in textual format to stdout"),
     incremental_verify_ich: bool = (false, parse_bool, [UNTRACKED],
@@ -1450,7 +1528,8 @@ options! {DebuggingOptions, DebuggingSetter, basic_debugging_options,
         parse_switch_with_opt_path, [UNTRACKED],
         "run the self profiler and output the raw event data"),
     self_profile_events: Option<Vec<String>> = (None, parse_opt_comma_list, [UNTRACKED],
-        "specifies which kinds of events get recorded by the self profiler"),
+        "specifies which kinds of events get recorded by the self profiler;
+        for example: `-Z self-profile-events=default,llvm-optimize,llvm-lto,llvm-codegen,cgu-items`"),
     emit_stack_sizes: bool = (false, parse_bool, [UNTRACKED],
         "emits a section containing stack size metadata"),
     plt: Option<bool> = (None, parse_opt_bool, [TRACKED],
@@ -1467,6 +1546,11 @@ options! {DebuggingOptions, DebuggingSetter, basic_debugging_options,
         "which mangling version to use for symbol names"),
     binary_dep_depinfo: bool = (false, parse_bool, [TRACKED],
         "include artifacts (sysroot, crate dependencies) used during compilation in dep-info"),
+    lint_config: Option<PathBuf> = (None, parse_opt_pathbuf, [TRACKED],
+        "read lint levels from the given file; `-A/-W/-D/-F` flags take precedence over it"),
+    const_eval_limit: Option<usize> = (None, parse_opt_uint, [TRACKED],
+        "the maximum number of steps const evaluation may take, or 0 for no limit; \
+         overrides `#![const_eval_limit]`"),
     insert_sideeffect: bool = (false, parse_bool, [TRACKED],
         "fix undefined behavior when a thread doesn't eventually make progress \
          (such as entering an empty infinite loop) by inserting llvm.sideeffect"),
@@ -1738,7 +1822,7 @@ pub fn rustc_short_optgroups() -> Vec<RustcOptGroup> {
             "emit",
             "Comma separated list of types of output for \
              the compiler to emit",
-            "[asm|llvm-bc|llvm-ir|obj|metadata|link|dep-info|mir]",
+            "[asm|llvm-bc|llvm-ir|obj|metadata|link|dep-info|mir|llvm-stats]",
         ),
         opt::multi_s(
             "",
@@ -1746,7 +1830,8 @@ pub fn rustc_short_optgroups() -> Vec<RustcOptGroup> {
             "Compiler information to print on stdout",
             "[crate-name|file-names|sysroot|cfg|target-list|\
              target-cpus|target-features|relocation-models|\
-             code-models|tls-models|target-spec-json|native-static-libs]",
+             code-models|tls-models|target-spec-json|native-static-libs|\
+             public-api]",
         ),
         opt::flagmulti_s("g", "", "Equivalent to -C debuginfo=2"),
         opt::flagmulti_s("O", "", "Equivalent to -C opt-level=2"),
@@ -1814,7 +1899,7 @@ pub fn rustc_optgroups() -> Vec<RustcOptGroup> {
             "",
             "error-format",
             "How errors and other messages are produced",
-            "human|json|short",
+            "human|json|short|sarif|pretty-sarif",
         ),
         opt::multi_s(
             "",
@@ -2002,12 +2087,14 @@ pub fn parse_error_format(
             Some("json") => ErrorOutputType::Json { pretty: false, json_rendered },
             Some("pretty-json") => ErrorOutputType::Json { pretty: true, json_rendered },
             Some("short") => ErrorOutputType::HumanReadable(HumanReadableErrorType::Short(color)),
+            Some("sarif") => ErrorOutputType::Sarif { pretty: false },
+            Some("pretty-sarif") => ErrorOutputType::Sarif { pretty: true },
 
             Some(arg) => early_error(
                 ErrorOutputType::HumanReadable(HumanReadableErrorType::Default(color)),
                 &format!(
-                    "argument for `--error-format` must be `human`, `json` or \
-                     `short` (instead was `{}`)",
+                    "argument for `--error-format` must be `human`, `json`, \
+                     `short` or `sarif` (instead was `{}`)",
                     arg
                 ),
             ),
@@ -2093,6 +2180,12 @@ pub fn build_session_options_and_crate_config(
                 "`--error-format=human-annotate-rs` is unstable",
             );
         }
+        if let ErrorOutputType::Sarif { .. } = error_format {
+            early_error(
+                ErrorOutputType::Json { pretty: false, json_rendered },
+                "`--error-format=sarif` is unstable",
+            );
+        }
     }
 
     let mut output_types = BTreeMap::new();
@@ -2111,6 +2204,9 @@ pub fn build_session_options_and_crate_config(
                         ),
                     ),
                 );
+                if output_type == OutputType::LlvmStats && !debugging_opts.unstable_options {
+                    early_error(error_format, "`--emit=llvm-stats` is unstable");
+                }
                 let path = parts.next().map(PathBuf::from);
                 output_types.insert(output_type, path);
             }
@@ -2398,6 +2494,17 @@ pub fn build_session_options_and_crate_config(
                 );
             }
         }
+        "public-api" => {
+            if is_unstable_enabled {
+                PrintRequest::PublicApi
+            } else {
+                early_error(
+                    error_format,
+                    "the `-Z unstable-options` flag must also be passed to \
+                     enable the public-api print option",
+                );
+            }
+        }
         req => early_error(error_format, &format!("unknown print request `{}`", req)),
     }));
 
@@ -2644,7 +2751,7 @@ mod dep_tracking {
     use std::collections::hash_map::DefaultHasher;
     use super::{CrateType, DebugInfo, ErrorOutputType, OptLevel, OutputTypes,
                 Passes, Sanitizer, LtoCli, LinkerPluginLto, SwitchWithOptPath,
-                SymbolManglingVersion};
+                SymbolManglingVersion, SplitDebuginfo};
     use rustc_target::spec::{MergeFunctions, PanicStrategy, RelroLevel, TargetTriple};
     use syntax::edition::Edition;
     use syntax::feature_gate::UnstableFeatures;
@@ -2704,6 +2811,7 @@ mod dep_tracking {
     impl_dep_tracking_hash_via_hash!(OptLevel);
     impl_dep_tracking_hash_via_hash!(LtoCli);
     impl_dep_tracking_hash_via_hash!(DebugInfo);
+    impl_dep_tracking_hash_via_hash!(SplitDebuginfo);
     impl_dep_tracking_hash_via_hash!(UnstableFeatures);
     impl_dep_tracking_hash_via_hash!(OutputTypes);
     impl_dep_tracking_hash_via_hash!(cstore::NativeLibraryKind);
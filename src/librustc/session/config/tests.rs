@@ -6,7 +6,8 @@ use crate::session::config::{
     build_session_options_and_crate_config,
     to_crate_config
 };
-use crate::session::config::{LtoCli, LinkerPluginLto, SwitchWithOptPath, ExternEntry};
+use crate::session::config::{LtoCli, LinkerPluginLto, SwitchWithOptPath, ExternEntry,
+                             SplitDebuginfo, Strip};
 use crate::session::build_session;
 use crate::session::search_paths::SearchPath;
 use std::collections::{BTreeMap, BTreeSet};
@@ -453,6 +454,9 @@ fn test_codegen_options_tracking_hash() {
     opts.cg.incremental = Some(String::from("abc"));
     assert_eq!(reference.dep_tracking_hash(), opts.dep_tracking_hash());
 
+    opts.cg.strip = Strip::Symbols;
+    assert_eq!(reference.dep_tracking_hash(), opts.dep_tracking_hash());
+
     // Make sure changing a [TRACKED] option changes the hash
     opts = reference.clone();
     opts.cg.lto = LtoCli::Fat;
@@ -557,6 +561,10 @@ fn test_codegen_options_tracking_hash() {
     opts = reference.clone();
     opts.cg.linker_plugin_lto = LinkerPluginLto::LinkerPluginAuto;
     assert!(reference.dep_tracking_hash() != opts.dep_tracking_hash());
+
+    opts = reference.clone();
+    opts.cg.split_debuginfo = SplitDebuginfo::Packed;
+    assert!(reference.dep_tracking_hash() != opts.dep_tracking_hash());
 }
 
 #[test]
@@ -595,6 +603,8 @@ fn test_debugging_options_tracking_hash() {
     assert_eq!(reference.dep_tracking_hash(), opts.dep_tracking_hash());
     opts.debugging_opts.incremental = Some(String::from("abc"));
     assert_eq!(reference.dep_tracking_hash(), opts.dep_tracking_hash());
+    opts.debugging_opts.incremental_cache_limit = Some(64);
+    assert_eq!(reference.dep_tracking_hash(), opts.dep_tracking_hash());
     opts.debugging_opts.dump_dep_graph = true;
     assert_eq!(reference.dep_tracking_hash(), opts.dep_tracking_hash());
     opts.debugging_opts.query_dep_graph = true;
@@ -682,6 +692,14 @@ fn test_debugging_options_tracking_hash() {
     opts = reference.clone();
     opts.debugging_opts.symbol_mangling_version = SymbolManglingVersion::V0;
     assert!(reference.dep_tracking_hash() != opts.dep_tracking_hash());
+
+    opts = reference.clone();
+    opts.debugging_opts.lint_config = Some(PathBuf::from("lints.toml"));
+    assert!(reference.dep_tracking_hash() != opts.dep_tracking_hash());
+
+    opts = reference.clone();
+    opts.debugging_opts.const_eval_limit = Some(0);
+    assert!(reference.dep_tracking_hash() != opts.dep_tracking_hash());
 }
 
 #[test]
@@ -7,7 +7,7 @@ use rustc_data_structures::fingerprint::Fingerprint;
 
 use crate::lint;
 use crate::lint::builtin::BuiltinLintDiagnostics;
-use crate::session::config::{OutputType, PrintRequest, SwitchWithOptPath};
+use crate::session::config::{OutputType, PrintRequest, SplitDebuginfo, SwitchWithOptPath};
 use crate::session::search_paths::{PathKind, SearchPath};
 use crate::util::nodemap::{FxHashMap, FxHashSet};
 use crate::util::common::{duration_to_secs_str, ErrorReported};
@@ -22,6 +22,7 @@ use errors::{DiagnosticBuilder, DiagnosticId, Applicability};
 use errors::emitter::{Emitter, EmitterWriter};
 use errors::emitter::HumanReadableErrorType;
 use errors::annotate_snippet_emitter_writer::{AnnotateSnippetEmitterWriter};
+use errors::sarif_emitter::SarifEmitter;
 use syntax::ast::{self, NodeId};
 use syntax::edition::Edition;
 use syntax::ext::allocator::AllocatorKind;
@@ -105,6 +106,10 @@ pub struct Session {
     /// The maximum length of types during monomorphization.
     pub type_length_limit: Once<usize>,
 
+    /// The maximum number of interpreter steps allowed in const eval,
+    /// where 0 means no limit.
+    pub const_eval_limit: Once<usize>,
+
     /// The maximum number of stackframes allowed in const eval.
     pub const_eval_stack_frame_limit: usize,
 
@@ -621,6 +626,40 @@ impl Session {
             .panic
             .unwrap_or(self.target.target.options.panic_strategy)
     }
+    /// Returns how debuginfo is split out of the object files. Without debuginfo there is
+    /// nothing to split, so this is `Off` unless `-C debuginfo` is also enabled.
+    pub fn split_debuginfo(&self) -> SplitDebuginfo {
+        if self.opts.debuginfo == config::DebugInfo::None {
+            SplitDebuginfo::Off
+        } else {
+            self.opts.cg.split_debuginfo
+        }
+    }
+
+    /// Whether the debuginfo of the codegen units is written to `.dwo` files, leaving skeleton
+    /// units in their object files. Only the object files LLVM assembles itself into machine code
+    /// can have their debuginfo split out.
+    pub fn split_dwarf_objects(&self) -> bool {
+        let options = &self.target.target.options;
+        let emit_obj = self.opts.output_types.contains_key(&OutputType::Object) ||
+            self.opts.output_types.contains_key(&OutputType::Exe);
+        self.split_debuginfo() != SplitDebuginfo::Off &&
+            emit_obj &&
+            !options.obj_is_bitcode &&
+            !self.opts.cg.linker_plugin_lto.enabled() &&
+            !options.no_integrated_as &&
+            !self.opts.cg.no_integrated_as
+    }
+
+    /// Whether the target produces ELF object files, the only format whose debuginfo can be
+    /// split into `.dwo` files.
+    pub fn target_is_elf(&self) -> bool {
+        let options = &self.target.target.options;
+        let arch = &self.target.target.arch;
+        !options.is_like_osx && !options.is_like_windows && !options.is_like_emscripten &&
+            !arch.starts_with("wasm") && !arch.starts_with("nvptx")
+    }
+
     pub fn fewer_names(&self) -> bool {
         let more_names = self.opts
             .output_types
@@ -1052,6 +1091,12 @@ fn default_emitter(
                 external_macro_backtrace,
             ).ui_testing(sopts.debugging_opts.ui_testing),
         ),
+        (config::ErrorOutputType::Sarif { pretty }, None) => Box::new(
+            SarifEmitter::stderr(Some(registry), Some(source_map.clone()), pretty),
+        ),
+        (config::ErrorOutputType::Sarif { pretty }, Some(dst)) => Box::new(
+            SarifEmitter::new(dst, Some(registry), Some(source_map.clone()), pretty),
+        ),
     }
 }
 
@@ -1215,6 +1260,7 @@ fn build_session_(
         features: Once::new(),
         recursion_limit: Once::new(),
         type_length_limit: Once::new(),
+        const_eval_limit: Once::new(),
         const_eval_stack_frame_limit: 100,
         next_node_id: OneThread::new(Cell::new(NodeId::from_u32(1))),
         allocator_kind: Once::new(),
@@ -1283,11 +1329,33 @@ fn validate_commandline_args_with_session_available(sess: &Session) {
     if sess.opts.cg.profile_generate.enabled() &&
        sess.target.target.options.is_like_msvc &&
        sess.panic_strategy() == PanicStrategy::Unwind &&
-       sess.opts.prints.iter().all(|&p| p == PrintRequest::NativeStaticLibs) {
+       sess.opts.prints.iter().all(|&p| p == PrintRequest::NativeStaticLibs ||
+                                      p == PrintRequest::PublicApi) {
         sess.err("Profile-guided optimization does not yet work in conjunction \
                   with `-Cpanic=unwind` on Windows when targeting MSVC. \
                   See https://github.com/rust-lang/rust/issues/61002 for details.");
     }
+
+    // Split debuginfo relies on the `.dwo`/`.dwp` formats of DWARF on ELF, other object formats
+    // have their own ways of keeping debuginfo out of the binary.
+    if sess.split_debuginfo() != SplitDebuginfo::Off && !sess.target_is_elf() {
+        sess.err(&format!("`-C split-debuginfo` is not supported for the `{}` target, \
+                           only for targets that produce ELF object files",
+                          sess.opts.target_triple));
+    }
+
+    // Fat LTO merges the codegen units into one module after their debuginfo was created, so
+    // the skeleton units would refer to `.dwo` files of codegen units that are never written.
+    if sess.split_debuginfo() != SplitDebuginfo::Off && sess.lto() == config::Lto::Fat {
+        sess.err("`-C split-debuginfo` cannot be used together with fat LTO");
+    }
+
+    // Stripping removes the skeleton units `llvm-dwp` finds the `.dwo` files through, and the
+    // `.dwo` files are deleted once packaged, so none of the debuginfo would be kept.
+    if sess.split_debuginfo() == SplitDebuginfo::Packed &&
+       sess.opts.cg.strip != config::Strip::None {
+        sess.err("`-C split-debuginfo=packed` cannot be used together with `-C strip`");
+    }
 }
 
 /// Hash value constructed out of all the `-C metadata` arguments passed to the
@@ -1348,6 +1416,8 @@ pub fn early_error(output: config::ErrorOutputType, msg: &str) -> ! {
         }
         config::ErrorOutputType::Json { pretty, json_rendered } =>
             Box::new(JsonEmitter::basic(pretty, json_rendered, false)),
+        config::ErrorOutputType::Sarif { pretty } =>
+            Box::new(SarifEmitter::stderr(None, None, pretty)),
     };
     let handler = errors::Handler::with_emitter(true, None, emitter);
     handler.struct_fatal(msg).emit();
@@ -1362,6 +1432,8 @@ pub fn early_warn(output: config::ErrorOutputType, msg: &str) {
         }
         config::ErrorOutputType::Json { pretty, json_rendered } =>
             Box::new(JsonEmitter::basic(pretty, json_rendered, false)),
+        config::ErrorOutputType::Sarif { pretty } =>
+            Box::new(SarifEmitter::stderr(None, None, pretty)),
     };
     let handler = errors::Handler::with_emitter(true, None, emitter);
     handler.struct_warn(msg).emit();
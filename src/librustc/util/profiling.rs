@@ -36,6 +36,10 @@ bitflags! {
         const QUERY_CACHE_HITS   = 1 << 2;
         const QUERY_BLOCKED      = 1 << 3;
         const INCR_CACHE_LOADS   = 1 << 4;
+        const LLVM_OPTIMIZE      = 1 << 5;
+        const LLVM_LTO           = 1 << 6;
+        const LLVM_CODEGEN       = 1 << 7;
+        const CGU_ITEMS          = 1 << 8;
 
         const DEFAULT = Self::GENERIC_ACTIVITIES.bits |
                         Self::QUERY_PROVIDERS.bits |
@@ -51,13 +55,35 @@ bitflags! {
 const EVENT_FILTERS_BY_NAME: &[(&str, EventFilter)] = &[
     ("none", EventFilter::NONE),
     ("all", EventFilter::ALL),
+    ("default", EventFilter::DEFAULT),
     ("generic-activity", EventFilter::GENERIC_ACTIVITIES),
     ("query-provider", EventFilter::QUERY_PROVIDERS),
     ("query-cache-hit", EventFilter::QUERY_CACHE_HITS),
     ("query-blocked" , EventFilter::QUERY_BLOCKED),
     ("incr-cache-load", EventFilter::INCR_CACHE_LOADS),
+    ("llvm-optimize", EventFilter::LLVM_OPTIMIZE),
+    ("llvm-lto", EventFilter::LLVM_LTO),
+    ("llvm-codegen", EventFilter::LLVM_CODEGEN),
+    ("cgu-items", EventFilter::CGU_ITEMS),
 ];
 
+/// Separates the label of an event id from its arguments, and the arguments
+/// from each other, e.g. `LLVM_module_optimize\x1ecrate.7rcbfp3g-cgu.0`. The
+/// ASCII record separator doesn't occur in CGU names or item paths, so tools
+/// processing the profile can split event ids on it.
+pub const EVENT_ARG_SEPARATOR: char = '\x1e';
+
+fn event_id_with_args<'a, I>(label: &str, args: I) -> String
+    where I: IntoIterator<Item = &'a str>
+{
+    let mut event_id = label.to_owned();
+    for arg in args {
+        event_id.push(EVENT_ARG_SEPARATOR);
+        event_id.push_str(arg);
+    }
+    event_id
+}
+
 fn thread_id_to_u64(tid: ThreadId) -> u64 {
     unsafe { mem::transmute::<ThreadId, u64>(tid) }
 }
@@ -216,6 +242,70 @@ impl SelfProfilerRef {
         })
     }
 
+    /// Start profiling an LLVM optimization activity on a single codegen
+    /// unit. The event id carries the name of the CGU as an argument.
+    /// Profiling continues until the TimingGuard returned from this call is
+    /// dropped.
+    #[inline(always)]
+    pub fn llvm_optimize(&self, event_label: &str, cgu_name: &str) -> TimingGuard<'_> {
+        self.cgu_activity(EventFilter::LLVM_OPTIMIZE, event_label, cgu_name)
+    }
+
+    /// Start profiling an LTO activity on a single codegen unit. See
+    /// `llvm_optimize`.
+    #[inline(always)]
+    pub fn llvm_lto(&self, event_label: &str, cgu_name: &str) -> TimingGuard<'_> {
+        self.cgu_activity(EventFilter::LLVM_LTO, event_label, cgu_name)
+    }
+
+    /// Start profiling the emission of machine code, assembly or bitcode for
+    /// a single codegen unit. See `llvm_optimize`.
+    #[inline(always)]
+    pub fn llvm_codegen(&self, event_label: &str, cgu_name: &str) -> TimingGuard<'_> {
+        self.cgu_activity(EventFilter::LLVM_CODEGEN, event_label, cgu_name)
+    }
+
+    /// Record which mono items a codegen unit holds, as an instant event whose
+    /// arguments are the name of the CGU followed by the items. This allows
+    /// the per-CGU LLVM events above to be attributed to source items.
+    ///
+    /// The item names are only computed if the `cgu-items` event filter is
+    /// enabled, since they can be expensive to produce.
+    #[inline(always)]
+    pub fn cgu_items<F>(&self, cgu_name: &str, items: F)
+        where F: FnOnce() -> Vec<String>
+    {
+        self.non_guard_generic_event(
+            |profiler| profiler.generic_activity_event_kind,
+            |profiler| {
+                let items = items();
+                let args = Some(cgu_name).into_iter()
+                    .chain(items.iter().map(|item| &item[..]));
+                profiler.profiler.alloc_string(&event_id_with_args("codegen_unit_items", args)[..])
+            },
+            EventFilter::CGU_ITEMS,
+            TimestampKind::Instant,
+        );
+    }
+
+    #[inline(always)]
+    fn cgu_activity(
+        &self,
+        event_filter: EventFilter,
+        event_label: &str,
+        cgu_name: &str,
+    ) -> TimingGuard<'_> {
+        self.exec(event_filter, |profiler| {
+            let event_id = event_id_with_args(event_label, Some(cgu_name));
+            let event_id = profiler.profiler.alloc_string(&event_id[..]);
+            TimingGuard::start(
+                profiler,
+                profiler.generic_activity_event_kind,
+                event_id
+            )
+        })
+    }
+
     #[inline(always)]
     fn non_guard_query_event(
         &self,
@@ -535,6 +535,8 @@ pub(crate) fn run_pass_manager(cgcx: &CodegenContext<LlvmCodegenBackend>,
                     module: &ModuleCodegen<ModuleLlvm>,
                     config: &ModuleConfig,
                     thin: bool) {
+    let _cgu_timer = cgcx.prof.llvm_lto("LLVM_lto_run_pass_manager", &module.name);
+
     // Now we have one massive module inside of llmod. Time to run the
     // LTO-specific optimization passes that LLVM provides.
     //
@@ -676,6 +678,7 @@ pub unsafe fn optimize_thin_module(
     thin_module: &mut ThinModule<LlvmCodegenBackend>,
     cgcx: &CodegenContext<LlvmCodegenBackend>,
 ) -> Result<ModuleCodegen<ModuleLlvm>, FatalError> {
+    let _cgu_timer = cgcx.prof.llvm_lto("LLVM_thin_lto_optimize_module", thin_module.name());
     let diag_handler = cgcx.create_diag_handler();
     let tm = (cgcx.tm_factory.0)().map_err(|e| {
         write::llvm_err(&diag_handler, &e)
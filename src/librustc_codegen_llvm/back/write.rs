@@ -26,6 +26,7 @@ use std::ffi::{CString, CStr};
 use std::fs;
 use std::io::{self, Write};
 use std::path::{Path, PathBuf};
+use std::ptr;
 use std::str;
 use std::sync::Arc;
 use std::slice;
@@ -68,10 +69,14 @@ pub fn write_output_file(
         pm: &llvm::PassManager<'ll>,
         m: &'ll llvm::Module,
         output: &Path,
+        dwo_output: Option<&Path>,
         file_type: llvm::FileType) -> Result<(), FatalError> {
     unsafe {
         let output_c = path_to_c_string(output);
-        let result = llvm::LLVMRustWriteOutputFile(target, pm, m, output_c.as_ptr(), file_type);
+        let dwo_output_c = dwo_output.map(path_to_c_string);
+        let dwo_output_ptr = dwo_output_c.as_ref().map_or(ptr::null(), |s| s.as_ptr());
+        let result = llvm::LLVMRustWriteOutputFile(target, pm, m, output_c.as_ptr(),
+                                                   dwo_output_ptr, file_type);
         result.into_result().map_err(|()| {
             let msg = format!("could not write output to {}", output.display());
             llvm_err(handler, &msg)
@@ -307,6 +312,7 @@ pub(crate) unsafe fn optimize(cgcx: &CodegenContext<LlvmCodegenBackend>,
     -> Result<(), FatalError>
 {
     let _timer = cgcx.prof.generic_activity("LLVM_module_optimize");
+    let _cgu_timer = cgcx.prof.llvm_optimize("LLVM_module_optimize", &module.name);
 
     let llmod = module.module_llvm.llmod();
     let llcx = &*module.module_llvm.llcx;
@@ -455,6 +461,7 @@ pub(crate) unsafe fn codegen(cgcx: &CodegenContext<LlvmCodegenBackend>,
     -> Result<CompiledModule, FatalError>
 {
     let _timer = cgcx.prof.generic_activity("LLVM_module_codegen");
+    let _cgu_timer = cgcx.prof.llvm_codegen("LLVM_module_codegen", &module.name);
     {
         let llmod = module.module_llvm.llmod();
         let llcx = &*module.module_llvm.llcx;
@@ -598,15 +605,17 @@ pub(crate) unsafe fn codegen(cgcx: &CodegenContext<LlvmCodegenBackend>,
                     llmod
                 };
                 with_codegen(tm, llmod, config.no_builtins, |cpm| {
-                    write_output_file(diag_handler, tm, cpm, llmod, &path,
+                    write_output_file(diag_handler, tm, cpm, llmod, &path, None,
                                       llvm::FileType::AssemblyFile)
                 })?;
             }
 
             if write_obj {
                 let _timer = cgcx.prof.generic_activity("LLVM_module_codegen_emit_obj");
+                let dwo_out = cgcx.output_filenames.split_dwarf_path(module_name);
+                let dwo_out = if config.emit_dwarf_obj { Some(&*dwo_out) } else { None };
                 with_codegen(tm, llmod, config.no_builtins, |cpm| {
-                    write_output_file(diag_handler, tm, cpm, llmod, &obj_out,
+                    write_output_file(diag_handler, tm, cpm, llmod, &obj_out, dwo_out,
                                       llvm::FileType::ObjectFile)
                 })?;
             } else if asm_to_obj {
@@ -641,6 +650,7 @@ pub(crate) unsafe fn codegen(cgcx: &CodegenContext<LlvmCodegenBackend>,
     Ok(module.into_compiled_module(config.emit_obj,
                                    config.emit_bc,
                                    config.emit_bc_compressed,
+                                   config.emit_dwarf_obj,
                                    &cgcx.output_filenames))
 }
 
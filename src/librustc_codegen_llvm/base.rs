@@ -23,11 +23,11 @@ use crate::builder::Builder;
 use crate::common;
 use crate::context::CodegenCx;
 use rustc::dep_graph;
-use rustc::mir::mono::{Linkage, Visibility};
+use rustc::mir::mono::{Linkage, MonoItem, Visibility};
 use rustc::middle::cstore::{EncodedMetadata};
 use rustc::ty::TyCtxt;
 use rustc::middle::exported_symbols;
-use rustc::session::config::DebugInfo;
+use rustc::session::config::{DebugInfo, OutputType};
 use rustc_codegen_ssa::mono_item::MonoItemExt;
 use rustc_data_structures::small_c_str::SmallCStr;
 
@@ -111,6 +111,14 @@ pub fn compile_codegen_unit(
     let prof_timer = tcx.prof.generic_activity("codegen_module");
     let start_time = Instant::now();
 
+    tcx.prof.cgu_items(&cgu_name.as_str(), || {
+        tcx.codegen_unit(cgu_name)
+            .items_in_deterministic_order(tcx)
+            .iter()
+            .map(|&(mono_item, _)| mono_item.to_string(tcx, true))
+            .collect()
+    });
+
     let dep_node = tcx.codegen_unit(cgu_name).codegen_dep_node(tcx);
     let (module, _) = tcx.dep_graph.with_task(
         dep_node,
@@ -172,6 +180,10 @@ pub fn compile_codegen_unit(
             if cx.sess().opts.debuginfo != DebugInfo::None {
                 cx.debuginfo_finalize();
             }
+
+            if cx.sess().opts.output_types.contains_key(&OutputType::LlvmStats) {
+                record_fn_sizes(&cx, &mono_items);
+            }
         }
 
         ModuleCodegen {
@@ -182,6 +194,54 @@ pub fn compile_codegen_unit(
     }
 }
 
+/// Records the number of LLVM instructions generated for each function of the
+/// codegen unit, for `--emit=llvm-stats`. This counts the unoptimized IR,
+/// which is what rustc is directly responsible for.
+fn record_fn_sizes<'ll, 'tcx>(
+    cx: &CodegenCx<'ll, 'tcx>,
+    mono_items: &[(MonoItem<'tcx>, (Linkage, Visibility))],
+) {
+    let tcx = cx.tcx;
+    let instances = cx.instances.borrow();
+    let mut code_stats = tcx.sess.code_stats.borrow_mut();
+    for &(mono_item, _) in mono_items {
+        let instance = match mono_item {
+            MonoItem::Fn(instance) => instance,
+            MonoItem::Static(..) | MonoItem::GlobalAsm(..) => continue,
+        };
+        let llfn = match instances.get(&instance) {
+            Some(&llfn) => llfn,
+            None => continue,
+        };
+        if unsafe { llvm::LLVMIsDeclaration(llfn) } != 0 {
+            continue;
+        }
+        let def_id = instance.def_id();
+        code_stats.record_fn_size(
+            tcx.symbol_name(instance).name.as_str().to_string(),
+            tcx.crate_name(def_id.krate).as_str().to_string(),
+            tcx.def_path_str(def_id),
+            count_instructions(llfn),
+        );
+    }
+}
+
+fn count_instructions(llfn: &Value) -> u64 {
+    let mut count = 0;
+    unsafe {
+        let mut bb = Some(llvm::LLVMGetFirstBasicBlock(llfn));
+        while let Some(b) = bb {
+            let mut inst = llvm::LLVMGetFirstInstruction(b);
+            while let Some(i) = inst {
+                count += 1;
+                inst = llvm::LLVMGetNextInstruction(i);
+            }
+            bb = llvm::LLVMGetNextBasicBlock(b);
+        }
+    }
+    count
+}
+
 pub fn set_link_section(llval: &Value, attrs: &CodegenFnAttrs) {
     let sect = match attrs.link_section {
         Some(name) => name,
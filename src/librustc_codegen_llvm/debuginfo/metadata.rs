@@ -929,7 +929,15 @@ pub fn compile_unit_metadata(
     let work_dir = SmallCStr::new(&tcx.sess.working_dir.0.to_string_lossy());
     let producer = CString::new(producer).unwrap();
     let flags = "\0";
-    let split_name = "\0";
+
+    // With `-C split-debuginfo`, the skeleton unit left in the object file
+    // refers to the `.dwo` file the rest of the debuginfo is written to.
+    let split_name = if tcx.sess.split_dwarf_objects() {
+        let path = tcx.output_filenames(LOCAL_CRATE).split_dwarf_path(Some(codegen_unit_name));
+        path_to_c_string(&path)
+    } else {
+        CString::default()
+    };
 
     // FIXME(#60020):
     //
@@ -963,7 +971,7 @@ pub fn compile_unit_metadata(
             tcx.sess.opts.optimize != config::OptLevel::No,
             flags.as_ptr().cast(),
             0,
-            split_name.as_ptr().cast(),
+            split_name.as_ptr(),
             kind);
 
         if tcx.sess.opts.debugging_opts.profile {
@@ -3,7 +3,8 @@
 
 use rustc::session::{Session, filesearch};
 use rustc::session::config::{
-    self, RUST_CGU_EXT, DebugInfo, OutputFilenames, OutputType, PrintRequest, Sanitizer
+    self, RUST_CGU_EXT, DebugInfo, OutputFilenames, OutputType, PrintRequest, Sanitizer,
+    SplitDebuginfo,
 };
 use rustc::session::search_paths::PathKind;
 use rustc::middle::dependency_format::Linkage;
@@ -117,6 +118,11 @@ pub fn link_binary<'a, B: ArchiveBuilder<'a>>(sess: &'a Session,
         for obj in codegen_results.modules.iter().filter_map(|m| m.bytecode_compressed.as_ref()) {
             remove(sess, obj);
         }
+        if !preserve_dwarf_objects(sess) {
+            for dwo in codegen_results.modules.iter().filter_map(|m| m.dwarf_object.as_ref()) {
+                remove(sess, dwo);
+            }
+        }
         if let Some(ref metadata_module) = codegen_results.metadata_module {
             if let Some(ref obj) = metadata_module.object {
                 remove(sess, obj);
@@ -723,6 +729,52 @@ fn link_natively<'a, B: ArchiveBuilder<'a>>(sess: &'a Session,
             sess.fatal(&format!("failed to run dsymutil: {}", e))
         }
     }
+
+    // With packed split debuginfo, the `.dwo` files of this crate and of the
+    // upstream crates it links, found through the skeleton units in the
+    // output, are packaged into a `.dwp` file next to it.
+    if sess.split_debuginfo() == SplitDebuginfo::Packed {
+        package_dwarf_objects(sess, out_filename);
+    }
+}
+
+/// Runs `llvm-dwp` to package the `.dwo` files referenced by `out_filename`
+/// into `<out_filename>.dwp`.
+fn package_dwarf_objects(sess: &Session, out_filename: &Path) {
+    let mut dwp_filename = out_filename.as_os_str().to_owned();
+    dwp_filename.push(".dwp");
+
+    // Look for `llvm-dwp` among the tools bundled in the sysroot first.
+    let mut new_path = sess.host_filesearch(PathKind::All).get_tools_search_paths();
+    if let Some(path) = env::var_os("PATH") {
+        new_path.extend(env::split_paths(&path));
+    }
+
+    let mut cmd = Command::new("llvm-dwp");
+    cmd.env("PATH", env::join_paths(new_path).unwrap());
+    cmd.arg("-e").arg(out_filename).arg("-o").arg(&dwp_filename);
+    info!("{:?}", &cmd);
+
+    match cmd.output() {
+        Ok(prog) => {
+            if !prog.status.success() {
+                let mut output = prog.stderr.clone();
+                output.extend_from_slice(&prog.stdout);
+                sess.struct_err(&format!("packaging split debuginfo with `llvm-dwp` failed: {}",
+                                         prog.status))
+                    .note(&format!("{:?}", &cmd))
+                    .note(&String::from_utf8_lossy(&output))
+                    .emit();
+            }
+        }
+        Err(e) => {
+            sess.struct_err("could not exec `llvm-dwp` to package split debuginfo")
+                .note(&e.to_string())
+                .help("use `-C split-debuginfo=unpacked` to keep the `.dwo` files instead")
+                .emit();
+        }
+    }
+    sess.abort_if_errors();
 }
 
 /// Returns a boolean indicating whether the specified crate should be ignored
@@ -810,6 +862,16 @@ pub fn linker_and_flavor(sess: &Session) -> (PathBuf, LinkerFlavor) {
     bug!("Not enough information provided to determine how to invoke the linker");
 }
 
+/// Returns a boolean indicating whether we should preserve the `.dwo` files
+/// written with `-C split-debuginfo`. They are only redundant once they have
+/// been packaged into a `.dwp` file, and archives still refer to them.
+fn preserve_dwarf_objects(sess: &Session) -> bool {
+    sess.split_debuginfo() != SplitDebuginfo::Packed ||
+        sess.crate_types.borrow()
+            .iter()
+            .any(|&x| x == config::CrateType::Rlib || x == config::CrateType::Staticlib)
+}
+
 /// Returns a boolean indicating whether we should preserve the object files on
 /// the filesystem for their debug information. This is often useful with
 /// split-dwarf like schemes.
@@ -1168,8 +1230,8 @@ fn link_args<'a, B: ArchiveBuilder<'a>>(cmd: &mut dyn Linker,
     // Pass optimization flags down to the linker.
     cmd.optimize();
 
-    // Pass debuginfo flags down to the linker.
-    cmd.debuginfo();
+    // Pass debuginfo and stripping flags down to the linker.
+    cmd.debuginfo(sess.opts.cg.strip);
 
     // We want to, by default, prevent the compiler from accidentally leaking in
     // any system libraries, so we may explicitly ask linkers to not link to any
@@ -13,7 +13,7 @@ use rustc::hir::def_id::{LOCAL_CRATE, CrateNum};
 use rustc::middle::dependency_format::Linkage;
 use rustc::session::Session;
 use rustc::session::config::{self, CrateType, OptLevel, DebugInfo,
-                             LinkerPluginLto, Lto};
+                             LinkerPluginLto, Lto, Strip};
 use rustc::middle::exported_symbols::ExportedSymbol;
 use rustc::ty::TyCtxt;
 use rustc_target::spec::{LinkerFlavor, LldFlavor};
@@ -120,7 +120,7 @@ pub trait Linker {
     fn no_relro(&mut self);
     fn optimize(&mut self);
     fn pgo_gen(&mut self);
-    fn debuginfo(&mut self);
+    fn debuginfo(&mut self, strip: Strip);
     fn no_default_libraries(&mut self);
     fn build_dylib(&mut self, out_filename: &Path);
     fn build_static_executable(&mut self);
@@ -339,15 +339,37 @@ impl<'a> Linker for GccLinker<'a> {
         self.cmd.arg("__llvm_profile_runtime");
     }
 
-    fn debuginfo(&mut self) {
-        if let DebugInfo::None = self.sess.opts.debuginfo {
-            // If we are building without debuginfo enabled and we were called with
-            // `-Zstrip-debuginfo-if-disabled=yes`, tell the linker to strip any debuginfo
-            // found when linking to get rid of symbols from libstd.
-            if let Some(true) = self.sess.opts.debugging_opts.strip_debuginfo_if_disabled {
-                self.linker_arg("-S");
+    fn debuginfo(&mut self, strip: Strip) {
+        match strip {
+            Strip::None => {
+                if let DebugInfo::None = self.sess.opts.debuginfo {
+                    // If we are building without debuginfo enabled and we were called with
+                    // `-Zstrip-debuginfo-if-disabled=yes`, tell the linker to strip any
+                    // debuginfo found when linking to get rid of symbols from libstd.
+                    if let Some(true) = self.sess.opts.debugging_opts.strip_debuginfo_if_disabled {
+                        self.linker_arg("-S");
+                    }
+                }
             }
-        };
+            Strip::Debuginfo => {
+                // The macOS and Solaris linkers only have the short form of this flag.
+                let options = &self.sess.target.target.options;
+                if options.is_like_osx || options.is_like_solaris {
+                    self.linker_arg("-S");
+                } else {
+                    self.linker_arg("--strip-debug");
+                }
+            }
+            Strip::Symbols => {
+                // The macOS and Solaris linkers only have the short form of this flag.
+                let options = &self.sess.target.target.options;
+                if options.is_like_osx || options.is_like_solaris {
+                    self.linker_arg("-s");
+                } else {
+                    self.linker_arg("--strip-all");
+                }
+            }
+        }
     }
 
     fn no_default_libraries(&mut self) {
@@ -631,7 +653,14 @@ impl<'a> Linker for MsvcLinker<'a> {
         // Nothing needed here.
     }
 
-    fn debuginfo(&mut self) {
+    fn debuginfo(&mut self, strip: Strip) {
+        // The symbols and the debuginfo both live in the PDB file, so
+        // stripping either means not generating one.
+        if strip != Strip::None {
+            self.cmd.arg("/DEBUG:NONE");
+            return;
+        }
+
         // This will cause the Microsoft linker to generate a PDB file
         // from the CodeView line tables in the object files.
         self.cmd.arg("/DEBUG");
@@ -835,7 +864,7 @@ impl<'a> Linker for EmLinker<'a> {
         // noop, but maybe we need something like the gnu linker?
     }
 
-    fn debuginfo(&mut self) {
+    fn debuginfo(&mut self, _strip: Strip) {
         // Preserve names or generate source maps depending on debug info
         self.cmd.arg(match self.sess.opts.debuginfo {
             DebugInfo::None => "-g0",
@@ -1037,7 +1066,16 @@ impl<'a> Linker for WasmLd<'a> {
     fn pgo_gen(&mut self) {
     }
 
-    fn debuginfo(&mut self) {
+    fn debuginfo(&mut self, strip: Strip) {
+        match strip {
+            Strip::None => {}
+            Strip::Debuginfo => {
+                self.cmd.arg("--strip-debug");
+            }
+            Strip::Symbols => {
+                self.cmd.arg("--strip-all");
+            }
+        }
     }
 
     fn no_default_libraries(&mut self) {
@@ -1155,7 +1193,7 @@ impl<'a> Linker for PtxLinker<'a> {
         self.cmd.arg("-L").arg(path);
     }
 
-    fn debuginfo(&mut self) {
+    fn debuginfo(&mut self, _strip: Strip) {
         self.cmd.arg("--debug");
     }
 
@@ -66,6 +66,9 @@ pub struct ModuleConfig {
     pub emit_ir: bool,
     pub emit_asm: bool,
     pub emit_obj: bool,
+    // Whether the debuginfo of the object file is written to a separate `.dwo`
+    // file, with `-C split-debuginfo`.
+    pub emit_dwarf_obj: bool,
     // Miscellaneous flags.  These are mostly copied from command-line
     // options.
     pub verify_llvm_ir: bool,
@@ -103,6 +106,7 @@ impl ModuleConfig {
             emit_ir: false,
             emit_asm: false,
             emit_obj: false,
+            emit_dwarf_obj: false,
             obj_is_bitcode: false,
             embed_bitcode: false,
             embed_bitcode_marker: false,
@@ -419,6 +423,7 @@ pub fn start_async_codegen<B: ExtraBackendMethods>(
             },
             OutputType::Mir => {}
             OutputType::DepInfo => {}
+            OutputType::LlvmStats => {}
         }
     }
 
@@ -426,6 +431,10 @@ pub fn start_async_codegen<B: ExtraBackendMethods>(
     metadata_config.set_flags(sess, no_builtins);
     allocator_config.set_flags(sess, no_builtins);
 
+    // The metadata and allocator modules have no debuginfo to split out. This
+    // must agree with the skeleton units created in the codegen backend.
+    modules_config.emit_dwarf_obj = sess.split_dwarf_objects();
+
     // Exclude metadata and allocator modules from time_passes output, since
     // they throw off the "LLVM passes" measurement.
     metadata_config.time_passes = false;
@@ -486,6 +495,9 @@ fn copy_all_cgu_workproducts_to_incr_comp_cache_dir(
         if let Some(ref path) = module.bytecode_compressed {
             files.push((WorkProductFileKind::BytecodeCompressed, path.clone()));
         }
+        if let Some(ref path) = module.dwarf_object {
+            files.push((WorkProductFileKind::DwarfObject, path.clone()));
+        }
 
         if let Some((id, product)) =
                 copy_cgu_workproducts_to_incr_comp_cache_dir(sess, &module.name, &files) {
@@ -570,6 +582,15 @@ fn produce_final_output_artifacts(sess: &Session,
                 user_wants_objects = true;
                 copy_if_one_unit(OutputType::Object, true);
             }
+            OutputType::LlvmStats => {
+                // All codegen units record into the session, so there is a
+                // single report regardless of the number of codegen units.
+                let path = crate_output.path(OutputType::LlvmStats);
+                if let Err(e) = sess.code_stats.borrow().write_llvm_stats(&path) {
+                    sess.err(&format!("failed to write LLVM stats to `{}`: {}",
+                                      path.display(), e));
+                }
+            }
             OutputType::Mir |
             OutputType::Metadata |
             OutputType::Exe |
@@ -833,6 +854,7 @@ fn execute_copy_from_cache_work_item<B: ExtraBackendMethods>(
     let mut object = None;
     let mut bytecode = None;
     let mut bytecode_compressed = None;
+    let mut dwarf_object = None;
     for (kind, saved_file) in &module.source.saved_files {
         let obj_out = match kind {
             WorkProductFileKind::Object => {
@@ -854,6 +876,11 @@ fn execute_copy_from_cache_work_item<B: ExtraBackendMethods>(
                 bytecode_compressed = Some(path.clone());
                 path
             }
+            WorkProductFileKind::DwarfObject => {
+                let path = cgcx.output_filenames.split_dwarf_path(Some(&module.name));
+                dwarf_object = Some(path.clone());
+                path
+            }
         };
         let source_file = in_incr_comp_dir(&incr_comp_session_dir,
                                            &saved_file);
@@ -873,6 +900,7 @@ fn execute_copy_from_cache_work_item<B: ExtraBackendMethods>(
     assert_eq!(object.is_some(), module_config.emit_obj);
     assert_eq!(bytecode.is_some(), module_config.emit_bc);
     assert_eq!(bytecode_compressed.is_some(), module_config.emit_bc_compressed);
+    assert_eq!(dwarf_object.is_some(), module_config.emit_dwarf_obj);
 
     Ok(WorkItemResult::Compiled(CompiledModule {
         name: module.name,
@@ -880,6 +908,7 @@ fn execute_copy_from_cache_work_item<B: ExtraBackendMethods>(
         object,
         bytecode,
         bytecode_compressed,
+        dwarf_object,
     }))
 }
 
@@ -26,7 +26,7 @@ use rustc::ty::layout::{self, Align, TyLayout, LayoutOf, VariantIdx, HasTyCtxt};
 use rustc::ty::query::Providers;
 use rustc::middle::cstore::{self, LinkagePreference};
 use rustc::util::common::{time, print_time_passes_entry, set_time_depth, time_depth};
-use rustc::session::config::{self, EntryFnType, Lto};
+use rustc::session::config::{self, EntryFnType, Lto, OutputType};
 use rustc::session::Session;
 use rustc::util::nodemap::FxHashMap;
 use rustc_index::vec::Idx;
@@ -588,6 +588,8 @@ pub fn codegen_crate<B: ExtraBackendMethods>(
     };
 
     let mut total_codegen_time = Duration::new(0, 0);
+    let mut work_product_hits = 0;
+    let mut work_product_misses = 0;
 
     for cgu in codegen_units.into_iter() {
         ongoing_codegen.wait_for_signal_to_codegen_item();
@@ -596,7 +598,7 @@ pub fn codegen_crate<B: ExtraBackendMethods>(
         let cgu_reuse = determine_cgu_reuse(tcx, &cgu);
         tcx.sess.cgu_reuse_tracker.set_actual_reuse(&cgu.name().as_str(), cgu_reuse);
 
-        match cgu_reuse {
+        let reused = match cgu_reuse {
             CguReuse::No => {
                 let start_time = Instant::now();
                 backend.compile_codegen_unit(tcx, *cgu.name(), &ongoing_codegen.coordinator_send);
@@ -620,6 +622,18 @@ pub fn codegen_crate<B: ExtraBackendMethods>(
                 true
             }
         };
+
+        if reused {
+            work_product_hits += 1;
+        } else {
+            work_product_misses += 1;
+        }
+    }
+
+    if tcx.sess.opts.debugging_opts.incremental_info && tcx.dep_graph.is_fully_enabled() {
+        println!("[incremental] work products: {} hits, {} misses",
+                 work_product_hits,
+                 work_product_misses);
     }
 
     ongoing_codegen.codegen_finished(tcx);
@@ -837,6 +851,12 @@ fn determine_cgu_reuse<'tcx>(tcx: TyCtxt<'tcx>, cgu: &CodegenUnit<'tcx>) -> CguR
         return CguReuse::No
     }
 
+    // The instruction counts for `--emit=llvm-stats` are taken while
+    // codegening the LLVM module, so it can't come from the cache.
+    if tcx.sess.opts.output_types.contains_key(&OutputType::LlvmStats) {
+        return CguReuse::No
+    }
+
     let work_product_id = &cgu.work_product_id();
     if tcx.dep_graph.previous_work_product(work_product_id).is_none() {
         // We don't have anything cached for this CGU. This can happen
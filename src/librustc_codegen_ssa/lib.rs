@@ -67,6 +67,7 @@ impl<M> ModuleCodegen<M> {
                             emit_obj: bool,
                             emit_bc: bool,
                             emit_bc_compressed: bool,
+                            emit_dwarf_obj: bool,
                             outputs: &OutputFilenames) -> CompiledModule {
         let object = if emit_obj {
             Some(outputs.temp_path(OutputType::Object, Some(&self.name)))
@@ -84,6 +85,11 @@ impl<M> ModuleCodegen<M> {
         } else {
             None
         };
+        let dwarf_object = if emit_dwarf_obj {
+            Some(outputs.split_dwarf_path(Some(&self.name)))
+        } else {
+            None
+        };
 
         CompiledModule {
             name: self.name.clone(),
@@ -91,6 +97,7 @@ impl<M> ModuleCodegen<M> {
             object,
             bytecode,
             bytecode_compressed,
+            dwarf_object,
         }
     }
 }
@@ -102,6 +109,7 @@ pub struct CompiledModule {
     pub object: Option<PathBuf>,
     pub bytecode: Option<PathBuf>,
     pub bytecode_compressed: Option<PathBuf>,
+    pub dwarf_object: Option<PathBuf>,
 }
 
 pub struct CachedModuleCodegen {
@@ -70,6 +70,7 @@ use syntax_pos::{DUMMY_SP, FileName};
 
 pub mod pretty;
 mod args;
+mod public_api;
 
 /// Exit status code used for successful compilation and help output.
 pub const EXIT_SUCCESS: i32 = 0;
@@ -376,6 +377,12 @@ pub fn run_compiler(
 
         compiler.global_ctxt()?.peek_mut().enter(|tcx| tcx.analysis(LOCAL_CRATE))?;
 
+        if sess.opts.prints.contains(&PrintRequest::PublicApi) {
+            compiler.global_ctxt()?.peek_mut().enter(|tcx| {
+                println!("{}", public_api::public_api(tcx).pretty());
+            });
+        }
+
         if callbacks.after_analysis(compiler) == Compilation::Stop {
             return sess.compile_status();
         }
@@ -638,9 +645,11 @@ impl RustcDefaultCalls {
                         ofile: &Option<PathBuf>)
                         -> Compilation {
         use rustc::session::config::PrintRequest::*;
-        // PrintRequest::NativeStaticLibs is special - printed during linking
+        // PrintRequest::NativeStaticLibs is special - printed during linking,
+        // and PrintRequest::PublicApi is printed after analysis
         // (empty iterator returns true)
-        if sess.opts.prints.iter().all(|&p| p == PrintRequest::NativeStaticLibs) {
+        if sess.opts.prints.iter().all(|&p| p == PrintRequest::NativeStaticLibs ||
+                                            p == PrintRequest::PublicApi) {
             return Compilation::Continue;
         }
 
@@ -736,7 +745,7 @@ impl RustcDefaultCalls {
                     codegen_backend.print(*req, sess);
                 }
                 // Any output here interferes with Cargo's parsing of other printed output
-                PrintRequest::NativeStaticLibs => {}
+                PrintRequest::NativeStaticLibs | PrintRequest::PublicApi => {}
             }
         }
         return Compilation::Stop;
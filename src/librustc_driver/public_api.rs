@@ -0,0 +1,282 @@
+//! Implementation of `--print=public-api`.
+//!
+//! This describes every item that the local crate exports to other crates, as computed by the
+//! privacy pass, together with its signature, the trait impls that are visible to other crates
+//! and whether each public type implements the auto traits. Items are sorted by path so that
+//! the output of two versions of a crate can be diffed directly.
+//!
+//! The path of an item is the one other crates name it with, found by walking the public modules
+//! and re-exports from the crate root, rather than the path of its definition. This way, moving
+//! an item behind an unchanged re-export doesn't change the API.
+
+use rustc::hir::{self, Node};
+use rustc::hir::def::DefKind;
+use rustc::hir::def_id::{DefId, CRATE_DEF_INDEX, LOCAL_CRATE};
+use rustc::middle::privacy::AccessLevel;
+use rustc::traits;
+use rustc::ty::{self, DefIdTree, TyCtxt};
+use rustc::util::nodemap::FxHashMap;
+use rustc_serialize::json::Json;
+use syntax::symbol::kw;
+use syntax_pos::DUMMY_SP;
+
+use std::collections::{BTreeMap, VecDeque};
+
+struct ApiItem {
+    path: String,
+    kind: &'static str,
+    fields: BTreeMap<String, Json>,
+}
+
+/// Returns a JSON description of the public API of the local crate.
+pub fn public_api(tcx: TyCtxt<'_>) -> Json {
+    let access_levels = tcx.privacy_access_levels(LOCAL_CRATE);
+    let auto_traits = tcx.all_traits(LOCAL_CRATE).iter().cloned().filter(|&trait_def_id| {
+        tcx.trait_is_auto(trait_def_id) && tcx.visibility(trait_def_id) == ty::Visibility::Public
+    }).collect::<Vec<_>>();
+
+    let paths = public_paths(tcx);
+
+    let mut items = access_levels.map.iter()
+        .filter(|&(_, &level)| level >= AccessLevel::Exported)
+        .filter_map(|(&hir_id, &level)| describe_item(tcx, hir_id, level, &auto_traits, &paths))
+        .collect::<Vec<_>>();
+    items.sort_by(|a, b| (&a.path, a.kind).cmp(&(&b.path, b.kind)));
+
+    let items = items.into_iter().map(|item| {
+        let mut fields = item.fields;
+        fields.insert("path".to_string(), Json::String(item.path));
+        fields.insert("kind".to_string(), Json::String(item.kind.to_string()));
+        Json::Object(fields)
+    }).collect();
+
+    let mut krate = BTreeMap::new();
+    krate.insert("crate".to_string(), Json::String(tcx.crate_name(LOCAL_CRATE).to_string()));
+    krate.insert("items".to_string(), Json::Array(items));
+    Json::Object(krate)
+}
+
+fn describe_item(
+    tcx: TyCtxt<'_>,
+    hir_id: hir::HirId,
+    level: AccessLevel,
+    auto_traits: &[DefId],
+    paths: &FxHashMap<DefId, String>,
+) -> Option<ApiItem> {
+    if hir_id == hir::CRATE_HIR_ID {
+        return None;
+    }
+    let def_id = tcx.hir().local_def_id(hir_id);
+    let mut fields = BTreeMap::new();
+    let level = if level == AccessLevel::Public { "public" } else { "exported" };
+    fields.insert("level".to_string(), Json::String(level.to_string()));
+
+    let (path, kind) = match tcx.hir().find(hir_id)? {
+        Node::Item(&hir::Item { kind: hir::ItemKind::Impl(..), .. }) => {
+            // Inherent impls are described through their associated items.
+            let trait_ref = tcx.impl_trait_ref(def_id)?;
+            if tcx.impl_polarity(def_id) == ty::ImplPolarity::Negative {
+                fields.insert("negative".to_string(), Json::Boolean(true));
+            }
+            describe_generics(tcx, def_id, &mut fields);
+            let self_ty = match trait_ref.self_ty().kind {
+                ty::Adt(def, _) => public_name(tcx, paths, def.did, trait_ref.self_ty()),
+                _ => trait_ref.self_ty().to_string(),
+            };
+            let trait_ = public_name(tcx, paths, trait_ref.def_id, trait_ref);
+            (format!("<{} as {}>", self_ty, trait_), "impl")
+        }
+        Node::Field(_) => {
+            fields.insert("signature".to_string(), Json::String(tcx.type_of(def_id).to_string()));
+            (public_path(tcx, paths, def_id), "field")
+        }
+        _ => {
+            let def_kind = tcx.hir().def_kind(hir_id)?;
+            match def_kind {
+                // Constructors are covered by their struct or variant, and opaque types are
+                // only reachable through the signatures that mention them.
+                DefKind::Ctor(..) | DefKind::OpaqueTy | DefKind::AssocOpaqueTy => return None,
+                DefKind::Method | DefKind::AssocConst | DefKind::AssocTy => {
+                    // Items of trait impls are described by the trait itself.
+                    if let ty::ImplContainer(impl_def_id) = tcx.associated_item(def_id).container {
+                        if tcx.impl_trait_ref(impl_def_id).is_some() {
+                            return None;
+                        }
+                    }
+                }
+                _ => {}
+            }
+
+            let signature = match def_kind {
+                DefKind::Fn | DefKind::Method => Some(tcx.fn_sig(def_id).to_string()),
+                DefKind::Const | DefKind::Static | DefKind::AssocConst | DefKind::TyAlias => {
+                    Some(tcx.type_of(def_id).to_string())
+                }
+                _ => None,
+            };
+            if let Some(signature) = signature {
+                fields.insert("signature".to_string(), Json::String(signature));
+            }
+
+            match def_kind {
+                DefKind::Fn | DefKind::Method | DefKind::Trait | DefKind::TraitAlias |
+                DefKind::TyAlias => describe_generics(tcx, def_id, &mut fields),
+                DefKind::Struct | DefKind::Enum | DefKind::Union => {
+                    describe_generics(tcx, def_id, &mut fields);
+                    describe_auto_traits(tcx, def_id, auto_traits, &mut fields);
+                }
+                DefKind::ForeignTy => describe_auto_traits(tcx, def_id, auto_traits, &mut fields),
+                _ => {}
+            }
+
+            (public_path(tcx, paths, def_id), def_kind.descr(def_id))
+        }
+    };
+
+    Some(ApiItem { path, kind, fields })
+}
+
+/// Finds the paths at which other crates can name the items of the local crate, by walking the
+/// public modules and the re-exports from the crate root. An item that can be named in several
+/// ways gets the shortest of its paths.
+fn public_paths(tcx: TyCtxt<'_>) -> FxHashMap<DefId, String> {
+    let mut paths = FxHashMap::default();
+    let mut modules = VecDeque::new();
+    modules.push_back((DefId::local(CRATE_DEF_INDEX), String::new()));
+
+    while let Some((module, prefix)) = modules.pop_front() {
+        let mut children = Vec::new();
+        let (hir_module, ..) = tcx.hir().get_module(module);
+        for item_id in &hir_module.item_ids {
+            let item = tcx.hir().expect_item(item_id.id);
+            match item.kind {
+                // Re-exports are found through the export map below.
+                hir::ItemKind::Use(..) | hir::ItemKind::ExternCrate(..) |
+                hir::ItemKind::Impl(..) | hir::ItemKind::GlobalAsm(..) => {}
+                hir::ItemKind::ForeignMod(ref foreign_mod) => {
+                    children.extend(foreign_mod.items.iter()
+                        .filter(|foreign_item| foreign_item.vis.node.is_pub())
+                        .map(|foreign_item| {
+                            (foreign_item.ident.name, tcx.hir().local_def_id(foreign_item.hir_id))
+                        }));
+                }
+                _ if item.vis.node.is_pub() => {
+                    children.push((item.ident.name, tcx.hir().local_def_id(item.hir_id)));
+                }
+                _ => {}
+            }
+        }
+        let mut exports = tcx.module_exports(module).unwrap_or(&[]).iter()
+            .filter(|export| export.vis == ty::Visibility::Public)
+            .filter_map(|export| {
+                let def_id = export.res.opt_def_id().filter(|def_id| def_id.is_local())?;
+                Some((export.ident.name, def_id))
+            })
+            .collect::<Vec<_>>();
+        exports.sort_by_key(|&(name, _)| name.as_str());
+        children.extend(exports);
+
+        for (name, def_id) in children {
+            if paths.contains_key(&def_id) {
+                continue;
+            }
+            let path = if prefix.is_empty() {
+                name.to_string()
+            } else {
+                format!("{}::{}", prefix, name)
+            };
+            if tcx.def_kind(def_id) == Some(DefKind::Mod) {
+                modules.push_back((def_id, path.clone()));
+            }
+            paths.insert(def_id, path);
+        }
+    }
+
+    paths
+}
+
+/// Returns the path other crates name `def_id` with. Fields, variants and associated items are
+/// named through the type or trait they belong to. Items that can't be named by other crates,
+/// like the types they only reach through signatures, keep the path of their definition.
+fn public_path(tcx: TyCtxt<'_>, paths: &FxHashMap<DefId, String>, def_id: DefId) -> String {
+    if let Some(path) = paths.get(&def_id) {
+        return path.clone();
+    }
+
+    let parent = match tcx.opt_associated_item(def_id) {
+        Some(item) => match item.container {
+            ty::TraitContainer(trait_def_id) => Some(trait_def_id),
+            ty::ImplContainer(impl_def_id) => {
+                tcx.type_of(impl_def_id).ty_adt_def().map(|adt| adt.did)
+            }
+        },
+        None => tcx.parent(def_id).filter(|&parent| tcx.def_kind(parent) != Some(DefKind::Mod)),
+    };
+    match parent {
+        Some(parent) => format!("{}::{}", public_path(tcx, paths, parent), tcx.item_name(def_id)),
+        None => tcx.def_path_str(def_id),
+    }
+}
+
+/// Prints `printed`, which starts with the path of the definition `def_id`, like a type or a trait
+/// reference, with the path other crates name `def_id` with instead.
+fn public_name(
+    tcx: TyCtxt<'_>,
+    paths: &FxHashMap<DefId, String>,
+    def_id: DefId,
+    printed: impl ToString,
+) -> String {
+    let printed = printed.to_string();
+    let def_path = tcx.def_path_str(def_id);
+    if def_id.is_local() && printed.starts_with(&def_path) {
+        format!("{}{}", public_path(tcx, paths, def_id), &printed[def_path.len()..])
+    } else {
+        printed
+    }
+}
+
+/// Records the generic parameters and the predicates of `def_id`, if it has any.
+fn describe_generics(tcx: TyCtxt<'_>, def_id: DefId, fields: &mut BTreeMap<String, Json>) {
+    let params = tcx.generics_of(def_id).params.iter()
+        .filter(|param| param.name != kw::SelfUpper)
+        .map(|param| Json::String(param.name.to_string()))
+        .collect::<Vec<_>>();
+    if !params.is_empty() {
+        fields.insert("generics".to_string(), Json::Array(params));
+    }
+
+    let mut predicates = tcx.predicates_of(def_id).predicates.iter()
+        .map(|(predicate, _)| predicate.to_string())
+        .collect::<Vec<_>>();
+    predicates.sort();
+    predicates.dedup();
+    if !predicates.is_empty() {
+        let predicates = predicates.into_iter().map(Json::String).collect();
+        fields.insert("predicates".to_string(), Json::Array(predicates));
+    }
+}
+
+/// Records, for each public auto trait, whether the type `def_id` implements it for every
+/// instantiation allowed by its own bounds.
+fn describe_auto_traits(
+    tcx: TyCtxt<'_>,
+    def_id: DefId,
+    auto_traits: &[DefId],
+    fields: &mut BTreeMap<String, Json>,
+) {
+    let ty = tcx.type_of(def_id);
+    let param_env = tcx.param_env(def_id);
+    let implemented = auto_traits.iter().map(|&trait_def_id| {
+        let implemented = tcx.infer_ctxt().enter(|infcx| {
+            traits::type_known_to_meet_bound_modulo_regions(
+                &infcx,
+                param_env,
+                ty,
+                trait_def_id,
+                DUMMY_SP,
+            )
+        });
+        (tcx.def_path_str(trait_def_id), Json::Boolean(implemented))
+    }).collect();
+    fields.insert("auto_traits".to_string(), Json::Object(implemented));
+}
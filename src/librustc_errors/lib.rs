@@ -32,6 +32,7 @@ mod diagnostic;
 mod diagnostic_builder;
 pub mod emitter;
 pub mod annotate_snippet_emitter_writer;
+pub mod sarif_emitter;
 mod snippet;
 pub mod registry;
 mod styled_buffer;
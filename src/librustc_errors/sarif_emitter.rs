@@ -0,0 +1,320 @@
+//! A SARIF emitter for errors.
+//!
+//! [SARIF] (the Static Analysis Results Interchange Format) is the format
+//! consumed by code-scanning platforms. Unlike the JSON emitter, which writes
+//! one object per diagnostic, SARIF describes a whole analysis run in a single
+//! document. This emitter therefore buffers every diagnostic as a SARIF
+//! `result` and writes the document once, when the emitter is dropped at the
+//! end of the compilation session.
+//!
+//! The mapping is as follows:
+//!
+//! * The diagnostic level becomes the result `level`. Errors and ICEs map to
+//!   `error`, warnings to `warning` and notes and helps to `note`.
+//! * The error code or lint name becomes the `ruleId`. Every rule that is
+//!   referenced is also listed in the tool's `rules`, together with its
+//!   long-form explanation if the registry knows one.
+//! * Primary spans become `locations`; secondary spans and the spans of
+//!   children become `relatedLocations`.
+//! * Each `CodeSuggestion` becomes a `fix`, with one `replacement` per
+//!   substitution part. The suggestion's `Applicability` is recorded in the
+//!   fix's property bag, since SARIF has no notion of it.
+//!
+//! [SARIF]: https://docs.oasis-open.org/sarif/sarif/v2.1.0/sarif-v2.1.0.html
+
+use crate::{
+    Level, CodeSuggestion, Diagnostic, DiagnosticId, SubDiagnostic, SourceMapperDyn,
+};
+use crate::emitter::Emitter;
+use crate::registry::Registry;
+
+use syntax_pos::{Span, SpanLabel};
+use rustc_data_structures::sync::Lrc;
+use rustc_serialize::json::{Json, Object};
+
+use std::collections::BTreeMap;
+use std::io::{self, Write};
+
+const SARIF_SCHEMA: &str =
+    "https://schemastore.azurewebsites.net/schemas/json/sarif-2.1.0-rtm.5.json";
+const SARIF_VERSION: &str = "2.1.0";
+
+/// Buffers diagnostics and writes them as a single SARIF 2.1.0 log on drop.
+pub struct SarifEmitter {
+    dst: Box<dyn Write + Send>,
+    registry: Option<Registry>,
+    sm: Option<Lrc<SourceMapperDyn>>,
+    pretty: bool,
+    /// The rules referenced by `results`, keyed by error code or lint name.
+    rules: BTreeMap<String, Option<&'static str>>,
+    results: Vec<Json>,
+    /// Whether to write nothing instead of a log without results.
+    skip_empty: bool,
+}
+
+impl SarifEmitter {
+    pub fn stderr(
+        registry: Option<Registry>,
+        source_map: Option<Lrc<SourceMapperDyn>>,
+        pretty: bool,
+    ) -> SarifEmitter {
+        SarifEmitter::new(Box::new(io::stderr()), registry, source_map, pretty)
+    }
+
+    pub fn new(
+        dst: Box<dyn Write + Send>,
+        registry: Option<Registry>,
+        source_map: Option<Lrc<SourceMapperDyn>>,
+        pretty: bool,
+    ) -> SarifEmitter {
+        SarifEmitter {
+            dst,
+            registry,
+            sm: source_map,
+            pretty,
+            rules: BTreeMap::new(),
+            results: Vec::new(),
+            skip_empty: false,
+        }
+    }
+
+    /// Writes no log at all if nothing was emitted. Tools that report diagnostics through several
+    /// handlers use this for all but one of them, so that they write a single log.
+    pub fn skip_empty(mut self, skip_empty: bool) -> Self {
+        // `SarifEmitter` implements `Drop`, so it can't be rebuilt with `..self`.
+        self.skip_empty = skip_empty;
+        self
+    }
+
+    /// Builds the complete SARIF log for everything emitted so far.
+    fn log(&mut self) -> Json {
+        let rules = self.rules.iter().map(|(id, explanation)| {
+            let mut rule = Object::new();
+            rule.insert("id".to_owned(), Json::String(id.clone()));
+            if let Some(explanation) = explanation {
+                rule.insert("fullDescription".to_owned(), text(explanation));
+            }
+            Json::Object(rule)
+        }).collect();
+
+        let mut driver = Object::new();
+        driver.insert("name".to_owned(), Json::String("rustc".to_owned()));
+        driver.insert("informationUri".to_owned(),
+                      Json::String("https://www.rust-lang.org/".to_owned()));
+        driver.insert("rules".to_owned(), Json::Array(rules));
+
+        let mut tool = Object::new();
+        tool.insert("driver".to_owned(), Json::Object(driver));
+
+        let mut run = Object::new();
+        run.insert("tool".to_owned(), Json::Object(tool));
+        run.insert("results".to_owned(),
+                   Json::Array(std::mem::replace(&mut self.results, Vec::new())));
+
+        let mut log = Object::new();
+        log.insert("$schema".to_owned(), Json::String(SARIF_SCHEMA.to_owned()));
+        log.insert("version".to_owned(), Json::String(SARIF_VERSION.to_owned()));
+        log.insert("runs".to_owned(), Json::Array(vec![Json::Object(run)]));
+        Json::Object(log)
+    }
+
+    fn result(&mut self, db: &Diagnostic) -> Json {
+        let mut result = Object::new();
+
+        if let Some(code) = &db.code {
+            let id = match code {
+                DiagnosticId::Error(s) | DiagnosticId::Lint(s) => s.clone(),
+            };
+            let explanation = self.registry.as_ref()
+                .and_then(|registry| registry.find_description(&id));
+            self.rules.entry(id.clone()).or_insert(explanation);
+            result.insert("ruleId".to_owned(), Json::String(id));
+        }
+
+        result.insert("level".to_owned(), Json::String(sarif_level(db.level).to_owned()));
+        result.insert("message".to_owned(), text(&db.message()));
+
+        let (primary, secondary): (Vec<_>, Vec<_>) = db.span.span_labels()
+            .into_iter()
+            .partition(|label| label.is_primary);
+        let locations: Vec<_> = primary.into_iter()
+            .filter_map(|label| self.location_from_label(label))
+            .collect();
+        if !locations.is_empty() {
+            result.insert("locations".to_owned(), Json::Array(locations));
+        }
+
+        let related: Vec<_> = secondary.into_iter()
+            .filter_map(|label| self.location_from_label(label))
+            .chain(db.children.iter().flat_map(|child| self.locations_from_child(child)))
+            .collect();
+        if !related.is_empty() {
+            result.insert("relatedLocations".to_owned(), Json::Array(related));
+        }
+
+        let fixes: Vec<_> = db.suggestions.iter()
+            .filter_map(|sugg| self.fix(sugg))
+            .collect();
+        if !fixes.is_empty() {
+            result.insert("fixes".to_owned(), Json::Array(fixes));
+        }
+
+        Json::Object(result)
+    }
+
+    fn location_from_label(&self, label: SpanLabel) -> Option<Json> {
+        let mut location = self.physical_location(label.span)?;
+        if let Some(label) = label.label {
+            location.insert("message".to_owned(), text(&label));
+        }
+        Some(Json::Object(location))
+    }
+
+    /// Children have no SARIF equivalent, so they are flattened into related
+    /// locations whose message carries the child's level, e.g. `note: ...`.
+    fn locations_from_child(&self, child: &SubDiagnostic) -> Vec<Json> {
+        let msg = format!("{}: {}", child.level.to_str(), child.message());
+        let span = child.render_span.as_ref().unwrap_or(&child.span);
+        let mut locations: Vec<_> = span.primary_spans()
+            .iter()
+            .filter_map(|&sp| self.physical_location(sp))
+            .map(|mut location| {
+                location.insert("message".to_owned(), text(&msg));
+                Json::Object(location)
+            })
+            .collect();
+        if locations.is_empty() {
+            let mut location = Object::new();
+            location.insert("message".to_owned(), text(&msg));
+            locations.push(Json::Object(location));
+        }
+        locations
+    }
+
+    fn physical_location(&self, span: Span) -> Option<Object> {
+        let sm = self.sm.as_ref()?;
+        if span.is_dummy() {
+            return None;
+        }
+        let start = sm.lookup_char_pos(span.lo());
+        let end = sm.lookup_char_pos(span.hi());
+
+        let mut region = Object::new();
+        region.insert("startLine".to_owned(), Json::U64(start.line as u64));
+        region.insert("startColumn".to_owned(), Json::U64(start.col.0 as u64 + 1));
+        region.insert("endLine".to_owned(), Json::U64(end.line as u64));
+        region.insert("endColumn".to_owned(), Json::U64(end.col.0 as u64 + 1));
+        region.insert("byteOffset".to_owned(),
+                      Json::U64((span.lo().0 - start.file.start_pos.0) as u64));
+        region.insert("byteLength".to_owned(),
+                      Json::U64((span.hi().0 - span.lo().0) as u64));
+
+        let mut physical = Object::new();
+        physical.insert("artifactLocation".to_owned(),
+                        artifact_location(&start.file.name.to_string()));
+        physical.insert("region".to_owned(), Json::Object(region));
+
+        let mut location = Object::new();
+        location.insert("physicalLocation".to_owned(), Json::Object(physical));
+        Some(location)
+    }
+
+    fn fix(&self, suggestion: &CodeSuggestion) -> Option<Json> {
+        let sm = self.sm.as_ref()?;
+        // SARIF groups replacements by the file they apply to.
+        let mut changes: BTreeMap<String, Vec<Json>> = BTreeMap::new();
+        for part in suggestion.substitutions.iter().flat_map(|s| s.parts.iter()) {
+            let file = sm.span_to_filename(part.span).to_string();
+            let location = match self.physical_location(part.span) {
+                Some(location) => location,
+                None => continue,
+            };
+            let region = match location.get("physicalLocation") {
+                Some(Json::Object(physical)) => physical["region"].clone(),
+                _ => continue,
+            };
+            let mut replacement = Object::new();
+            replacement.insert("deletedRegion".to_owned(), region);
+            replacement.insert("insertedContent".to_owned(), text(&part.snippet));
+            changes.entry(file).or_default().push(Json::Object(replacement));
+        }
+        if changes.is_empty() {
+            return None;
+        }
+
+        let changes = changes.into_iter().map(|(file, replacements)| {
+            let mut change = Object::new();
+            change.insert("artifactLocation".to_owned(), artifact_location(&file));
+            change.insert("replacements".to_owned(), Json::Array(replacements));
+            Json::Object(change)
+        }).collect();
+
+        let mut properties = Object::new();
+        properties.insert("applicability".to_owned(),
+                          Json::String(format!("{:?}", suggestion.applicability)));
+
+        let mut fix = Object::new();
+        fix.insert("description".to_owned(), text(&suggestion.msg));
+        fix.insert("artifactChanges".to_owned(), Json::Array(changes));
+        fix.insert("properties".to_owned(), Json::Object(properties));
+        Some(Json::Object(fix))
+    }
+}
+
+impl Emitter for SarifEmitter {
+    fn emit_diagnostic(&mut self, db: &Diagnostic) {
+        // Failure notes such as "aborting due to previous error" only summarize
+        // the other diagnostics and aren't results in their own right.
+        if db.level.is_failure_note() {
+            return;
+        }
+        let result = self.result(db);
+        self.results.push(result);
+    }
+
+    fn should_show_explain(&self) -> bool {
+        false
+    }
+}
+
+impl Drop for SarifEmitter {
+    fn drop(&mut self) {
+        if self.skip_empty && self.results.is_empty() {
+            return;
+        }
+        let log = self.log();
+        let result = if self.pretty {
+            writeln!(&mut self.dst, "{}", log.pretty())
+        } else {
+            writeln!(&mut self.dst, "{}", log)
+        };
+        if let Err(e) = result.and_then(|()| self.dst.flush()) {
+            // Don't double-panic while unwinding out of a fatal error.
+            if !std::thread::panicking() {
+                panic!("failed to print diagnostics: {:?}", e);
+            }
+        }
+    }
+}
+
+fn sarif_level(level: Level) -> &'static str {
+    match level {
+        Level::Bug | Level::Fatal | Level::Error => "error",
+        Level::Warning => "warning",
+        Level::Note | Level::Help => "note",
+        Level::FailureNote | Level::Cancelled => "none",
+    }
+}
+
+/// Both SARIF `message` and `artifactContent` objects are `{ "text": ... }`.
+fn text(s: &str) -> Json {
+    let mut text = Object::new();
+    text.insert("text".to_owned(), Json::String(s.to_owned()));
+    Json::Object(text)
+}
+
+fn artifact_location(uri: &str) -> Json {
+    let mut location = Object::new();
+    location.insert("uri".to_owned(), Json::String(uri.to_owned()));
+    Json::Object(location)
+}
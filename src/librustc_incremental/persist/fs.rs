@@ -50,6 +50,18 @@
 //! any more and will delete those. It will also delete any finalized session
 //! directories for a given crate except for the most recent one.
 //!
+//! If a maximum size is set with `-Z incremental-cache-limit`, the compiler
+//! additionally evicts cache entries of other crates, least recently used
+//! first, whenever the incremental compilation directory as a whole has grown
+//! beyond that size. Since a new session directory is allocated every time a
+//! crate is compiled, the timestamp of a crate's finalized session directory
+//! tells when the crate was last compiled. Eviction first deletes the work
+//! products of the least recently used session directories, which only means
+//! that the corresponding codegen units have to be recompiled. If that is not
+//! enough, whole session directories are deleted, again starting with the
+//! least recently used one. The session directory of the current compilation
+//! session is never evicted.
+//!
 //! ## Synchronization
 //!
 //! There is some synchronization needed in order for the compiler to be able to
@@ -117,6 +129,8 @@ use std::time::{UNIX_EPOCH, SystemTime, Duration};
 
 use rand::{RngCore, thread_rng};
 
+use super::work_product;
+
 #[cfg(test)]
 mod tests;
 
@@ -141,6 +155,9 @@ pub fn dep_graph_path_from(incr_comp_session_dir: &Path) -> PathBuf {
 pub fn work_products_path(sess: &Session) -> PathBuf {
     in_incr_comp_dir_sess(sess, WORK_PRODUCTS_FILENAME)
 }
+pub fn work_products_path_from(incr_comp_session_dir: &Path) -> PathBuf {
+    in_incr_comp_dir(incr_comp_session_dir, WORK_PRODUCTS_FILENAME)
+}
 
 pub fn query_cache_path(sess: &Session) -> PathBuf {
     in_incr_comp_dir_sess(sess, QUERY_CACHE_FILENAME)
@@ -376,6 +393,13 @@ pub fn finalize_session_directory(sess: &Session, svh: Svh) {
     }
 
     let _ = garbage_collect_session_directories(sess);
+
+    if let Some(limit) = sess.opts.debugging_opts.incremental_cache_limit {
+        if let Err(err) = enforce_cache_size_limit(sess, limit as u64 * 1024 * 1024) {
+            sess.warn(&format!("Error enforcing the size limit of the incremental \
+                                compilation directory: {}", err));
+        }
+    }
 }
 
 pub fn delete_all_session_dir_contents(sess: &Session) -> io::Result<()> {
@@ -855,6 +879,146 @@ pub fn garbage_collect_session_directories(sess: &Session) -> io::Result<()> {
     Ok(())
 }
 
+/// Evicts cache entries, least recently used first, until the incremental
+/// compilation directory is no bigger than `limit` bytes. See the module
+/// documentation for the eviction order. Sizes are the apparent sizes of the
+/// files, so a hard-linked file is counted once for each of its links.
+fn enforce_cache_size_limit(sess: &Session, limit: u64) -> io::Result<()> {
+    let incr_dir = sess.opts.incremental.as_ref().unwrap();
+    let current_session_dir = sess.incr_comp_session_dir().canonicalize()?;
+
+    let mut total_size = dir_size(incr_dir)?;
+    debug!("enforce_cache_size_limit() - size: {}, limit: {}", total_size, limit);
+
+    if sess.opts.debugging_opts.incremental_info {
+        println!("[incremental] cache size: {} bytes (limit: {} bytes)", total_size, limit);
+    }
+
+    if total_size <= limit {
+        return Ok(())
+    }
+
+    // Collect the finalized session directories of all crates in the
+    // incremental compilation directory, except for the current one.
+    let mut candidates = vec![];
+
+    for crate_dir in incr_dir.read_dir()? {
+        let crate_dir = match crate_dir {
+            Ok(crate_dir) => crate_dir.path(),
+            // Ignore any errors
+            Err(_) => continue,
+        };
+
+        let session_dirs = match crate_dir.read_dir() {
+            Ok(session_dirs) => session_dirs,
+            // This is not a crate directory, leave it alone
+            Err(_) => continue,
+        };
+
+        for session_dir in session_dirs.filter_map(|e| e.ok()) {
+            let directory_name = session_dir.file_name();
+            let directory_name = directory_name.to_string_lossy();
+
+            if !is_session_directory(&directory_name) || !is_finalized(&directory_name) {
+                continue
+            }
+
+            let timestamp = match extract_timestamp_from_session_dir(&directory_name) {
+                Ok(timestamp) => timestamp,
+                Err(()) => continue,
+            };
+
+            let path = session_dir.path();
+            if path.canonicalize().ok().as_ref() == Some(&current_session_dir) {
+                continue
+            }
+
+            // Hold an exclusive lock while evicting from the directory, so
+            // that no other process copies from it in the meantime.
+            match flock::Lock::new(&lock_file_path(&path),
+                                   false,  // don't wait
+                                   false,  // don't create the lock-file
+                                   true) { // get an exclusive lock
+                Ok(lock) => candidates.push((timestamp, path, lock)),
+                Err(_) => {
+                    debug!("enforce_cache_size_limit() - not evicting `{}`, still in use",
+                           path.display());
+                }
+            }
+        }
+    }
+
+    candidates.sort_by_key(|&(timestamp, ..)| timestamp);
+
+    let mut evicted_work_products = 0;
+    let mut evicted_session_dirs = 0;
+
+    // First evict the work products of the least recently used directories.
+    for &(_, ref session_dir, _) in &candidates {
+        if total_size <= limit {
+            break
+        }
+
+        let (freed, evicted) =
+            work_product::evict_workproducts(sess, session_dir, total_size - limit);
+        total_size = total_size.saturating_sub(freed);
+        evicted_work_products += evicted;
+    }
+
+    // Then evict whole session directories if that was not enough.
+    for (_, session_dir, lock) in candidates {
+        if total_size <= limit {
+            break
+        }
+
+        debug!("enforce_cache_size_limit() - evicting session directory `{}`",
+               session_dir.display());
+        let size = dir_size(&session_dir).unwrap_or(0);
+        match safe_remove_dir_all(&session_dir) {
+            Ok(()) => {
+                delete_session_dir_lock_file(sess, &lock_file_path(&session_dir));
+                total_size = total_size.saturating_sub(size);
+                evicted_session_dirs += 1;
+            }
+            Err(err) => {
+                sess.warn(&format!("Failed to evict incremental compilation \
+                                    session directory `{}`: {}",
+                                   session_dir.display(),
+                                   err));
+            }
+        }
+
+        // Let's make it explicit that the file lock is released at this point,
+        // or rather, that we held on to it until here
+        mem::drop(lock);
+    }
+
+    if sess.opts.debugging_opts.incremental_info {
+        println!("[incremental] cache eviction: {} work product files and \
+                  {} session directories evicted",
+                 evicted_work_products,
+                 evicted_session_dirs);
+        println!("[incremental] cache size after eviction: {} bytes", total_size);
+    }
+
+    Ok(())
+}
+
+/// Returns the total size of all files below `path`.
+fn dir_size(path: &Path) -> io::Result<u64> {
+    let mut size = 0;
+    for entry in path.read_dir()? {
+        let entry = entry?;
+        let metadata = entry.metadata()?;
+        if metadata.is_dir() {
+            size += dir_size(&entry.path())?;
+        } else {
+            size += metadata.len();
+        }
+    }
+    Ok(size)
+}
+
 fn all_except_most_recent(deletion_candidates: Vec<(SystemTime, PathBuf, Option<flock::Lock>)>)
                           -> FxHashMap<PathBuf, Option<flock::Lock>> {
     let most_recent = deletion_candidates.iter()
@@ -1,10 +1,14 @@
 //! This module contains files for saving intermediate work-products.
 
+use crate::persist::data::SerializedWorkProduct;
+use crate::persist::file_format;
 use crate::persist::fs::*;
 use rustc::dep_graph::{WorkProduct, WorkProductId, WorkProductFileKind};
 use rustc::session::Session;
 use rustc_fs_util::link_or_copy;
-use std::path::PathBuf;
+use rustc_serialize::{Decodable as RustcDecodable, Encodable as RustcEncodable};
+use rustc_serialize::opaque::{Decoder, Encoder};
+use std::path::{Path, PathBuf};
 use std::fs as std_fs;
 
 pub fn copy_cgu_workproducts_to_incr_comp_cache_dir(
@@ -26,6 +30,7 @@ pub fn copy_cgu_workproducts_to_incr_comp_cache_dir(
                      WorkProductFileKind::Object => "o",
                      WorkProductFileKind::Bytecode => "bc",
                      WorkProductFileKind::BytecodeCompressed => "bc.z",
+                     WorkProductFileKind::DwarfObject => "dwo",
                  };
                  let file_name = format!("{}.{}", cgu_name, extension);
                  let path_in_incr_dir = in_incr_comp_dir_sess(sess, &file_name);
@@ -53,8 +58,12 @@ pub fn copy_cgu_workproducts_to_incr_comp_cache_dir(
 }
 
 pub fn delete_workproduct_files(sess: &Session, work_product: &WorkProduct) {
+    delete_workproduct_files_in(sess, &sess.incr_comp_session_dir(), work_product)
+}
+
+fn delete_workproduct_files_in(sess: &Session, session_dir: &Path, work_product: &WorkProduct) {
     for &(_, ref file_name) in &work_product.saved_files {
-        let path = in_incr_comp_dir_sess(sess, file_name);
+        let path = in_incr_comp_dir(session_dir, file_name);
         match std_fs::remove_file(&path) {
             Ok(()) => { }
             Err(err) => {
@@ -65,3 +74,69 @@ pub fn delete_workproduct_files(sess: &Session, work_product: &WorkProduct) {
         }
     }
 }
+
+/// Evicts work products from `session_dir`, the finalized session directory
+/// of another compilation session, until at least `bytes_to_free` bytes have
+/// been freed. The evicted work products are also removed from the work
+/// product index of the directory, so that the next session reusing it only
+/// has to recompile their codegen units. Returns the number of bytes freed and
+/// the number of work products evicted.
+pub fn evict_workproducts(sess: &Session, session_dir: &Path, bytes_to_free: u64) -> (u64, usize) {
+    let index_path = work_products_path_from(session_dir);
+    let index = file_format::read_file(false, &index_path);
+    let mut work_products: Vec<SerializedWorkProduct> = match index {
+        Ok(Some((data, start_pos))) => {
+            let mut decoder = Decoder::new(&data[..], start_pos);
+            match RustcDecodable::decode(&mut decoder) {
+                Ok(work_products) => work_products,
+                Err(_) => return (0, 0),
+            }
+        }
+        // No index, or one from another compiler version: nothing to evict.
+        _ => return (0, 0),
+    };
+    work_products.sort_by(|a, b| a.work_product.cgu_name.cmp(&b.work_product.cgu_name));
+
+    let mut freed = 0;
+    let mut evicted = 0;
+    work_products.retain(|swp| {
+        if freed >= bytes_to_free {
+            return true;
+        }
+        debug!("evict_workproducts() - evicting `{}` from `{}`",
+               swp.work_product.cgu_name,
+               session_dir.display());
+        freed += swp.work_product.saved_files
+            .iter()
+            .filter_map(|&(_, ref name)| in_incr_comp_dir(session_dir, name).metadata().ok())
+            .map(|metadata| metadata.len())
+            .sum::<u64>();
+        delete_workproduct_files_in(sess, session_dir, &swp.work_product);
+        evicted += 1;
+        false
+    });
+
+    if evicted == 0 {
+        return (0, 0);
+    }
+
+    // Write the index of the remaining work products. The old index has to be
+    // deleted rather than overwritten, since it may be hard-linked into other
+    // session directories.
+    let mut encoder = Encoder::new(Vec::new());
+    file_format::write_file_header(&mut encoder);
+    work_products.encode(&mut encoder).unwrap();
+    let result = std_fs::remove_file(&index_path)
+        .and_then(|()| std_fs::write(&index_path, encoder.into_inner()));
+    if let Err(err) = result {
+        sess.warn(&format!("failed to update the work product index `{}` after \
+                            evicting work products: {}",
+                           index_path.display(),
+                           err));
+        // Without an index, the next session reusing the directory simply
+        // doesn't reuse any work products.
+        let _ = std_fs::remove_file(&index_path);
+    }
+
+    (freed, evicted)
+}
@@ -641,6 +641,12 @@ fn escape_dep_filename(filename: &FileName) -> String {
     filename.to_string().replace(" ", "\\ ")
 }
 
+/// Makefile comments only end at a newline, so make sure a variable's value
+/// can't contain one.
+fn escape_dep_env(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('\n', "\\n").replace('\r', "\\r")
+}
+
 fn write_out_deps(compiler: &Compiler, outputs: &OutputFilenames, out_filenames: &[PathBuf]) {
     let sess = &compiler.sess;
     // Write out dependency rules to the dep-info file if requested
@@ -660,6 +666,19 @@ fn write_out_deps(compiler: &Compiler, outputs: &OutputFilenames, out_filenames:
             .map(|fmap| escape_dep_filename(&fmap.unmapped_path.as_ref().unwrap_or(&fmap.name)))
             .collect();
 
+        // Files that proc macros read through `proc_macro::tracked_path`.
+        let mut tracked_files: Vec<_> = sess.parse_sess.file_depinfo
+            .borrow()
+            .iter()
+            .map(|path| escape_dep_filename(&FileName::Real(PathBuf::from(&*path.as_str()))))
+            .collect();
+        tracked_files.sort();
+        for path in tracked_files {
+            if !files.contains(&path) {
+                files.push(path);
+            }
+        }
+
         if sess.binary_dep_depinfo() {
             for cnum in compiler.cstore.crates_untracked() {
                 let metadata = compiler.cstore.crate_data_as_rc_any(cnum);
@@ -687,6 +706,24 @@ fn write_out_deps(compiler: &Compiler, outputs: &OutputFilenames, out_filenames:
         for path in files {
             writeln!(file, "{}:", path)?;
         }
+
+        // Environment variables that proc macros read through `proc_macro::tracked_env`
+        // are recorded as comments, which build systems like Cargo understand.
+        let mut env_depinfo: Vec<_> = sess.parse_sess.env_depinfo
+            .borrow()
+            .iter()
+            .map(|&(k, v)| (k.as_str().to_string(), v.map(|v| v.as_str().to_string())))
+            .collect();
+        if !env_depinfo.is_empty() {
+            env_depinfo.sort();
+            writeln!(file)?;
+            for (k, v) in env_depinfo {
+                match v {
+                    Some(v) => writeln!(file, "# env-dep:{}={}", k, escape_dep_env(&v))?,
+                    None => writeln!(file, "# env-dep:{}", k)?,
+                }
+            }
+        }
         Ok(())
     })();
 
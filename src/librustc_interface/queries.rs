@@ -6,6 +6,7 @@ use rustc::session::config::{OutputFilenames, OutputType};
 use rustc::util::common::{time, ErrorReported};
 use rustc::hir;
 use rustc::hir::def_id::LOCAL_CRATE;
+use rustc::ty::TyCtxt;
 use rustc::ty::steal::Steal;
 use rustc::dep_graph::DepGraph;
 use std::cell::{Ref, RefMut, RefCell};
@@ -80,6 +81,7 @@ pub(crate) struct Queries {
     lower_to_hir: Query<(Steal<hir::map::Forest>, ExpansionResult)>,
     prepare_outputs: Query<OutputFilenames>,
     global_ctxt: Query<BoxedGlobalCtxt>,
+    analysis: Query<()>,
     ongoing_codegen: Query<Box<dyn Any>>,
     link: Query<()>,
 }
@@ -225,6 +227,28 @@ impl Compiler {
         })
     }
 
+    /// Runs type checking, borrow checking and the other analysis passes over the crate, without
+    /// starting code generation. Fails if any errors were reported along the way.
+    pub fn analysis(&self) -> Result<&Query<()>> {
+        self.queries.analysis.compute(|| {
+            self.global_ctxt()?.peek_mut().enter(|tcx| tcx.analysis(LOCAL_CRATE))?;
+            self.session().compile_status()
+        })
+    }
+
+    /// Runs `analysis` and, if it succeeded, calls `f` with the type context.
+    ///
+    /// This is the entry point for tools that only want to inspect the results of analysis,
+    /// e.g. the typeck tables of every body: code generation is never started, and the
+    /// `GlobalCtxt` remains available for further calls afterwards.
+    pub fn enter_analysis<F, R>(&self, f: F) -> Result<R>
+    where
+        F: for<'tcx> FnOnce(TyCtxt<'tcx>) -> R,
+    {
+        self.analysis()?;
+        Ok(self.global_ctxt()?.peek_mut().enter(f))
+    }
+
     pub fn ongoing_codegen(&self) -> Result<&Query<Box<dyn Any>>> {
         self.queries.ongoing_codegen.compute(|| {
             let outputs = self.prepare_outputs()?;
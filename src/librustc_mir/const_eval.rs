@@ -7,8 +7,9 @@ use std::hash::Hash;
 use std::collections::hash_map::Entry;
 use std::convert::TryInto;
 
-use rustc::hir::def::DefKind;
+use rustc::hir::{self, def::DefKind};
 use rustc::hir::def_id::DefId;
+use rustc::lint::builtin::LONG_RUNNING_CONST_EVAL;
 use rustc::mir::interpret::{ConstEvalErr, ErrorHandled, ScalarMaybeUndef};
 use rustc::mir;
 use rustc::ty::{self, Ty, TyCtxt, subst::Subst};
@@ -24,15 +25,12 @@ use crate::interpret::{self,
     RawConst, ConstValue, Machine,
     InterpResult, InterpErrorInfo, GlobalId, InterpCx, StackPopCleanup,
     Allocation, AllocId, MemoryKind, Memory,
-    snapshot, RefTracking, intern_const_alloc_recursive,
+    RefTracking, intern_const_alloc_recursive,
 };
 
-/// Number of steps until the detector even starts doing anything.
-/// Also, a warning is shown to the user when this number is reached.
-const STEPS_UNTIL_DETECTOR_ENABLED: isize = 1_000_000;
-/// The number of steps between loop detector snapshots.
-/// Should be a power of two for performance reasons.
-const DETECTOR_SNAPSHOT_PERIOD: isize = 256;
+/// Number of steps after which the `long_running_const_eval` lint tells the user
+/// that a constant is taking a long time to evaluate.
+const STEPS_UNTIL_LINT: usize = 1_000_000;
 
 /// The `InterpCx` is only meant to be used to do field and index projections into constants for
 /// `simd_shuffle` and const patterns in match arms.
@@ -47,7 +45,7 @@ fn mk_eval_cx<'mir, 'tcx>(
     param_env: ty::ParamEnv<'tcx>,
 ) -> CompileTimeEvalContext<'mir, 'tcx> {
     debug!("mk_eval_cx: {:?}", param_env);
-    InterpCx::new(tcx.at(span), param_env, CompileTimeInterpreter::new(), Default::default())
+    InterpCx::new(tcx.at(span), param_env, CompileTimeInterpreter::new(tcx), Default::default())
 }
 
 fn op_to_const<'tcx>(
@@ -208,22 +206,20 @@ impl Error for ConstEvalError {
 }
 
 // Extra machine state for CTFE, and the Machine instance
-pub struct CompileTimeInterpreter<'mir, 'tcx> {
-    /// When this value is negative, it indicates the number of interpreter
-    /// steps *until* the loop detector is enabled. When it is positive, it is
-    /// the number of steps after the detector has been enabled modulo the loop
-    /// detector period.
-    pub(super) steps_since_detector_enabled: isize,
-
-    /// Extra state to detect loops.
-    pub(super) loop_detector: snapshot::InfiniteLoopDetector<'mir, 'tcx>,
+pub struct CompileTimeInterpreter {
+    /// The number of terminators evaluated so far.
+    pub(super) steps: usize,
+
+    /// The number of steps after which evaluation is aborted, or 0 if there
+    /// is no limit. Set by `#![const_eval_limit]` or `-Z const-eval-limit`.
+    pub(super) step_limit: usize,
 }
 
-impl<'mir, 'tcx> CompileTimeInterpreter<'mir, 'tcx> {
-    fn new() -> Self {
+impl CompileTimeInterpreter {
+    fn new(tcx: TyCtxt<'_>) -> Self {
         CompileTimeInterpreter {
-            loop_detector: Default::default(),
-            steps_since_detector_enabled: -STEPS_UNTIL_DETECTOR_ENABLED,
+            steps: 0,
+            step_limit: *tcx.sess.const_eval_limit.get(),
         }
     }
 }
@@ -290,7 +286,7 @@ impl<K: Hash + Eq, V> interpret::AllocMap<K, V> for FxHashMap<K, V> {
 }
 
 crate type CompileTimeEvalContext<'mir, 'tcx> =
-    InterpCx<'mir, 'tcx, CompileTimeInterpreter<'mir, 'tcx>>;
+    InterpCx<'mir, 'tcx, CompileTimeInterpreter>;
 
 impl interpret::MayLeak for ! {
     #[inline(always)]
@@ -300,7 +296,7 @@ impl interpret::MayLeak for ! {
     }
 }
 
-impl<'mir, 'tcx> interpret::Machine<'mir, 'tcx> for CompileTimeInterpreter<'mir, 'tcx> {
+impl<'mir, 'tcx> interpret::Machine<'mir, 'tcx> for CompileTimeInterpreter {
     type MemoryKinds = !;
     type PointerTag = ();
     type ExtraFnVal = !;
@@ -444,27 +440,15 @@ impl<'mir, 'tcx> interpret::Machine<'mir, 'tcx> for CompileTimeInterpreter<'mir,
     }
 
     fn before_terminator(ecx: &mut InterpCx<'mir, 'tcx, Self>) -> InterpResult<'tcx> {
-        {
-            let steps = &mut ecx.machine.steps_since_detector_enabled;
-
-            *steps += 1;
-            if *steps < 0 {
-                return Ok(());
-            }
-
-            *steps %= DETECTOR_SNAPSHOT_PERIOD;
-            if *steps != 0 {
-                return Ok(());
-            }
+        ecx.machine.steps += 1;
+        let steps = ecx.machine.steps;
+        if steps == STEPS_UNTIL_LINT {
+            lint_long_running_const_eval(ecx);
         }
-
-        let span = ecx.frame().span;
-        ecx.machine.loop_detector.observe_and_analyze(
-            *ecx.tcx,
-            span,
-            &ecx.memory,
-            &ecx.stack[..],
-        )
+        if ecx.machine.step_limit != 0 && steps >= ecx.machine.step_limit {
+            throw_exhaust!(StepLimitReached)
+        }
+        Ok(())
     }
 
     #[inline(always)]
@@ -479,6 +463,40 @@ impl<'mir, 'tcx> interpret::Machine<'mir, 'tcx> for CompileTimeInterpreter<'mir,
     }
 }
 
+/// Tells the user that the constant being evaluated is taking a long time, and
+/// shows the const call stack that it is currently stuck in.
+fn lint_long_running_const_eval(ecx: &CompileTimeEvalContext<'_, '_>) {
+    let stacktrace = ecx.generate_stacktrace(None);
+    let hir_id = stacktrace
+        .iter()
+        .rev()
+        .filter_map(|frame| frame.lint_root)
+        .next()
+        .unwrap_or(hir::CRATE_HIR_ID);
+    // The outermost frame is the constant itself.
+    let span = ecx.stack().first().map_or(ecx.tcx.span, |frame| frame.span);
+    let mut lint = ecx.tcx.struct_span_lint_hir(
+        LONG_RUNNING_CONST_EVAL,
+        hir_id,
+        span,
+        "constant evaluation is taking a long time",
+    );
+    // Like for const eval errors, skip the last frame, which is the constant itself.
+    if stacktrace.len() > 0 {
+        for frame_info in &stacktrace[..stacktrace.len() - 1] {
+            lint.span_label(frame_info.call_site, frame_info.to_string());
+        }
+    }
+    if ecx.machine.step_limit != 0 {
+        lint.note(&format!(
+            "evaluation will be aborted after {} steps; the limit can be changed with \
+             `#![const_eval_limit]`",
+            ecx.machine.step_limit,
+        ));
+    }
+    lint.emit();
+}
+
 /// Extracts a field of a (variant of a) const.
 // this function uses `unwrap` copiously, because an already validated constant must have valid
 // fields and can thus never fail outside of compiler bugs
@@ -668,7 +686,7 @@ pub fn const_eval_raw_provider<'tcx>(
     let mut ecx = InterpCx::new(
         tcx.at(span),
         key.param_env,
-        CompileTimeInterpreter::new(),
+        CompileTimeInterpreter::new(tcx),
         Default::default()
     );
 
@@ -151,7 +151,7 @@ impl<'rt, 'mir, 'tcx> InternVisitor<'rt, 'mir, 'tcx> {
 }
 
 impl<'rt, 'mir, 'tcx>
-    ValueVisitor<'mir, 'tcx, CompileTimeInterpreter<'mir, 'tcx>>
+    ValueVisitor<'mir, 'tcx, CompileTimeInterpreter>
 for
     InternVisitor<'rt, 'mir, 'tcx>
 {
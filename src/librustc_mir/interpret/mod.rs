@@ -7,7 +7,6 @@ mod operand;
 mod machine;
 mod memory;
 mod operator;
-pub(crate) mod snapshot; // for const_eval
 mod step;
 mod terminator;
 mod traits;
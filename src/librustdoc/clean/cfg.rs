@@ -120,6 +120,72 @@ impl Cfg {
         }
     }
 
+    /// Keeps the parts of the configuration that depend on crate features, e.g., turns
+    /// `all(unix, feature = "serde")` into `feature = "serde"`.
+    ///
+    /// Returns `None` if no part of the configuration is a feature requirement.
+    pub fn features_only(&self) -> Option<Cfg> {
+        match *self {
+            Cfg::Cfg(name, Some(_)) if name == sym::feature => Some(self.clone()),
+            Cfg::False | Cfg::True | Cfg::Cfg(..) => None,
+            // Dropping a part of a negation or of a union would show a stricter requirement than
+            // the real one, so these are kept only if they are entirely about features.
+            Cfg::Not(ref child) => {
+                if child.features_only().as_ref() == Some(child) {
+                    Some(self.clone())
+                } else {
+                    None
+                }
+            }
+            Cfg::Any(ref sub_cfgs) => {
+                let only_features = sub_cfgs.iter()
+                    .all(|sub_cfg| sub_cfg.features_only().as_ref() == Some(sub_cfg));
+                if only_features {
+                    Some(self.clone())
+                } else {
+                    None
+                }
+            }
+            Cfg::All(ref sub_cfgs) => {
+                sub_cfgs.iter().filter_map(Cfg::features_only).fold(None, |acc, sub_cfg| {
+                    Some(match acc {
+                        Some(acc) => acc & sub_cfg,
+                        None => sub_cfg,
+                    })
+                })
+            }
+        }
+    }
+
+    /// Removes the requirements that `assume` already implies, e.g., turns
+    /// `all(unix, feature = "serde")` into `feature = "serde"` when `assume` is `unix`.
+    ///
+    /// Returns `None` if `assume` implies the whole configuration.
+    pub fn simplify_with(&self, assume: &Cfg) -> Option<Cfg> {
+        if self == assume {
+            return None;
+        }
+        let assumed = |cfg: &Cfg| match *assume {
+            Cfg::All(ref sub_cfgs) => sub_cfgs.contains(cfg),
+            _ => cfg == assume,
+        };
+        match *self {
+            Cfg::All(ref sub_cfgs) => {
+                let mut sub_cfgs = sub_cfgs.iter()
+                    .filter(|sub_cfg| !assumed(sub_cfg))
+                    .cloned()
+                    .collect::<Vec<_>>();
+                match sub_cfgs.len() {
+                    0 => None,
+                    1 => sub_cfgs.pop(),
+                    _ => Some(Cfg::All(sub_cfgs)),
+                }
+            }
+            _ if assumed(self) => None,
+            _ => Some(self.clone()),
+        }
+    }
+
     /// Whether the configuration consists of just `Cfg` or `Not`.
     fn is_simple(&self) -> bool {
         match *self {
@@ -189,7 +255,7 @@ impl Cfg {
 
     fn should_use_with_in_description(&self) -> bool {
         match *self {
-            Cfg::Cfg(name, _) if name == sym::target_feature => true,
+            Cfg::Cfg(name, _) if name == sym::target_feature || name == sym::feature => true,
             _ => false,
         }
     }
@@ -213,14 +279,26 @@ impl ops::BitAndAssign for Cfg {
             (&mut Cfg::False, _) | (_, Cfg::True) => {},
             (s, Cfg::False) => *s = Cfg::False,
             (s @ &mut Cfg::True, b) => *s = b,
-            (&mut Cfg::All(ref mut a), Cfg::All(ref mut b)) => a.append(b),
-            (&mut Cfg::All(ref mut a), ref mut b) => a.push(mem::replace(b, Cfg::True)),
+            // Requirements that are already there aren't repeated, since a propagated cfg is
+            // often the same as the one of the child item.
+            (&mut Cfg::All(ref mut a), Cfg::All(b)) => {
+                for c in b {
+                    if !a.contains(&c) {
+                        a.push(c);
+                    }
+                }
+            },
+            (&mut Cfg::All(ref mut a), b) => if !a.contains(&b) {
+                a.push(b);
+            },
             (s, Cfg::All(mut a)) => {
                 let b = mem::replace(s, Cfg::True);
-                a.push(b);
+                if !a.contains(&b) {
+                    a.push(b);
+                }
                 *s = Cfg::All(a);
             },
-            (s, b) => {
+            (s, b) => if *s != b {
                 let a = mem::replace(s, Cfg::True);
                 *s = Cfg::All(vec![a, b]);
             },
@@ -398,6 +476,13 @@ impl<'a> fmt::Display for Html<'a> {
                         } else {
                             return write!(fmt, "target feature <code>{}</code>", feat);
                         },
+                    ("feature", Some(feat)) =>
+                        if self.1 {
+                            return write!(fmt, "<code>{}</code>", Escape(&*feat.as_str()));
+                        } else {
+                            return write!(fmt, "crate feature <code>{}</code>",
+                                          Escape(&*feat.as_str()));
+                        },
                     _ => "",
                 };
                 if !human_readable.is_empty() {
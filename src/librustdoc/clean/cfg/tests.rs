@@ -117,6 +117,67 @@ fn test_cfg_and() {
             word_cfg("a") & word_cfg("b") & word_cfg("c"),
             Cfg::All(vec![word_cfg("a"), word_cfg("b"), word_cfg("c")])
         );
+
+        assert_eq!(word_cfg("a") & word_cfg("a"), word_cfg("a"));
+        assert_eq!(
+            word_cfg("a") & word_cfg("b") & word_cfg("a"),
+            Cfg::All(vec![word_cfg("a"), word_cfg("b")])
+        );
+        assert_eq!(
+            (word_cfg("a") & word_cfg("b")) & (word_cfg("b") & word_cfg("c")),
+            Cfg::All(vec![word_cfg("a"), word_cfg("b"), word_cfg("c")])
+        );
+    })
+}
+
+#[test]
+fn test_features_only() {
+    with_default_globals(|| {
+        let serde = name_value_cfg("feature", "serde");
+        let std = name_value_cfg("feature", "std");
+
+        assert_eq!(serde.features_only(), Some(serde.clone()));
+        assert_eq!(word_cfg("unix").features_only(), None);
+        assert_eq!(name_value_cfg("target_os", "linux").features_only(), None);
+        assert_eq!((word_cfg("unix") & serde.clone()).features_only(), Some(serde.clone()));
+        assert_eq!(
+            (serde.clone() & word_cfg("unix") & std.clone()).features_only(),
+            Some(serde.clone() & std.clone())
+        );
+        assert_eq!((!std.clone()).features_only(), Some(!std.clone()));
+        assert_eq!(
+            (serde.clone() | std.clone()).features_only(),
+            Some(serde.clone() | std.clone())
+        );
+        assert_eq!((serde.clone() | word_cfg("unix")).features_only(), None);
+        assert_eq!((!(serde.clone() & word_cfg("unix"))).features_only(), None);
+    })
+}
+
+#[test]
+fn test_simplify_with() {
+    with_default_globals(|| {
+        let serde = name_value_cfg("feature", "serde");
+        let unix = word_cfg("unix");
+        let arm = name_value_cfg("target_arch", "arm");
+
+        assert_eq!(unix.simplify_with(&unix), None);
+        assert_eq!(unix.simplify_with(&(unix.clone() & arm.clone())), None);
+        assert_eq!(serde.simplify_with(&unix), Some(serde.clone()));
+        assert_eq!((unix.clone() & serde.clone()).simplify_with(&unix), Some(serde.clone()));
+        let unix_arm = unix.clone() & arm.clone();
+        assert_eq!(
+            (unix.clone() & arm.clone() & serde.clone()).simplify_with(&unix_arm),
+            Some(serde.clone())
+        );
+        assert_eq!(
+            (unix.clone() & arm.clone() & serde.clone()).simplify_with(&unix),
+            Some(arm.clone() & serde.clone())
+        );
+        assert_eq!(
+            (unix.clone() | serde.clone()).simplify_with(&unix),
+            Some(unix.clone() | serde.clone())
+        );
     })
 }
 
@@ -322,6 +383,10 @@ fn test_render_short_html() {
             ).render_short_html(),
             "x86-64 and <code>sse2</code>"
         );
+        assert_eq!(
+            name_value_cfg("feature", "serde").render_short_html(),
+            "<code>serde</code>"
+        );
     })
 }
 
@@ -408,5 +473,9 @@ fn test_render_long_html() {
             "This is supported on <strong>x86-64 and target feature \
             <code>sse2</code></strong> only."
         );
+        assert_eq!(
+            name_value_cfg("feature", "serde").render_long_html(),
+            "This is supported with <strong>crate feature <code>serde</code></strong> only."
+        );
     })
 }
@@ -857,6 +857,7 @@ impl Attributes {
         let mut doc_strings = vec![];
         let mut sp = None;
         let mut cfg = Cfg::True;
+        let mut has_doc_cfg = false;
         let mut doc_line = 0;
 
         let other_attrs = attrs.iter().filter_map(|attr| {
@@ -885,6 +886,7 @@ impl Attributes {
                                 Ok(new_cfg) => cfg &= new_cfg,
                                 Err(e) => diagnostic.span_err(e.span, e.msg),
                             }
+                            has_doc_cfg = true;
                             return None;
                         } else if let Some((filename, contents)) = Attributes::extract_include(&mi)
                         {
@@ -916,6 +918,21 @@ impl Attributes {
             }
         }
 
+        // Unless the cfg is given with #[doc(cfg(...))], show the crate features that
+        // #[cfg(...)] attributes require
+        if !has_doc_cfg {
+            for attr in attrs.iter().filter(|a| a.check_name(sym::cfg)) {
+                let cfg_mi = match attr.meta_item_list() {
+                    Some(ref items) if items.len() == 1 => items[0].meta_item().cloned(),
+                    _ => None,
+                };
+                if let Some(feat_cfg) = cfg_mi.and_then(|mi| Cfg::parse(&mi).ok())
+                                              .and_then(|c| c.features_only()) {
+                    cfg &= feat_cfg;
+                }
+            }
+        }
+
         let inner_docs = attrs.iter()
                               .filter(|a| a.check_name(sym::doc))
                               .next()
@@ -2729,7 +2746,7 @@ impl GetDefId for Type {
 }
 
 impl PrimitiveType {
-    fn from_str(s: &str) -> Option<PrimitiveType> {
+    pub fn from_str(s: &str) -> Option<PrimitiveType> {
         match s {
             "isize" => Some(PrimitiveType::Isize),
             "i8" => Some(PrimitiveType::I8),
@@ -2831,6 +2848,19 @@ impl From<ast::FloatTy> for PrimitiveType {
     }
 }
 
+impl From<hir::PrimTy> for PrimitiveType {
+    fn from(prim_ty: hir::PrimTy) -> PrimitiveType {
+        match prim_ty {
+            hir::PrimTy::Int(int_ty) => int_ty.into(),
+            hir::PrimTy::Uint(uint_ty) => uint_ty.into(),
+            hir::PrimTy::Float(float_ty) => float_ty.into(),
+            hir::PrimTy::Str => PrimitiveType::Str,
+            hir::PrimTy::Bool => PrimitiveType::Bool,
+            hir::PrimTy::Char => PrimitiveType::Char,
+        }
+    }
+}
+
 impl Clean<Type> for hir::Ty {
     fn clean(&self, cx: &DocContext<'_>) -> Type {
         use rustc::hir::*;
@@ -4078,6 +4108,9 @@ impl Clean<Vec<Item>> for doctree::Import<'_> {
                     Some(rustc::ty::Attributes::Borrowed(self.attrs)),
                     &mut visited
                 ) {
+                    if let Some(did) = path.res.opt_def_id() {
+                        cx.inlined_reexports.borrow_mut().insert(did, self.id);
+                    }
                     return items;
                 }
             }
@@ -4254,14 +4287,7 @@ fn resolve_type(cx: &DocContext<'_>,
     }
 
     let is_generic = match path.res {
-        Res::PrimTy(p) => match p {
-            hir::Str => return Primitive(PrimitiveType::Str),
-            hir::Bool => return Primitive(PrimitiveType::Bool),
-            hir::Char => return Primitive(PrimitiveType::Char),
-            hir::Int(int_ty) => return Primitive(int_ty.into()),
-            hir::Uint(uint_ty) => return Primitive(uint_ty.into()),
-            hir::Float(float_ty) => return Primitive(float_ty.into()),
-        },
+        Res::PrimTy(p) => return Primitive(p.into()),
         Res::SelfTy(..) if path.segments.len() == 1 => {
             return Generic(kw::SelfUpper.to_string());
         }
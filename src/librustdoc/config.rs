@@ -1,6 +1,7 @@
 use std::collections::BTreeMap;
+use std::convert::TryFrom;
 use std::fmt;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use errors;
 use getopts;
@@ -19,11 +20,32 @@ use crate::core::new_handler;
 use crate::externalfiles::ExternalHtml;
 use crate::html;
 use crate::html::{static_files};
+use crate::html::layout::Templates;
 use crate::html::markdown::{IdMap};
 use crate::opts;
 use crate::passes::{self, DefaultPassOption};
+use crate::scrape_examples::{AllCallLocations, ScrapeExamplesOptions, load_call_locations};
 use crate::theme;
 
+/// The format that rustdoc writes the documentation in.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum OutputFormat {
+    Json,
+    Html,
+}
+
+impl TryFrom<&str> for OutputFormat {
+    type Error = String;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match value {
+            "json" => Ok(OutputFormat::Json),
+            "html" => Ok(OutputFormat::Html),
+            _ => Err(format!("unknown output format `{}`", value)),
+        }
+    }
+}
+
 /// Configuration options for rustdoc.
 #[derive(Clone)]
 pub struct Options {
@@ -85,6 +107,9 @@ pub struct Options {
     /// For example, using ignore-foo to ignore running the doctest on any target that
     /// contains "foo" as a substring
     pub enable_per_target_ignores: bool,
+    /// Whether to compile doctests that don't need a crate of their own into a single binary,
+    /// instead of compiling each of them separately.
+    pub merge_doctests: bool,
 
     /// The path to a rustc-like binary to build tests with. If not set, we
     /// default to loading from $sysroot/bin/rustc.
@@ -106,6 +131,11 @@ pub struct Options {
     /// Whether to run the `calculate-doc-coverage` pass, which counts the number of public items
     /// with and without documentation.
     pub show_coverage: bool,
+    /// The format that we output when rendering. Defaults to HTML.
+    pub output_format: Option<OutputFormat>,
+    /// If present, type-check the input and write the calls it makes to the functions of other
+    /// crates to a file, instead of documenting it.
+    pub scrape_examples_options: Option<ScrapeExamplesOptions>,
 
     // Options that alter generated documentation pages
 
@@ -150,11 +180,14 @@ impl fmt::Debug for Options {
             .field("manual_passes", &self.manual_passes)
             .field("display_warnings", &self.display_warnings)
             .field("show_coverage", &self.show_coverage)
+            .field("output_format", &self.output_format)
+            .field("scrape_examples_options", &self.scrape_examples_options)
             .field("crate_version", &self.crate_version)
             .field("render_options", &self.render_options)
             .field("runtool", &self.runtool)
             .field("runtool_args", &self.runtool_args)
             .field("enable-per-target-ignores", &self.enable_per_target_ignores)
+            .field("merge_doctests", &self.merge_doctests)
             .finish()
     }
 }
@@ -184,6 +217,9 @@ pub struct RenderOptions {
     pub themes: Vec<PathBuf>,
     /// If present, CSS file that contains rules to add to the default CSS.
     pub extension_css: Option<PathBuf>,
+    /// The templates of the page shell and of the sidebar, which `--html-templates` can
+    /// override.
+    pub templates: Templates,
     /// A map of crate names to the URL to use instead of querying the crate's `html_root_url`.
     pub extern_html_root_urls: BTreeMap<String, String>,
     /// If present, suffix added to CSS/JavaScript files when referencing them in generated pages.
@@ -219,6 +255,9 @@ pub struct RenderOptions {
     pub generate_search_filter: bool,
     /// Option (disabled by default) to generate files used by RLS and some other tools.
     pub generate_redirect_pages: bool,
+    /// The calls to the crate's functions found in its examples and tests, which are shown on the
+    /// pages of those functions.
+    pub call_locations: AllCallLocations,
 }
 
 impl Options {
@@ -287,7 +326,15 @@ impl Options {
             println!("rustdoc: [theme-checker] Starting tests!");
             for theme_file in to_check.iter() {
                 print!(" - Checking \"{}\"...", theme_file);
-                let (success, differences) = theme::test_theme_against(theme_file, &paths, &diag);
+                // A directory is checked as the templates given to `--html-templates`.
+                let (success, differences) = if Path::new(theme_file).is_dir() {
+                    match Templates::load(Path::new(theme_file), &diag) {
+                        Some(templates) => (true, templates.check()),
+                        None => (false, Vec::new()),
+                    }
+                } else {
+                    theme::test_theme_against(theme_file, &paths, &diag)
+                };
                 if !differences.is_empty() || !success {
                     println!(" FAILED");
                     errors += 1;
@@ -376,6 +423,27 @@ impl Options {
             }
         }
 
+        let templates = match matches.opt_str("html-templates") {
+            Some(dir) => {
+                if !Path::new(&dir).is_dir() {
+                    diag.struct_err("option --html-templates argument must be a directory").emit();
+                    return Err(1);
+                }
+                let templates = match Templates::load(Path::new(&dir), &diag) {
+                    Some(templates) => templates,
+                    None => return Err(1),
+                };
+                if !templates.check().is_empty() {
+                    diag.struct_err(&format!("invalid templates: \"{}\"", dir))
+                        .help("check what's wrong with the --theme-checker option")
+                        .emit();
+                    return Err(1);
+                }
+                templates
+            }
+            None => Templates::default(),
+        };
+
         let edition = if let Some(e) = matches.opt_str("edition") {
             match e.parse() {
                 Ok(e) => e,
@@ -438,6 +506,23 @@ impl Options {
         let show_coverage = matches.opt_present("show-coverage");
         let document_private = matches.opt_present("document-private-items");
 
+        let output_format = match matches.opt_str("output-format") {
+            Some(s) => match OutputFormat::try_from(s.as_str()) {
+                Ok(OutputFormat::Json) if !nightly_options::is_unstable_enabled(matches) => {
+                    diag.struct_err("the `-Z unstable-options` flag must also be passed to \
+                                     enable the json output format")
+                        .emit();
+                    return Err(1);
+                }
+                Ok(o) => Some(o),
+                Err(e) => {
+                    diag.struct_err(&e).emit();
+                    return Err(1);
+                }
+            },
+            None => None,
+        };
+
         let default_passes = if matches.opt_present("no-defaults") {
             passes::DefaultPassOption::None
         } else if show_coverage && document_private {
@@ -476,6 +561,8 @@ impl Options {
         let generate_search_filter = !matches.opt_present("disable-per-crate-search");
         let persist_doctests = matches.opt_str("persist-doctests").map(PathBuf::from);
         let generate_redirect_pages = matches.opt_present("generate-redirect-pages");
+        let scrape_examples_options = ScrapeExamplesOptions::new(&matches, &diag)?;
+        let call_locations = load_call_locations(matches.opt_strs("with-examples"), &diag)?;
         let test_builder = matches.opt_str("test-builder").map(PathBuf::from);
         let codegen_options_strs = matches.opt_strs("C");
         let lib_strs = matches.opt_strs("L");
@@ -483,6 +570,7 @@ impl Options {
         let runtool = matches.opt_str("runtool");
         let runtool_args = matches.opt_strs("runtool-arg");
         let enable_per_target_ignores = matches.opt_present("enable-per-target-ignores");
+        let merge_doctests = matches.opt_present("merge-doctests");
 
         let (lint_opts, describe_lints, lint_cap) = get_cmd_lint_options(matches, error_format);
 
@@ -511,11 +599,14 @@ impl Options {
             manual_passes,
             display_warnings,
             show_coverage,
+            output_format,
+            scrape_examples_options,
             crate_version,
             persist_doctests,
             runtool,
             runtool_args,
             enable_per_target_ignores,
+            merge_doctests,
             test_builder,
             render_options: RenderOptions {
                 output,
@@ -525,6 +616,7 @@ impl Options {
                 sort_modules_alphabetically,
                 themes,
                 extension_css,
+                templates,
                 extern_html_root_urls,
                 resource_suffix,
                 enable_minification,
@@ -536,6 +628,7 @@ impl Options {
                 markdown_playground_url,
                 generate_search_filter,
                 generate_redirect_pages,
+                call_locations,
             }
         })
     }
@@ -551,7 +644,6 @@ impl Options {
 fn check_deprecated_options(matches: &getopts::Matches, diag: &errors::Handler) {
     let deprecated_flags = [
        "input-format",
-       "output-format",
        "no-defaults",
        "passes",
     ];
@@ -21,6 +21,7 @@ use syntax::json::JsonEmitter;
 use syntax::symbol::sym;
 use errors;
 use errors::emitter::{Emitter, EmitterWriter};
+use errors::sarif_emitter::SarifEmitter;
 
 use std::cell::RefCell;
 use std::mem;
@@ -33,6 +34,7 @@ use crate::clean::{MAX_DEF_ID, AttributesExt};
 use crate::html::render::RenderInfo;
 
 use crate::passes;
+use crate::scrape_examples;
 
 pub use rustc::session::config::{Input, Options, CodegenOptions};
 pub use rustc::session::search_paths::SearchPath;
@@ -68,6 +70,9 @@ pub struct DocContext<'tcx> {
     // FIXME(eddyb) make this a `ty::TraitRef<'tcx>` set.
     pub generated_synthetics: RefCell<FxHashSet<(Ty<'tcx>, DefId)>>,
     pub auto_traits: Vec<DefId>,
+    /// Items inlined from other crates -> the `pub use` that inlined them. The documentation
+    /// written on the re-export is checked by the passes, unlike the rest of their documentation.
+    pub inlined_reexports: RefCell<FxHashMap<DefId, HirId>>,
 }
 
 impl<'tcx> DocContext<'tcx> {
@@ -210,6 +215,13 @@ pub fn new_handler(error_format: ErrorOutputType,
                 ).ui_testing(ui_testing)
             )
         },
+        ErrorOutputType::Sarif { pretty } => {
+            // The compiler session writes the SARIF log of a successful run, so these handlers
+            // only write one when they report an error themselves.
+            Box::new(
+                SarifEmitter::stderr(None, source_map.map(|cm| cm as _), pretty).skip_empty(true)
+            )
+        },
     };
 
     errors::Handler::with_emitter_and_flags(
@@ -247,6 +259,7 @@ pub fn run_core(options: RustdocOptions) -> (clean::Crate, RenderInfo, RenderOpt
         mut manual_passes,
         display_warnings,
         render_options,
+        output_format,
         ..
     } = options;
 
@@ -372,6 +385,11 @@ pub fn run_core(options: RustdocOptions) -> (clean::Crate, RenderInfo, RenderOpt
 
             let mut renderinfo = RenderInfo::default();
             renderinfo.access_levels = access_levels;
+            renderinfo.output_format = output_format;
+            renderinfo.call_locations = scrape_examples::resolve_call_locations(
+                tcx,
+                &render_options.call_locations,
+            );
 
             let mut ctxt = DocContext {
                 tcx,
@@ -390,6 +408,7 @@ pub fn run_core(options: RustdocOptions) -> (clean::Crate, RenderInfo, RenderOpt
                 auto_traits: tcx.all_traits(LOCAL_CRATE).iter().cloned().filter(|trait_def_id| {
                     tcx.trait_is_auto(*trait_def_id)
                 }).collect(),
+                inlined_reexports: Default::default(),
             };
             debug!("crate: {:?}", tcx.hir().krate());
 
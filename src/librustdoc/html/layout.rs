@@ -1,9 +1,14 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
-use crate::externalfiles::ExternalHtml;
+use rustc_data_structures::fx::FxHashSet;
+
+use crate::externalfiles::{ExternalHtml, load_string};
 use crate::html::render::ensure_trailing_slash;
 use crate::html::format::{Buffer, Print};
 
+#[cfg(test)]
+mod tests;
+
 #[derive(Clone)]
 pub struct Layout {
     pub logo: String,
@@ -16,6 +21,8 @@ pub struct Layout {
     /// If false, the `select` element to have search filtering by crates on rendered docs
     /// won't be generated.
     pub generate_search_filter: bool,
+    /// The templates of the page shell and of the sidebar.
+    pub templates: Templates,
 }
 
 pub struct Page<'a> {
@@ -30,15 +37,9 @@ pub struct Page<'a> {
     pub static_extra_scripts: &'a [&'a str],
 }
 
-pub fn render<T: Print, S: Print>(
-    layout: &Layout,
-    page: &Page<'_>,
-    sidebar: S,
-    t: T,
-    themes: &[PathBuf],
-) -> String {
-    let static_root_path = page.static_root_path.unwrap_or(page.root_path);
-    format!("<!DOCTYPE html>\
+/// The default page shell. Variables are written `{name}` and braces are doubled, like in
+/// `format!`.
+pub const PAGE_TEMPLATE: &str = "<!DOCTYPE html>\
 <html lang=\"en\">\
 <head>\
     <meta charset=\"utf-8\">\
@@ -71,11 +72,7 @@ pub fn render<T: Print, S: Print>(
     </div>\
     <![endif]-->\
     {before_content}\
-    <nav class=\"sidebar\">\
-        <div class=\"sidebar-menu\">&#9776;</div>\
-        {logo}\
-        {sidebar}\
-    </nav>\
+    {sidebar}\
     <div class=\"theme-picker\">\
         <button id=\"theme-picker\" aria-label=\"Pick another theme!\">\
             <img src=\"{static_root_path}brush{suffix}.svg\" \
@@ -144,7 +141,8 @@ pub fn render<T: Print, S: Print>(
                 </p>\
                 <p>\
                     Search functions by type signature (e.g., \
-                    <code>vec -> usize</code> or <code>* -> vec</code>)\
+                    <code>vec -> usize</code>, <code>Vec&lt;u8&gt; -> String</code>, \
+                    <code>&amp;str, usize -> ()</code> or <code>* -> vec</code>)\
                 </p>\
                 <p>\
                     Search multiple things at once by splitting your query with comma (e.g., \
@@ -164,8 +162,83 @@ pub fn render<T: Print, S: Print>(
     {extra_scripts}\
     <script defer src=\"{root_path}search-index{suffix}.js\"></script>\
 </body>\
-</html>",
-    css_extension = if layout.css_file_extension.is_some() {
+</html>";
+
+/// The default sidebar, which is the `{sidebar}` of the page shell. Its own `{sidebar}` is the
+/// list of links to the sections and items of the page.
+pub const SIDEBAR_TEMPLATE: &str = "<nav class=\"sidebar\">\
+    <div class=\"sidebar-menu\">&#9776;</div>\
+    {logo}\
+    {sidebar}\
+</nav>";
+
+/// The variables that templates can use.
+const VARIABLES: &[&str] = &[
+    "title", "description", "keywords", "static_root_path", "root_path", "suffix", "themes",
+    "css_extension", "favicon", "in_header", "css_class", "before_content", "logo",
+    "filter_crates", "content", "after_content", "krate", "static_extra_scripts", "extra_scripts",
+    "sidebar",
+];
+
+/// The templates that generated pages are made from, which can be overridden with the
+/// `--html-templates` option.
+#[derive(Clone, Debug)]
+pub struct Templates {
+    pub page: String,
+    pub sidebar: String,
+}
+
+impl Default for Templates {
+    fn default() -> Templates {
+        Templates {
+            page: PAGE_TEMPLATE.to_owned(),
+            sidebar: SIDEBAR_TEMPLATE.to_owned(),
+        }
+    }
+}
+
+impl Templates {
+    /// Loads the `page.html` and `sidebar.html` overrides of the given directory. The missing ones
+    /// are left as the default.
+    pub fn load(dir: &Path, diag: &errors::Handler) -> Option<Templates> {
+        let load = |file: &str, default: &str| {
+            let path = dir.join(file);
+            if path.is_file() {
+                load_string(&path, diag).ok()
+            } else {
+                Some(default.to_owned())
+            }
+        };
+        Some(Templates {
+            page: load("page.html", PAGE_TEMPLATE)?,
+            sidebar: load("sidebar.html", SIDEBAR_TEMPLATE)?,
+        })
+    }
+
+    /// Checks the templates against the default ones. Returns the problems found, each prefixed
+    /// with the template it was found in.
+    pub fn check(&self) -> Vec<String> {
+        let mut problems = Vec::new();
+        for &(template, default, file) in &[
+            (&self.page, PAGE_TEMPLATE, "page.html"),
+            (&self.sidebar, SIDEBAR_TEMPLATE, "sidebar.html"),
+        ] {
+            problems.extend(check_template(template, default).into_iter()
+                                .map(|problem| format!("{}: {}", file, problem)));
+        }
+        problems
+    }
+}
+
+pub fn render<T: Print, S: Print>(
+    layout: &Layout,
+    page: &Page<'_>,
+    sidebar: S,
+    t: T,
+    themes: &[PathBuf],
+) -> String {
+    let static_root_path = page.static_root_path.unwrap_or(page.root_path);
+    let css_extension = if layout.css_file_extension.is_some() {
         format!("<link rel=\"stylesheet\" \
                        type=\"text/css\" \
                        href=\"{static_root_path}theme{suffix}.css\">",
@@ -173,12 +246,8 @@ pub fn render<T: Print, S: Print>(
                 suffix=page.resource_suffix)
     } else {
         String::new()
-    },
-    content   = Buffer::html().to_display(t),
-    static_root_path = static_root_path,
-    root_path = page.root_path,
-    css_class = page.css_class,
-    logo      = {
+    };
+    let logo = {
         let p = format!("{}{}", page.root_path, layout.krate);
         let p = ensure_trailing_slash(&p);
         if layout.logo.is_empty() {
@@ -194,49 +263,179 @@ pub fn render<T: Print, S: Print>(
                     p,
                     layout.logo)
         }
-    },
-    title     = page.title,
-    description = page.description,
-    keywords = page.keywords,
-    favicon   = if layout.favicon.is_empty() {
+    };
+    let favicon = if layout.favicon.is_empty() {
         format!(r#"<link rel="shortcut icon" href="{static_root_path}favicon{suffix}.ico">"#,
                 static_root_path=static_root_path,
                 suffix=page.resource_suffix)
     } else {
         format!(r#"<link rel="shortcut icon" href="{}">"#, layout.favicon)
-    },
-    in_header = layout.external_html.in_header,
-    before_content = layout.external_html.before_content,
-    after_content = layout.external_html.after_content,
-    sidebar   = Buffer::html().to_display(sidebar),
-    krate     = layout.krate,
-    themes = themes.iter()
-                   .filter_map(|t| t.file_stem())
-                   .filter_map(|t| t.to_str())
-                   .map(|t| format!(r#"<link rel="stylesheet" type="text/css" href="{}{}{}.css">"#,
-                                    static_root_path,
-                                    t,
-                                    page.resource_suffix))
-                   .collect::<String>(),
-    suffix=page.resource_suffix,
-    static_extra_scripts=page.static_extra_scripts.iter().map(|e| {
+    };
+    let themes = themes.iter()
+        .filter_map(|t| t.file_stem())
+        .filter_map(|t| t.to_str())
+        .map(|t| format!(r#"<link rel="stylesheet" type="text/css" href="{}{}{}.css">"#,
+                         static_root_path,
+                         t,
+                         page.resource_suffix))
+        .collect::<String>();
+    let static_extra_scripts = page.static_extra_scripts.iter().map(|e| {
         format!("<script src=\"{static_root_path}{extra_script}.js\"></script>",
                 static_root_path=static_root_path,
                 extra_script=e)
-    }).collect::<String>(),
-    extra_scripts=page.extra_scripts.iter().map(|e| {
+    }).collect::<String>();
+    let extra_scripts = page.extra_scripts.iter().map(|e| {
         format!("<script src=\"{root_path}{extra_script}.js\"></script>",
                 root_path=page.root_path,
                 extra_script=e)
-    }).collect::<String>(),
-    filter_crates=if layout.generate_search_filter {
+    }).collect::<String>();
+    let filter_crates = if layout.generate_search_filter {
         "<select id=\"crate-search\">\
             <option value=\"All crates\">All crates</option>\
         </select>"
     } else {
         ""
-    },
-    )
+    };
+    let content = Buffer::html().to_display(t);
+    let sidebar_items = Buffer::html().to_display(sidebar);
+
+    let mut variables = vec![
+        ("title", page.title),
+        ("description", page.description),
+        ("keywords", page.keywords),
+        ("static_root_path", static_root_path),
+        ("root_path", page.root_path),
+        ("suffix", page.resource_suffix),
+        ("themes", &themes[..]),
+        ("css_extension", &css_extension[..]),
+        ("favicon", &favicon[..]),
+        ("in_header", &layout.external_html.in_header[..]),
+        ("css_class", page.css_class),
+        ("before_content", &layout.external_html.before_content[..]),
+        ("logo", &logo[..]),
+        ("filter_crates", filter_crates),
+        ("content", &content[..]),
+        ("after_content", &layout.external_html.after_content[..]),
+        ("krate", &layout.krate[..]),
+        ("static_extra_scripts", &static_extra_scripts[..]),
+        ("extra_scripts", &extra_scripts[..]),
+        ("sidebar", &sidebar_items[..]),
+    ];
+    let sidebar = fill_template(&layout.templates.sidebar, &variables);
+    // In the page shell, `{sidebar}` is the whole sidebar rather than its items.
+    variables.pop();
+    variables.push(("sidebar", &sidebar[..]));
+    fill_template(&layout.templates.page, &variables)
+}
+
+/// Replaces the variables of a template with their values. Checking the template beforehand
+/// ensures that it only uses known variables and that its braces are matched.
+fn fill_template(template: &str, variables: &[(&str, &str)]) -> String {
+    let mut out = String::with_capacity(template.len());
+    for part in parse_template(template) {
+        match part {
+            TemplatePart::Text(text) => out.push_str(text),
+            TemplatePart::Variable(name) => {
+                if let Some(&(_, value)) = variables.iter().find(|&&(n, _)| n == name) {
+                    out.push_str(value);
+                }
+            }
+            TemplatePart::UnmatchedBrace(_) => {}
+        }
+    }
+    out
+}
+
+enum TemplatePart<'a> {
+    Text(&'a str),
+    Variable(&'a str),
+    /// A `{` or `}` that isn't part of a variable or doubled, with its position in the template.
+    UnmatchedBrace(usize),
+}
+
+/// Splits a template into text and variables. As in `format!`, a variable is written `{name}`,
+/// and `{{` and `}}` stand for a single brace.
+fn parse_template(template: &str) -> Vec<TemplatePart<'_>> {
+    let mut parts = Vec::new();
+    let mut text_start = 0;
+    let mut pos = 0;
+    let bytes = template.as_bytes();
+    while pos < bytes.len() {
+        match bytes[pos] {
+            c @ b'{' | c @ b'}' if bytes.get(pos + 1) == Some(&c) => {
+                // Keep the first brace of the pair.
+                parts.push(TemplatePart::Text(&template[text_start..pos + 1]));
+                pos += 2;
+                text_start = pos;
+            }
+            b'{' => {
+                let name_len = template[pos + 1..].find('}').filter(|&len| {
+                    len > 0 && template[pos + 1..pos + 1 + len]
+                        .bytes()
+                        .all(|b| b == b'_' || b.is_ascii_alphanumeric())
+                });
+                parts.push(TemplatePart::Text(&template[text_start..pos]));
+                match name_len {
+                    Some(len) => {
+                        parts.push(TemplatePart::Variable(&template[pos + 1..pos + 1 + len]));
+                        pos += len + 2;
+                    }
+                    None => {
+                        parts.push(TemplatePart::UnmatchedBrace(pos));
+                        pos += 1;
+                    }
+                }
+                text_start = pos;
+            }
+            b'}' => {
+                parts.push(TemplatePart::Text(&template[text_start..pos]));
+                parts.push(TemplatePart::UnmatchedBrace(pos));
+                pos += 1;
+                text_start = pos;
+            }
+            _ => pos += 1,
+        }
+    }
+    parts.push(TemplatePart::Text(&template[text_start..]));
+    parts
+}
+
+/// Returns the names of the variables that a template uses.
+fn template_variables(template: &str) -> FxHashSet<&str> {
+    parse_template(template).into_iter().filter_map(|part| match part {
+        TemplatePart::Variable(name) => Some(name),
+        _ => None,
+    }).collect()
+}
+
+/// Checks a template against the default one: it can use all the variables that `render` fills
+/// in, and must use the ones that the default template uses. Returns the problems found.
+fn check_template(template: &str, default: &str) -> Vec<String> {
+    let mut problems = Vec::new();
+    for part in parse_template(template) {
+        if let TemplatePart::UnmatchedBrace(pos) = part {
+            let line = template[..pos].matches('\n').count() + 1;
+            problems.push(format!("unmatched `{}` on line {} (use `{{{{` or `}}}}` for a brace)",
+                                  &template[pos..pos + 1], line));
+        }
+    }
+    let used = template_variables(template);
+    let mut unknown = used.iter()
+                          .filter(|&&name| !VARIABLES.contains(&name))
+                          .cloned()
+                          .collect::<Vec<_>>();
+    unknown.sort();
+    for name in unknown {
+        problems.push(format!("unknown variable `{{{}}}`", name));
+    }
+    let mut missing = template_variables(default).difference(&used)
+                                                 .cloned()
+                                                 .collect::<Vec<_>>();
+    missing.sort();
+    for name in missing {
+        problems.push(format!("missing variable `{{{}}}`", name));
+    }
+    problems
 }
 
 pub fn redirect(url: &str) -> String {
@@ -0,0 +1,34 @@
+use super::*;
+
+#[test]
+fn test_fill_template() {
+    let variables = [("title", "Foo"), ("content", "<p>bar</p>")];
+    assert_eq!(fill_template("<title>{title}</title>{content}", &variables),
+               "<title>Foo</title><p>bar</p>");
+    assert_eq!(fill_template("a{{b}}c {{title}}", &variables), "a{b}c {title}");
+    assert_eq!(fill_template("#x{{color:red;}}", &variables), "#x{color:red;}");
+}
+
+#[test]
+fn test_check_default_templates() {
+    assert!(Templates::default().check().is_empty());
+}
+
+#[test]
+fn test_check_template() {
+    let sidebar = "<nav>{logo}<a href=\"/\">Home</a>{sidebar}</nav>";
+    assert!(check_template(sidebar, SIDEBAR_TEMPLATE).is_empty());
+    // All the variables of the page shell can be used in the sidebar.
+    let sidebar = "<nav>{logo}{krate}{sidebar}</nav>";
+    assert!(check_template(sidebar, SIDEBAR_TEMPLATE).is_empty());
+
+    assert_eq!(check_template("<nav>{logo}{sidebar}{foo}</nav>", SIDEBAR_TEMPLATE),
+               vec!["unknown variable `{foo}`".to_owned()]);
+    assert_eq!(check_template("<nav>{sidebar}</nav>", SIDEBAR_TEMPLATE),
+               vec!["missing variable `{logo}`".to_owned()]);
+    assert_eq!(check_template("<nav>{logo}\n{sidebar}\n}</nav>", SIDEBAR_TEMPLATE),
+               vec!["unmatched `}` on line 3 (use `{{` or `}}` for a brace)".to_owned()]);
+    assert_eq!(check_template("<nav>{logo}{sidebar}{ }</nav>", SIDEBAR_TEMPLATE),
+               vec!["unmatched `{` on line 1 (use `{{` or `}}` for a brace)".to_owned(),
+                    "unmatched `}` on line 1 (use `{{` or `}}` for a brace)".to_owned()]);
+}
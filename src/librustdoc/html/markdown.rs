@@ -625,7 +625,7 @@ pub enum Ignore {
 }
 
 impl LangString {
-    fn all_false() -> LangString {
+    crate fn all_false() -> LangString {
         LangString {
             original: String::new(),
             should_panic: false,
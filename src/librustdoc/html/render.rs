@@ -57,7 +57,7 @@ use rustc::util::nodemap::{FxHashMap, FxHashSet};
 use rustc_data_structures::flock;
 
 use crate::clean::{self, AttributesExt, Deprecation, GetDefId, SelfTy, Mutability};
-use crate::config::RenderOptions;
+use crate::config::{OutputFormat, RenderOptions};
 use crate::docfs::{DocFS, ErrorStorage, PathError};
 use crate::doctree;
 use crate::html::escape::Escape;
@@ -68,7 +68,8 @@ use crate::html::format::fmt_impl_for_trait_page;
 use crate::html::item_type::ItemType;
 use crate::html::markdown::{self, Markdown, MarkdownHtml, MarkdownSummaryLine, ErrorCodes, IdMap};
 use crate::html::{highlight, layout, static_files};
-use crate::html::sources;
+use crate::html::sources::{self, SourceContext};
+use crate::scrape_examples::FnCallLocations;
 
 use minifier;
 
@@ -284,6 +285,8 @@ pub struct RenderInfo {
     pub deref_trait_did: Option<DefId>,
     pub deref_mut_trait_did: Option<DefId>,
     pub owned_box_did: Option<DefId>,
+    pub output_format: Option<OutputFormat>,
+    pub call_locations: FxHashMap<DefId, FnCallLocations>,
 }
 
 // Helper structs for rendering items/sidebars and carrying along contextual
@@ -412,6 +415,7 @@ pub fn run(mut krate: clean::Crate,
         sort_modules_alphabetically,
         themes,
         extension_css,
+        templates,
         extern_html_root_urls,
         resource_suffix,
         static_root_path,
@@ -443,6 +447,7 @@ pub fn run(mut krate: clean::Crate,
         krate: krate.name.clone(),
         css_file_extension: extension_css,
         generate_search_filter,
+        templates,
     };
     let mut issue_tracker_base_url = None;
     let mut include_sources = true;
@@ -2276,10 +2281,68 @@ fn item_function(w: &mut Buffer, cx: &Context, it: &clean::Item, f: &clean::Func
               indent: 0,
               asyncness: f.header.asyncness,
            }.print());
-    document(w, cx, it)
+    document(w, cx, it);
+    render_call_locations(w, cx, it);
+}
+
+/// Renders the calls to the function `it` that were scraped from the crate's examples and tests.
+fn render_call_locations(w: &mut Buffer, cx: &Context, it: &clean::Item) {
+    let c = cache();
+    let call_locations = match c.call_locations.get(&it.def_id) {
+        Some(call_locations) if !call_locations.is_empty() => call_locations,
+        _ => return,
+    };
+
+    let id = cx.derive_id("scraped-examples".to_string());
+    write!(w, "<div class='scraped-example-list'>\
+               <h2 id='{id}' class='small-section-header'>\
+               Examples found in repository<a href='#{id}' class='anchor'></a></h2>",
+           id = id);
+    for (path, call_data) in call_locations {
+        let contents = match fs::read_to_string(path) {
+            Ok(contents) => contents,
+            Err(e) => {
+                warn!("failed to read scraped example `{}`: {}", path, e);
+                continue;
+            }
+        };
+        // The compiler removes the byte order mark before computing the spans.
+        let contents = contents.trim_start_matches('\u{feff}');
+
+        // Show each item that contains calls once, with all of its calls highlighted.
+        let mut items: BTreeMap<(u32, u32), (usize, Vec<usize>)> = BTreeMap::new();
+        for location in &call_data.locations {
+            let item = &location.enclosing_item;
+            let (_, highlighted) = items.entry(item.byte_span)
+                .or_insert_with(|| (item.line_span.0, Vec::new()));
+            highlighted.extend(location.call_expr.line_span.0..=location.call_expr.line_span.1);
+        }
+
+        for ((lo, hi), (first_line, highlighted)) in items {
+            // Start at the beginning of the first line, so that the indentation is kept.
+            let lo = match contents.get(..lo as usize) {
+                Some(before) => before.rfind('\n').map_or(0, |i| i + 1),
+                None => continue,
+            };
+            let snippet = match contents.get(lo..hi as usize) {
+                Some(snippet) => snippet,
+                None => continue,
+            };
+            write!(w, "<div class='scraped-example'>\
+                       <div class='scraped-example-title'>{}</div>\
+                       <div class='example-wrap'>",
+                   Escape(&call_data.display_name));
+            sources::print_src(w, snippet, SourceContext::Embedded {
+                offset: first_line - 1,
+                highlighted: &highlighted,
+            });
+            write!(w, "</div></div>");
+        }
+    }
+    write!(w, "</div>");
 }
 
-fn render_implementor(cx: &Context, implementor: &Impl, w: &mut Buffer,
+fn render_implementor(cx: &Context, implementor: &Impl, trait_: &clean::Item, w: &mut Buffer,
                       implementor_dups: &FxHashMap<&str, (DefId, bool)>) {
     // If there's already another implementor that has the same abbridged name, use the
     // full path, for example in `std::iter::ExactSizeIterator`
@@ -2292,7 +2355,8 @@ fn render_implementor(cx: &Context, implementor: &Impl, w: &mut Buffer,
         _ => false,
     };
     render_impl(w, cx, implementor, AssocItemLink::Anchor(None), RenderMode::Normal,
-                implementor.impl_item.stable_since(), false, Some(use_absolute), false, false);
+                implementor.impl_item.stable_since(), trait_.attrs.cfg.as_deref(), false,
+                Some(use_absolute), false, false);
 }
 
 fn render_impls(cx: &Context, w: &mut Buffer,
@@ -2302,7 +2366,8 @@ fn render_impls(cx: &Context, w: &mut Buffer,
         let did = i.trait_did().unwrap();
         let assoc_link = AssocItemLink::GotoSource(did, &i.inner_impl().provided_trait_methods);
         render_impl(w, cx, i, assoc_link,
-                    RenderMode::Normal, containing_item.stable_since(), true, None, false, true);
+                    RenderMode::Normal, containing_item.stable_since(),
+                    containing_item.attrs.cfg.as_deref(), true, None, false, true);
     }
 }
 
@@ -2442,6 +2507,9 @@ fn item_trait(
         render_stability_since(w, m, t);
         write!(w, "</h3>");
         document(w, cx, m);
+        if m.is_ty_method() || m.is_method() {
+            render_call_locations(w, cx, m);
+        }
     }
 
     if !types.is_empty() {
@@ -2526,8 +2594,8 @@ fn item_trait(
                     &implementor.inner_impl().provided_trait_methods
                 );
                 render_impl(w, cx, &implementor, assoc_link,
-                            RenderMode::Normal, implementor.impl_item.stable_since(), false,
-                            None, true, false);
+                            RenderMode::Normal, implementor.impl_item.stable_since(),
+                            it.attrs.cfg.as_deref(), false, None, true, false);
             }
             write_loading_content(w, "");
         }
@@ -2535,7 +2603,7 @@ fn item_trait(
         write_small_section_header(w, "implementors", "Implementors",
                                    "<div class='item-list' id='implementors-list'>");
         for implementor in concrete {
-            render_implementor(cx, implementor, w, &implementor_dups);
+            render_implementor(cx, implementor, it, w, &implementor_dups);
         }
         write_loading_content(w, "</div>");
 
@@ -2546,7 +2614,7 @@ fn item_trait(
                 synthetic_types.extend(
                     collect_paths_for_type(implementor.inner_impl().for_.clone())
                 );
-                render_implementor(cx, implementor, w, &implementor_dups);
+                render_implementor(cx, implementor, it, w, &implementor_dups);
             }
             write_loading_content(w, "</div>");
         }
@@ -3180,7 +3248,8 @@ fn render_assoc_items(w: &mut Buffer,
         };
         for i in &non_trait {
             render_impl(w, cx, i, AssocItemLink::Anchor(None), render_mode,
-                        containing_item.stable_since(), true, None, false, true);
+                        containing_item.stable_since(), containing_item.attrs.cfg.as_deref(),
+                        true, None, false, true);
         }
     }
     if let AssocItemRender::DerefFor { .. } = what {
@@ -3350,7 +3419,8 @@ fn spotlight_decl(decl: &clean::FnDecl) -> String {
 }
 
 fn render_impl(w: &mut Buffer, cx: &Context, i: &Impl, link: AssocItemLink<'_>,
-               render_mode: RenderMode, outer_version: Option<&str>, show_def_docs: bool,
+               render_mode: RenderMode, outer_version: Option<&str>,
+               outer_cfg: Option<&clean::cfg::Cfg>, show_def_docs: bool,
                use_absolute: Option<bool>, is_on_foreign_type: bool,
                show_default_items: bool) {
     if render_mode == RenderMode::Normal {
@@ -3390,6 +3460,15 @@ fn render_impl(w: &mut Buffer, cx: &Context, i: &Impl, link: AssocItemLink<'_>,
                    l, "goto source code");
         }
         write!(w, "</h3>");
+        // The requirements of the page's item hold for its impls, so only the others are shown.
+        let cfg = i.impl_item.attrs.cfg.as_ref().and_then(|cfg| match outer_cfg {
+            Some(outer_cfg) => cfg.simplify_with(outer_cfg),
+            None => Some((**cfg).clone()),
+        });
+        if let Some(cfg) = cfg {
+            write!(w, "<div class='stability'><div class='stab portability'>{}</div></div>",
+                   cfg.render_long_html());
+        }
         if let Some(ref dox) = cx.shared.maybe_collapsed_doc_value(&i.impl_item) {
             let mut ids = cx.id_map.borrow_mut();
             write!(w, "<div class='docblock'>{}</div>",
@@ -3503,6 +3582,9 @@ fn render_impl(w: &mut Buffer, cx: &Context, i: &Impl, link: AssocItemLink<'_>,
                     document_short(w, cx, item, link, "", is_hidden);
                 }
             }
+            if show_def_docs && !is_hidden && item.is_method() {
+                render_call_locations(w, cx, item);
+            }
         }
     }
 
@@ -12,6 +12,7 @@ use serialize::json::{ToJson, Json, as_json};
 
 use super::{ItemType, IndexItem, IndexItemFunctionType, Impl, shorten, plain_summary_line};
 use super::{Type, RenderInfo};
+use crate::scrape_examples::FnCallLocations;
 
 /// Indicates where an external crate can be found.
 pub enum ExternalLocation {
@@ -121,6 +122,9 @@ crate struct Cache {
     /// Aliases added through `#[doc(alias = "...")]`. Since a few items can have the same alias,
     /// we need the alias element to have an array of items.
     pub(super) aliases: FxHashMap<String, Vec<IndexItem>>,
+
+    /// The calls to the crate's functions found in its examples and tests.
+    pub call_locations: FxHashMap<DefId, FnCallLocations>,
 }
 
 impl Cache {
@@ -139,6 +143,8 @@ impl Cache {
             deref_trait_did,
             deref_mut_trait_did,
             owned_box_did,
+            output_format: _,
+            call_locations,
         } = renderinfo;
 
         let external_paths = external_paths.into_iter()
@@ -168,6 +174,7 @@ impl Cache {
             owned_box_did,
             masked_crates: mem::take(&mut krate.masked_crates),
             aliases: Default::default(),
+            call_locations,
         };
 
         // Cache where all our extern crates are located
@@ -618,12 +625,15 @@ fn get_index_search_type(item: &clean::Item) -> Option<IndexItemFunctionType> {
         _ => return None,
     };
 
-    let inputs = all_types.iter().map(|arg| {
+    // The types are collected in sets, so sort them to keep the index stable.
+    let mut inputs = all_types.iter().map(|arg| {
         get_index_type(&arg)
-    }).filter(|a| a.name.is_some()).collect();
-    let output = ret_types.iter().map(|arg| {
+    }).filter(|a| a.name.is_some()).collect::<Vec<_>>();
+    inputs.sort_by(|a, b| (&a.name, &a.generics).cmp(&(&b.name, &b.generics)));
+    let mut output = ret_types.iter().map(|arg| {
         get_index_type(&arg)
     }).filter(|a| a.name.is_some()).collect::<Vec<_>>();
+    output.sort_by(|a, b| (&a.name, &a.generics).cmp(&(&b.name, &b.generics)));
     let output = if output.is_empty() {
         None
     } else {
@@ -652,24 +662,34 @@ fn get_index_type_name(clean_type: &clean::Type, accept_generic: bool) -> Option
             Some(path_segment.name.clone())
         }
         clean::Generic(ref s) if accept_generic => Some(s.clone()),
-        clean::Primitive(ref p) => Some(format!("{:?}", p)),
-        clean::BorrowedRef { ref type_, .. } => get_index_type_name(type_, accept_generic),
+        clean::Primitive(ref p) => Some(p.as_str().to_owned()),
+        // References and pointers are searched as the type they point to, and slices, arrays
+        // and tuples as the primitive type, with the types they contain as generics.
+        clean::BorrowedRef { ref type_, .. } |
+        clean::RawPointer(_, ref type_) => get_index_type_name(type_, accept_generic),
+        clean::Slice(_) => Some("slice".to_owned()),
+        clean::Array(..) => Some("array".to_owned()),
+        clean::Tuple(ref types) if !types.is_empty() => Some("tuple".to_owned()),
         // FIXME: add all from clean::Type.
         _ => None
     }
 }
 
 fn get_generics(clean_type: &clean::Type) -> Option<Vec<String>> {
-    clean_type.generics()
-              .and_then(|types| {
-                  let r = types.iter()
-                               .filter_map(|t| get_index_type_name(t, false))
-                               .map(|s| s.to_ascii_lowercase())
-                               .collect::<Vec<_>>();
-                  if r.is_empty() {
-                      None
-                  } else {
-                      Some(r)
-                  }
-              })
+    let types = match *clean_type {
+        clean::BorrowedRef { ref type_, .. } |
+        clean::RawPointer(_, ref type_) => return get_generics(type_),
+        clean::Slice(ref type_) | clean::Array(ref type_, _) => vec![(**type_).clone()],
+        clean::Tuple(ref types) => types.clone(),
+        _ => clean_type.generics()?,
+    };
+    let r = types.iter()
+                 .filter_map(|t| get_index_type_name(t, false))
+                 .map(|s| s.to_ascii_lowercase())
+                 .collect::<Vec<_>>();
+    if r.is_empty() {
+        None
+    } else {
+        Some(r)
+    }
 }
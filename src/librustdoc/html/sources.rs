@@ -120,7 +120,8 @@ impl<'a> SourceCollector<'a> {
             static_extra_scripts: &[&format!("source-script{}", self.scx.resource_suffix)],
         };
         let v = layout::render(&self.scx.layout,
-                       &page, "", |buf: &mut _| print_src(buf, &contents),
+                       &page, "",
+                       |buf: &mut _| print_src(buf, &contents, SourceContext::Standalone),
                        &self.scx.themes);
         self.scx.fs.write(&cur, v.as_bytes())?;
         self.scx.local_sources.insert(p.clone(), href);
@@ -155,19 +156,43 @@ where
     }
 }
 
+/// Where the source code rendered by `print_src` is shown.
+crate enum SourceContext<'a> {
+    /// The source page of a file. Each line number is an anchor.
+    Standalone,
+    /// A snippet of a file embedded in another page, such as a scraped example. The snippet
+    /// starts at line `offset + 1` of its file, and the lines in `highlighted` are marked.
+    Embedded { offset: usize, highlighted: &'a [usize] },
+}
+
 /// Wrapper struct to render the source code of a file. This will do things like
 /// adding line numbers to the left-hand side.
-fn print_src(buf: &mut Buffer, s: &str) {
+crate fn print_src(buf: &mut Buffer, s: &str, source_context: SourceContext<'_>) {
+    let offset = match source_context {
+        SourceContext::Standalone => 0,
+        SourceContext::Embedded { offset, .. } => offset,
+    };
     let lines = s.lines().count();
     let mut cols = 0;
-    let mut tmp = lines;
+    let mut tmp = lines + offset;
     while tmp > 0 {
         cols += 1;
         tmp /= 10;
     }
     write!(buf, "<pre class=\"line-numbers\">");
-    for i in 1..=lines {
-        write!(buf, "<span id=\"{0}\">{0:1$}</span>\n", i, cols);
+    for i in (1 + offset)..=(lines + offset) {
+        match source_context {
+            SourceContext::Standalone => {
+                write!(buf, "<span id=\"{0}\">{0:1$}</span>\n", i, cols);
+            }
+            SourceContext::Embedded { highlighted, .. } => {
+                if highlighted.contains(&i) {
+                    write!(buf, "<span class=\"line-highlighted\">{0:1$}</span>\n", i, cols);
+                } else {
+                    write!(buf, "<span>{0:1$}</span>\n", i, cols);
+                }
+            }
+        }
     }
     write!(buf, "</pre>");
     write!(buf, "{}",
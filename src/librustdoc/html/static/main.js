@@ -645,18 +645,66 @@ function getSearchElement() {
                 return transformResults(results);
             }
 
+            // Splits a list of types on the commas that aren't inside of generics, slices or
+            // tuples.
+            function splitTypes(val) {
+                var types = [];
+                var depth = 0;
+                var start = 0;
+                for (var pos = 0; pos < val.length; ++pos) {
+                    var c = val.charAt(pos);
+                    if (c === "<" || c === "[" || c === "(") {
+                        depth += 1;
+                    } else if (c === ">" || c === "]" || c === ")") {
+                        depth -= 1;
+                    } else if (c === "," && depth === 0) {
+                        types.push(val.substring(start, pos).trim());
+                        start = pos + 1;
+                    }
+                }
+                types.push(val.substring(start).trim());
+                return types.filter(function(type) {
+                    return type.length > 0;
+                });
+            }
+
+            // Turns a type written in a query into the form of the types of the search index.
+            // References and pointers are searched as the type they point to, and slices, arrays
+            // and tuples as the primitive type, with the types they contain as generics.
             function extractGenerics(val) {
-                val = val.toLowerCase();
-                if (val.indexOf("<") !== -1) {
-                    var values = val.substring(val.indexOf("<") + 1, val.lastIndexOf(">"));
-                    return {
-                        name: val.substring(0, val.indexOf("<")),
-                        generics: values.split(/\s*,\s*/),
-                    };
+                val = val.toLowerCase().trim();
+                while (true) {
+                    if (val.charAt(0) === "'") {
+                        // lifetime of a reference
+                        val = val.replace(/^\S+\s*/, "");
+                    } else if (/^(&|\*const\s|\*mut\s|mut\s|dyn\s|impl\s)/.test(val)) {
+                        val = val.replace(/^(&|\*const|\*mut|mut|dyn|impl)\s*/, "");
+                    } else {
+                        break;
+                    }
+                }
+                var name = val;
+                var generics = [];
+                var first = val.charAt(0);
+                var last = val.charAt(val.length - 1);
+                if (val.length > 1 && first === "[" && last === "]") {
+                    var elems = val.substring(1, val.length - 1).split(";");
+                    name = elems.length > 1 ? "array" : "slice";
+                    generics = [elems[0]];
+                } else if (val.length > 2 && first === "(" && last === ")") {
+                    name = "tuple";
+                    generics = splitTypes(val.substring(1, val.length - 1));
+                } else if (val.indexOf("<") !== -1) {
+                    var start = val.indexOf("<");
+                    name = val.substring(0, start).trim();
+                    generics = splitTypes(val.substring(start + 1, val.lastIndexOf(">")));
                 }
                 return {
-                    name: val,
-                    generics: [],
+                    name: name,
+                    // The search index only has the names of the generics.
+                    generics: generics.map(function(generic) {
+                        return extractGenerics(generic).name;
+                    }),
                 };
             }
 
@@ -819,6 +867,59 @@ function getSearchElement() {
                 return literalSearch === true ? false : lev_distance;
             }
 
+            // Returns how far a type of the search index is from a type of a signature query. A
+            // type written without generics in the query matches the type with any generics.
+            function checkSignatureType(obj, val) {
+                if (obj[NAME] === val.name && val.generics.length === 0) {
+                    return 0;
+                }
+                return checkType(obj, val);
+            }
+
+            // Returns how far the inputs of a function are from the inputs of a signature query,
+            // or `MAX_LEV_DISTANCE + 1` if one of them has no match. Each input of the query has
+            // to match a different input of the function, and the functions that take more inputs
+            // than the query are ranked a little lower.
+            function checkInputs(type, inputs) {
+                var fnInputs = type[INPUTS_DATA].slice(0);
+                var total = 0;
+                for (var x = 0; x < inputs.length; ++x) {
+                    var best = MAX_LEV_DISTANCE + 1;
+                    var pos = -1;
+                    for (var y = 0; y < fnInputs.length; ++y) {
+                        var lev = checkSignatureType(fnInputs[y], inputs[x]);
+                        if (lev < best) {
+                            best = lev;
+                            pos = y;
+                        }
+                    }
+                    if (pos === -1) {
+                        return MAX_LEV_DISTANCE + 1;
+                    }
+                    fnInputs.splice(pos, 1);
+                    total += best;
+                }
+                var lev_distance = inputs.length > 0 ? total / inputs.length : 0;
+                return lev_distance + fnInputs.length / 10;
+            }
+
+            // Returns how far the output of a function is from the output of a signature query.
+            function checkOutput(type, val) {
+                if (type.length <= OUTPUT_DATA) {
+                    return MAX_LEV_DISTANCE + 1;
+                }
+                // A single type is stored as is, several types as an array of types.
+                var outputs = type[OUTPUT_DATA];
+                if (typeof outputs[NAME] === "string") {
+                    outputs = [outputs];
+                }
+                var lev_distance = MAX_LEV_DISTANCE + 1;
+                for (var x = 0; x < outputs.length; ++x) {
+                    lev_distance = Math.min(checkSignatureType(outputs[x], val), lev_distance);
+                }
+                return lev_distance;
+            }
+
             function checkPath(contains, lastElem, ty) {
                 if (contains.length === 0) {
                     return 0;
@@ -941,72 +1042,69 @@ function getSearchElement() {
                 query.inputs = [val];
                 query.output = val;
                 query.search = val;
-            // searching by type
+            // searching by type signature
             } else if (val.search("->") > -1) {
-                var trimmer = function(s) { return s.trim(); };
-                var parts = val.split("->").map(trimmer);
-                var input = parts[0];
-                // sort inputs so that order does not matter
-                var inputs = input.split(",").map(trimmer).sort();
-                for (i = 0; i < inputs.length; ++i) {
-                    inputs[i] = extractGenerics(inputs[i]);
-                }
-                var output = extractGenerics(parts[1]);
+                var parts = val.split("->");
+                var input = parts[0].trim();
+                var output = parts.slice(1).join("->").trim();
+                // The search index only has the last segment of the paths.
+                var inputs = input === "" || input === "*" ? [] :
+                    splitTypes(input.replace(/\w+::/g, "")).map(extractGenerics);
+                var anyOutput = output === "" || output === "*";
+                var noOutput = output === "()";
+                var outputType = anyOutput || noOutput ? null :
+                    extractGenerics(output.replace(/\w+::/g, ""));
 
                 for (i = 0; i < nSearchWords; ++i) {
                     if (filterCrates !== undefined && searchIndex[i].crate !== filterCrates) {
                         continue;
                     }
-                    var type = searchIndex[i].type;
                     ty = searchIndex[i];
-                    if (!type) {
+                    if (!ty.type || !typePassesFilter(typeFilter, ty.ty)) {
                         continue;
                     }
                     fullId = generateId(ty);
 
-                    // allow searching for void (no output) functions as well
-                    var typeOutput = type.length > OUTPUT_DATA ? type[OUTPUT_DATA].name : "";
-                    returned = checkReturned(ty, output, true);
-                    if (output.name === "*" || returned === true) {
-                        in_args = false;
-                        var is_module = false;
+                    in_args = checkInputs(ty.type, inputs);
+                    if (anyOutput) {
+                        returned = 0;
+                    } else if (noOutput) {
+                        returned = ty.type.length > OUTPUT_DATA ? MAX_LEV_DISTANCE + 1 : 0;
+                    } else {
+                        returned = checkOutput(ty.type, outputType);
+                    }
 
-                        if (input === "*") {
-                            is_module = true;
-                        } else {
-                            var allFound = true;
-                            for (var it = 0; allFound === true && it < inputs.length; it++) {
-                                allFound = checkType(type, inputs[it], true);
-                            }
-                            in_args = allFound;
-                        }
-                        if (in_args === true) {
-                            results_in_args[fullId] = {
-                                id: i,
-                                index: -1,
-                                dontValidate: true,
-                            };
-                        }
-                        if (returned === true) {
-                            results_returned[fullId] = {
-                                id: i,
-                                index: -1,
-                                dontValidate: true,
-                            };
-                        }
-                        if (is_module === true) {
-                            results[fullId] = {
-                                id: i,
-                                index: -1,
-                                dontValidate: true,
-                            };
-                        }
+                    if (inputs.length > 0 && in_args <= MAX_LEV_DISTANCE) {
+                        results_in_args[fullId] = {
+                            id: i,
+                            index: -1,
+                            lev: in_args,
+                            dontValidate: true,
+                        };
+                    }
+                    if (outputType !== null && returned <= MAX_LEV_DISTANCE) {
+                        results_returned[fullId] = {
+                            id: i,
+                            index: -1,
+                            lev: returned,
+                            dontValidate: true,
+                        };
+                    }
+                    // The functions that match the whole signature are ranked by how far both
+                    // their inputs and their output are from the query.
+                    if (in_args <= MAX_LEV_DISTANCE && returned <= MAX_LEV_DISTANCE) {
+                        results[fullId] = {
+                            id: i,
+                            index: -1,
+                            lev: in_args + returned,
+                            dontValidate: true,
+                        };
                     }
                 }
                 query.inputs = inputs.map(function(input) {
                     return input.name;
                 });
-                query.output = output.name;
+                query.output = outputType !== null ? outputType.name : output;
             } else {
                 query.inputs = [val];
                 query.output = val;
@@ -1506,7 +1604,8 @@ function getSearchElement() {
                 return ret;
             }
 
-            var queries = query.raw.split(",");
+            // The commas of a signature search separate its inputs, not several queries.
+            var queries = query.raw.search("->") > -1 ? [query.raw] : query.raw.split(",");
             var results = {
                 "in_args": [],
                 "returned": [],
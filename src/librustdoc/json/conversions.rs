@@ -0,0 +1,296 @@
+//! Conversions from the cleaned types of `clean` to their JSON representation.
+//!
+//! Every value is an object whose keys are sorted, so the output is stable across runs. Values
+//! that can take several shapes, such as types and generic bounds, carry a `kind` key that says
+//! which one it is.
+
+use std::collections::BTreeMap;
+
+use rustc::hir;
+use rustc::hir::def_id::DefId;
+use serialize::json::{Json, ToJson};
+use syntax::source_map::FileName;
+
+use crate::clean;
+
+/// Builds a JSON object out of key-value pairs.
+crate fn object(fields: Vec<(&str, Json)>) -> Json {
+    Json::Object(fields.into_iter().map(|(k, v)| (k.to_string(), v)).collect::<BTreeMap<_, _>>())
+}
+
+/// Returns the identifier used for `did` in the JSON output.
+crate fn id(did: DefId) -> String {
+    format!("{}:{}", did.krate.as_u32(), did.index.as_u32())
+}
+
+crate fn list<T: ToJson>(items: &[T]) -> Json {
+    Json::Array(items.iter().map(|item| item.to_json()).collect())
+}
+
+/// Renders `path` without its generic arguments, e.g. `std::vec::Vec`.
+crate fn path_to_string(path: &clean::Path) -> String {
+    path.segments.iter().map(|s| &*s.name).collect::<Vec<_>>().join("::")
+}
+
+fn kind(kind: &str, mut fields: Vec<(&str, Json)>) -> Json {
+    fields.push(("kind", kind.to_json()));
+    object(fields)
+}
+
+impl ToJson for clean::Type {
+    fn to_json(&self) -> Json {
+        use crate::clean::Type::*;
+        match *self {
+            ResolvedPath { ref path, ref param_names, did, is_generic } => {
+                kind("resolved_path", vec![
+                    ("id", id(did).to_json()),
+                    ("name", path_to_string(path).to_json()),
+                    ("args", path.segments.last().map(|s| s.args.to_json()).to_json()),
+                    ("param_names", param_names.as_ref().map(|b| list(b)).to_json()),
+                    ("is_generic", is_generic.to_json()),
+                ])
+            }
+            Generic(ref name) => kind("generic", vec![("name", name.to_json())]),
+            Primitive(prim) => kind("primitive", vec![("name", prim.as_str().to_json())]),
+            BareFunction(ref decl) => kind("function_pointer", vec![
+                ("decl", decl.decl.to_json()),
+                ("generic_params", list(&decl.generic_params)),
+                ("is_unsafe", (decl.unsafety == hir::Unsafety::Unsafe).to_json()),
+                ("abi", decl.abi.name().to_json()),
+            ]),
+            Tuple(ref types) => kind("tuple", vec![("types", list(types))]),
+            Slice(ref ty) => kind("slice", vec![("type", ty.to_json())]),
+            Array(ref ty, ref len) => kind("array", vec![
+                ("type", ty.to_json()),
+                ("len", len.to_json()),
+            ]),
+            Never => kind("never", vec![]),
+            RawPointer(mutability, ref ty) => kind("raw_pointer", vec![
+                ("mutable", (mutability == clean::Mutable).to_json()),
+                ("type", ty.to_json()),
+            ]),
+            BorrowedRef { ref lifetime, mutability, ref type_ } => kind("borrowed_ref", vec![
+                ("lifetime", lifetime.as_ref().map(|l| l.get_ref().to_string()).to_json()),
+                ("mutable", (mutability == clean::Mutable).to_json()),
+                ("type", type_.to_json()),
+            ]),
+            QPath { ref name, ref self_type, ref trait_ } => kind("qualified_path", vec![
+                ("name", name.to_json()),
+                ("self_type", self_type.to_json()),
+                ("trait", trait_.to_json()),
+            ]),
+            Infer => kind("infer", vec![]),
+            ImplTrait(ref bounds) => kind("impl_trait", vec![("bounds", list(bounds))]),
+        }
+    }
+}
+
+impl ToJson for clean::GenericArgs {
+    fn to_json(&self) -> Json {
+        match *self {
+            clean::GenericArgs::AngleBracketed { ref args, ref bindings } => {
+                kind("angle_bracketed", vec![
+                    ("args", list(args)),
+                    ("bindings", list(bindings)),
+                ])
+            }
+            clean::GenericArgs::Parenthesized { ref inputs, ref output } => {
+                kind("parenthesized", vec![
+                    ("inputs", list(inputs)),
+                    ("output", output.to_json()),
+                ])
+            }
+        }
+    }
+}
+
+impl ToJson for clean::GenericArg {
+    fn to_json(&self) -> Json {
+        match *self {
+            clean::GenericArg::Lifetime(ref l) => {
+                kind("lifetime", vec![("name", l.get_ref().to_json())])
+            }
+            clean::GenericArg::Type(ref ty) => kind("type", vec![("type", ty.to_json())]),
+            clean::GenericArg::Const(ref c) => kind("const", vec![("const", c.to_json())]),
+        }
+    }
+}
+
+impl ToJson for clean::Constant {
+    fn to_json(&self) -> Json {
+        object(vec![
+            ("type", self.type_.to_json()),
+            ("expr", self.expr.to_json()),
+        ])
+    }
+}
+
+impl ToJson for clean::TypeBinding {
+    fn to_json(&self) -> Json {
+        let binding = match self.kind {
+            clean::TypeBindingKind::Equality { ref ty } => {
+                kind("equality", vec![("type", ty.to_json())])
+            }
+            clean::TypeBindingKind::Constraint { ref bounds } => {
+                kind("constraint", vec![("bounds", list(bounds))])
+            }
+        };
+        object(vec![
+            ("name", self.name.to_json()),
+            ("binding", binding),
+        ])
+    }
+}
+
+impl ToJson for clean::GenericBound {
+    fn to_json(&self) -> Json {
+        match *self {
+            clean::GenericBound::TraitBound(ref poly_trait, modifier) => {
+                kind("trait_bound", vec![
+                    ("trait", poly_trait.trait_.to_json()),
+                    ("generic_params", list(&poly_trait.generic_params)),
+                    ("maybe", (modifier == hir::TraitBoundModifier::Maybe).to_json()),
+                ])
+            }
+            clean::GenericBound::Outlives(ref l) => {
+                kind("outlives", vec![("lifetime", l.get_ref().to_json())])
+            }
+        }
+    }
+}
+
+impl ToJson for clean::GenericParamDef {
+    fn to_json(&self) -> Json {
+        let param = match self.kind {
+            clean::GenericParamDefKind::Lifetime => kind("lifetime", vec![]),
+            clean::GenericParamDefKind::Type { ref bounds, ref default, synthetic, .. } => {
+                kind("type", vec![
+                    ("bounds", list(bounds)),
+                    ("default", default.to_json()),
+                    ("synthetic", synthetic.is_some().to_json()),
+                ])
+            }
+            clean::GenericParamDefKind::Const { ref ty, .. } => {
+                kind("const", vec![("type", ty.to_json())])
+            }
+        };
+        object(vec![
+            ("name", self.name.to_json()),
+            ("param", param),
+        ])
+    }
+}
+
+impl ToJson for clean::WherePredicate {
+    fn to_json(&self) -> Json {
+        match *self {
+            clean::WherePredicate::BoundPredicate { ref ty, ref bounds } => {
+                kind("bound", vec![
+                    ("type", ty.to_json()),
+                    ("bounds", list(bounds)),
+                ])
+            }
+            clean::WherePredicate::RegionPredicate { ref lifetime, ref bounds } => {
+                kind("region", vec![
+                    ("lifetime", lifetime.get_ref().to_json()),
+                    ("bounds", list(bounds)),
+                ])
+            }
+            clean::WherePredicate::EqPredicate { ref lhs, ref rhs } => {
+                kind("eq", vec![
+                    ("lhs", lhs.to_json()),
+                    ("rhs", rhs.to_json()),
+                ])
+            }
+        }
+    }
+}
+
+impl ToJson for clean::Generics {
+    fn to_json(&self) -> Json {
+        object(vec![
+            ("params", list(&self.params)),
+            ("where_predicates", list(&self.where_predicates)),
+        ])
+    }
+}
+
+impl ToJson for clean::FnDecl {
+    fn to_json(&self) -> Json {
+        let inputs = self.inputs.values.iter().map(|arg| {
+            object(vec![
+                ("name", arg.name.to_json()),
+                ("type", arg.type_.to_json()),
+            ])
+        }).collect();
+        let output = match self.output {
+            clean::FunctionRetTy::Return(ref ty) => Some(ty.to_json()),
+            clean::FunctionRetTy::DefaultReturn => None,
+        };
+        object(vec![
+            ("inputs", Json::Array(inputs)),
+            ("output", output.to_json()),
+            ("c_variadic", self.c_variadic.to_json()),
+        ])
+    }
+}
+
+impl ToJson for hir::FnHeader {
+    fn to_json(&self) -> Json {
+        object(vec![
+            ("is_unsafe", (self.unsafety == hir::Unsafety::Unsafe).to_json()),
+            ("is_const", (self.constness == hir::Constness::Const).to_json()),
+            ("is_async", (self.asyncness == hir::IsAsync::Async).to_json()),
+            ("abi", self.abi.name().to_json()),
+        ])
+    }
+}
+
+impl ToJson for clean::Span {
+    fn to_json(&self) -> Json {
+        let filename = match self.filename {
+            FileName::Real(ref path) => path.display().to_string(),
+            ref other => other.to_string(),
+        };
+        object(vec![
+            ("filename", filename.to_json()),
+            ("begin", vec![self.loline, self.locol].to_json()),
+            ("end", vec![self.hiline, self.hicol].to_json()),
+        ])
+    }
+}
+
+impl ToJson for clean::Visibility {
+    fn to_json(&self) -> Json {
+        match *self {
+            clean::Public => "public".to_json(),
+            clean::Inherited => "default".to_json(),
+            clean::Visibility::Crate => "crate".to_json(),
+            clean::Visibility::Restricted(did, ref path) => kind("restricted", vec![
+                ("id", id(did).to_json()),
+                ("path", path_to_string(path).to_json()),
+            ]),
+        }
+    }
+}
+
+impl ToJson for clean::Deprecation {
+    fn to_json(&self) -> Json {
+        object(vec![
+            ("since", self.since.to_json()),
+            ("note", self.note.to_json()),
+        ])
+    }
+}
+
+impl ToJson for clean::Stability {
+    fn to_json(&self) -> Json {
+        use rustc::middle::stability::StabilityLevel;
+        object(vec![
+            ("stable", (self.level == StabilityLevel::Stable).to_json()),
+            ("feature", self.feature.to_json()),
+            ("since", self.since.to_json()),
+            ("issue", self.issue.to_json()),
+        ])
+    }
+}
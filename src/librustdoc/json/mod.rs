@@ -0,0 +1,355 @@
+//! Rustdoc's JSON backend, selected with `--output-format json`.
+//!
+//! This renders the cleaned crate into a single `{crate-name}.json` file in the output directory.
+//! Every item that survived the passes is stored in a flat `index`, keyed by its id, and items
+//! refer to each other through these ids. The `paths` map gives the full path of every item that
+//! can be named, including items from other crates that the documented crate refers to.
+//!
+//! The layout of the file is versioned by `FORMAT_VERSION`, which is written to the output so
+//! that consumers can reject files they do not understand.
+
+mod conversions;
+
+use std::collections::BTreeMap;
+use std::fs;
+
+use rustc::hir::def_id::DefId;
+use rustc_data_structures::fx::FxHashMap;
+use serialize::json::{Json, ToJson};
+use syntax::print::pprust;
+
+use crate::clean::{self, GetDefId};
+use crate::config::RenderOptions;
+use crate::doctree;
+use crate::html::item_type::ItemType;
+use crate::html::render::{Error, RenderInfo};
+
+use self::conversions::{id, list, object, path_to_string};
+
+/// The version of the JSON output. This must be bumped whenever the output changes in a way that
+/// existing consumers might not understand.
+pub const FORMAT_VERSION: u32 = 1;
+
+/// Renders `krate` as JSON into the output directory of `options`.
+pub fn run(
+    krate: clean::Crate,
+    options: RenderOptions,
+    renderinfo: RenderInfo,
+) -> Result<(), Error> {
+    let mut renderer = JsonRenderer::default();
+
+    for (did, &(ref path, kind)) in &renderinfo.external_paths {
+        renderer.paths.insert(id(*did), object(vec![
+            ("crate_id", did.krate.as_u32().to_json()),
+            ("path", path.to_json()),
+            ("kind", ItemType::from(kind).as_str().to_json()),
+        ]));
+    }
+
+    let root = krate.module.as_ref().map(|module| {
+        renderer.collect_impls(module);
+        renderer.item(module, &[krate.name.clone()], None)
+    });
+
+    let external_crates = krate.externs.iter().map(|&(cnum, ref external_crate)| {
+        (cnum.as_u32().to_string(), object(vec![("name", external_crate.name.to_json())]))
+    }).collect::<BTreeMap<_, _>>();
+
+    let output = object(vec![
+        ("format_version", FORMAT_VERSION.to_json()),
+        ("name", krate.name.to_json()),
+        ("version", krate.version.to_json()),
+        ("root", root.to_json()),
+        ("index", Json::Object(renderer.index)),
+        ("paths", Json::Object(renderer.paths)),
+        ("external_crates", Json::Object(external_crates)),
+    ]);
+
+    fs::create_dir_all(&options.output)
+        .map_err(|error| Error { file: options.output.clone(), error })?;
+    let file = options.output.join(format!("{}.json", krate.name));
+    fs::write(&file, output.to_string()).map_err(|error| Error { file, error })
+}
+
+#[derive(Default)]
+struct JsonRenderer {
+    index: BTreeMap<String, Json>,
+    paths: BTreeMap<String, Json>,
+    /// The impls of the crate, keyed by the `DefId` of the type they are for.
+    impls: FxHashMap<DefId, Vec<String>>,
+    /// The trait impls of the crate, keyed by the `DefId` of the trait.
+    implementors: FxHashMap<DefId, Vec<String>>,
+}
+
+/// Returns the id of an item. Blanket impls from other crates are instantiated once for every
+/// type they apply to, so the items inside them are told apart by the `suffix`, which is the id
+/// of the type the impl was instantiated for.
+fn item_id(did: DefId, suffix: Option<&str>) -> String {
+    match suffix {
+        Some(suffix) => format!("{}@{}", id(did), suffix),
+        None => id(did),
+    }
+}
+
+fn impl_suffix(impl_: &clean::Impl) -> Option<String> {
+    if impl_.blanket_impl.is_some() {
+        impl_.for_.def_id().map(id)
+    } else {
+        None
+    }
+}
+
+impl JsonRenderer {
+    /// Records which types and traits each impl of the crate belongs to, so that they can be
+    /// listed on those items.
+    fn collect_impls(&mut self, item: &clean::Item) {
+        match item.inner {
+            clean::ModuleItem(ref module) => {
+                for item in &module.items {
+                    self.collect_impls(item);
+                }
+            }
+            clean::ImplItem(ref impl_) => {
+                let impl_id = item_id(item.def_id, impl_suffix(impl_).as_ref().map(|s| &**s));
+                if let Some(did) = impl_.for_.def_id() {
+                    self.impls.entry(did).or_default().push(impl_id.clone());
+                }
+                if let Some(did) = impl_.trait_.def_id() {
+                    self.implementors.entry(did).or_default().push(impl_id);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Adds `item` and everything it contains to the index, and returns its id.
+    fn item(&mut self, item: &clean::Item, path: &[String], suffix: Option<&str>) -> String {
+        let impl_suffix = match item.inner {
+            clean::ImplItem(ref impl_) => impl_suffix(impl_),
+            _ => None,
+        };
+        let suffix = impl_suffix.as_ref().map(|s| &**s).or(suffix);
+        let item_id = item_id(item.def_id, suffix);
+        let item_type = ItemType::from(item);
+
+        let mut item_path = path.to_vec();
+        if let Some(ref name) = item.name {
+            if !name.is_empty() {
+                item_path.push(name.clone());
+            }
+        }
+        let records_path = match item.inner {
+            clean::ImplItem(..) | clean::ImportItem(..) | clean::ExternCrateItem(..) |
+            clean::StructFieldItem(..) | clean::VariantItem(..) | clean::TyMethodItem(..) |
+            clean::MethodItem(..) | clean::AssocConstItem(..) | clean::AssocTypeItem(..) => false,
+            _ => suffix.is_none() && !item_path.is_empty(),
+        };
+        if records_path {
+            self.paths.insert(item_id.clone(), object(vec![
+                ("crate_id", item.def_id.krate.as_u32().to_json()),
+                ("path", item_path.to_json()),
+                ("kind", item_type.as_str().to_json()),
+            ]));
+        }
+
+        let inner = self.inner(item, &item_path, suffix);
+
+        let links = item.attrs.links.iter().filter_map(|&(ref text, did, _)| {
+            did.map(|did| (text.clone(), id(did).to_json()))
+        }).collect::<BTreeMap<_, _>>();
+        let attrs = item.attrs.other_attrs.iter()
+            .map(pprust::attribute_to_string)
+            .collect::<Vec<_>>();
+        let span = if item.source.loline == 0 { None } else { Some(item.source.to_json()) };
+
+        self.index.insert(item_id.clone(), object(vec![
+            ("id", item_id.to_json()),
+            ("crate_id", item.def_id.krate.as_u32().to_json()),
+            ("name", item.name.to_json()),
+            ("kind", item_type.as_str().to_json()),
+            ("span", span.to_json()),
+            ("visibility", item.visibility.to_json()),
+            ("docs", item.attrs.collapsed_doc_value().to_json()),
+            ("links", Json::Object(links)),
+            ("attrs", attrs.to_json()),
+            ("deprecation", item.deprecation.to_json()),
+            ("stability", item.stability.to_json()),
+            ("inner", inner),
+        ]));
+
+        item_id
+    }
+
+    /// Adds the items that are not stripped to the index, and returns their ids.
+    fn children<'a, I>(&mut self, items: I, path: &[String], suffix: Option<&str>) -> Json
+        where I: IntoIterator<Item = &'a clean::Item>
+    {
+        Json::Array(items.into_iter()
+            .filter(|item| !item.is_stripped())
+            .map(|item| self.item(item, path, suffix).to_json())
+            .collect())
+    }
+
+    fn impls_of(&self, did: DefId) -> Json {
+        let mut impls = self.impls.get(&did).cloned().unwrap_or_default();
+        impls.sort();
+        impls.to_json()
+    }
+
+    fn inner(&mut self, item: &clean::Item, path: &[String], suffix: Option<&str>) -> Json {
+        match item.inner {
+            clean::ModuleItem(ref module) => object(vec![
+                ("is_crate", module.is_crate.to_json()),
+                ("items", self.children(&module.items, path, suffix)),
+            ]),
+            clean::ExternCrateItem(ref name, ref original) => object(vec![
+                ("name", name.to_json()),
+                ("original", original.to_json()),
+            ]),
+            clean::ImportItem(ref import) => {
+                let (name, source, glob) = match *import {
+                    clean::Import::Simple(ref name, ref source) => (Some(name), source, false),
+                    clean::Import::Glob(ref source) => (None, source, true),
+                };
+                object(vec![
+                    ("name", name.to_json()),
+                    ("source", path_to_string(&source.path).to_json()),
+                    ("id", source.did.map(id).to_json()),
+                    ("glob", glob.to_json()),
+                ])
+            }
+            clean::StructItem(clean::Struct {
+                struct_type, ref generics, ref fields, fields_stripped
+            }) |
+            clean::UnionItem(clean::Union {
+                struct_type, ref generics, ref fields, fields_stripped
+            }) => object(vec![
+                ("struct_type", struct_type_str(struct_type).to_json()),
+                ("generics", generics.to_json()),
+                ("fields", self.children(fields, path, suffix)),
+                ("fields_stripped", fields_stripped.to_json()),
+                ("impls", self.impls_of(item.def_id)),
+            ]),
+            clean::EnumItem(ref enum_) => object(vec![
+                ("generics", enum_.generics.to_json()),
+                ("variants", self.children(&enum_.variants, path, suffix)),
+                ("variants_stripped", enum_.variants_stripped.to_json()),
+                ("impls", self.impls_of(item.def_id)),
+            ]),
+            clean::VariantItem(ref variant) => match variant.kind {
+                clean::VariantKind::CLike => object(vec![
+                    ("variant_kind", "plain".to_json()),
+                ]),
+                clean::VariantKind::Tuple(ref types) => object(vec![
+                    ("variant_kind", "tuple".to_json()),
+                    ("types", list(types)),
+                ]),
+                clean::VariantKind::Struct(ref variant) => object(vec![
+                    ("variant_kind", "struct".to_json()),
+                    ("fields", self.children(&variant.fields, path, suffix)),
+                    ("fields_stripped", variant.fields_stripped.to_json()),
+                ]),
+            },
+            clean::StructFieldItem(ref ty) => object(vec![("type", ty.to_json())]),
+            clean::FunctionItem(ref f) | clean::ForeignFunctionItem(ref f) => object(vec![
+                ("decl", f.decl.to_json()),
+                ("generics", f.generics.to_json()),
+                ("header", f.header.to_json()),
+            ]),
+            clean::MethodItem(ref m) => object(vec![
+                ("decl", m.decl.to_json()),
+                ("generics", m.generics.to_json()),
+                ("header", m.header.to_json()),
+                ("has_body", true.to_json()),
+            ]),
+            clean::TyMethodItem(ref m) => object(vec![
+                ("decl", m.decl.to_json()),
+                ("generics", m.generics.to_json()),
+                ("header", m.header.to_json()),
+                ("has_body", false.to_json()),
+            ]),
+            clean::TypedefItem(ref typedef, _) => object(vec![
+                ("type", typedef.type_.to_json()),
+                ("generics", typedef.generics.to_json()),
+            ]),
+            clean::OpaqueTyItem(ref opaque, _) => object(vec![
+                ("bounds", list(&opaque.bounds)),
+                ("generics", opaque.generics.to_json()),
+            ]),
+            clean::StaticItem(ref s) | clean::ForeignStaticItem(ref s) => object(vec![
+                ("type", s.type_.to_json()),
+                ("mutable", (s.mutability == clean::Mutable).to_json()),
+                ("expr", s.expr.to_json()),
+            ]),
+            clean::ConstantItem(ref c) => c.to_json(),
+            clean::TraitItem(ref t) => object(vec![
+                ("is_auto", t.is_auto.to_json()),
+                ("is_unsafe", (t.unsafety == rustc::hir::Unsafety::Unsafe).to_json()),
+                ("items", self.children(&t.items, path, suffix)),
+                ("generics", t.generics.to_json()),
+                ("bounds", list(&t.bounds)),
+                ("implementors", {
+                    let mut implementors = self.implementors.get(&item.def_id)
+                        .cloned()
+                        .unwrap_or_default();
+                    implementors.sort();
+                    implementors.to_json()
+                }),
+            ]),
+            clean::TraitAliasItem(ref alias) => object(vec![
+                ("generics", alias.generics.to_json()),
+                ("bounds", list(&alias.bounds)),
+            ]),
+            clean::ImplItem(ref impl_) => {
+                let mut provided_trait_methods =
+                    impl_.provided_trait_methods.iter().cloned().collect::<Vec<_>>();
+                provided_trait_methods.sort();
+                let negative = impl_.polarity == Some(clean::ImplPolarity::Negative);
+                object(vec![
+                    ("is_unsafe", (impl_.unsafety == rustc::hir::Unsafety::Unsafe).to_json()),
+                    ("generics", impl_.generics.to_json()),
+                    ("provided_trait_methods", provided_trait_methods.to_json()),
+                    ("trait", impl_.trait_.to_json()),
+                    ("for", impl_.for_.to_json()),
+                    ("items", self.children(&impl_.items, path, suffix)),
+                    ("negative", negative.to_json()),
+                    ("synthetic", impl_.synthetic.to_json()),
+                    ("blanket_impl", impl_.blanket_impl.to_json()),
+                ])
+            }
+            clean::ForeignTypeItem => object(vec![("impls", self.impls_of(item.def_id))]),
+            clean::MacroItem(ref m) => object(vec![("source", m.source.to_json())]),
+            clean::ProcMacroItem(ref m) => {
+                let kind = match m.kind {
+                    syntax::ext::base::MacroKind::Bang => "bang",
+                    syntax::ext::base::MacroKind::Attr => "attr",
+                    syntax::ext::base::MacroKind::Derive => "derive",
+                };
+                object(vec![
+                    ("kind", kind.to_json()),
+                    ("helpers", m.helpers.to_json()),
+                ])
+            }
+            clean::PrimitiveItem(prim) => object(vec![("name", prim.as_str().to_json())]),
+            clean::AssocConstItem(ref ty, ref default) => object(vec![
+                ("type", ty.to_json()),
+                ("default", default.to_json()),
+            ]),
+            clean::AssocTypeItem(ref bounds, ref default) => object(vec![
+                ("bounds", list(bounds)),
+                ("default", default.to_json()),
+            ]),
+            clean::KeywordItem(ref keyword) => object(vec![("keyword", keyword.to_json())]),
+            // Stripped items are never added to the index.
+            clean::StrippedItem(..) => Json::Null,
+        }
+    }
+}
+
+fn struct_type_str(struct_type: doctree::StructType) -> &'static str {
+    match struct_type {
+        doctree::StructType::Plain => "plain",
+        doctree::StructType::Tuple => "tuple",
+        doctree::StructType::Unit => "unit",
+    }
+}
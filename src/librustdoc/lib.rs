@@ -56,6 +56,7 @@ mod core;
 mod docfs;
 mod doctree;
 mod fold;
+mod json;
 pub mod html {
     crate mod highlight;
     crate mod escape;
@@ -70,6 +71,7 @@ pub mod html {
 }
 mod markdown;
 mod passes;
+mod scrape_examples;
 mod visit_ast;
 mod visit_lib;
 mod test;
@@ -126,7 +128,7 @@ fn opts() -> Vec<RustcOptGroup> {
                      "[rust]")
         }),
         stable("w", |o| {
-            o.optopt("w", "output-format", "the output type to write", "[html]")
+            o.optopt("w", "output-format", "the output type to write", "[html|json]")
         }),
         stable("o", |o| o.optopt("o", "output", "where to place the output", "PATH")),
         stable("crate-name", |o| {
@@ -252,9 +254,16 @@ fn opts() -> Vec<RustcOptGroup> {
         }),
         unstable("theme-checker", |o| {
             o.optmulti("", "theme-checker",
-                       "check if given theme is valid",
+                       "check if given theme, or directory of templates given to \
+                        --html-templates, is valid",
                        "FILES")
         }),
+        unstable("html-templates", |o| {
+            o.optopt("", "html-templates",
+                     "directory with a page.html and/or a sidebar.html template to use instead \
+                      of the default page shell and sidebar",
+                     "PATH")
+        }),
         unstable("resource-suffix", |o| {
             o.optopt("",
                      "resource-suffix",
@@ -280,7 +289,7 @@ fn opts() -> Vec<RustcOptGroup> {
             o.optopt("",
                      "error-format",
                      "How errors and other messages are produced",
-                     "human|json|short")
+                     "human|json|short|sarif|pretty-sarif")
         }),
         stable("json", |o| {
             o.optopt("",
@@ -371,6 +380,34 @@ fn opts() -> Vec<RustcOptGroup> {
                        "",
                        "One (of possibly many) arguments to pass to the runtool")
         }),
+        unstable("merge-doctests", |o| {
+            o.optflag("",
+                      "merge-doctests",
+                      "compile doctests that don't need a crate of their own into a single binary")
+        }),
+        unstable("scrape-examples-output-path", |o| {
+            o.optopt("",
+                     "scrape-examples-output-path",
+                     "write the calls made by the crate to this file instead of documenting it",
+                     "PATH")
+        }),
+        unstable("scrape-examples-target-crate", |o| {
+            o.optmulti("",
+                       "scrape-examples-target-crate",
+                       "record the calls to functions of this crate",
+                       "CRATE")
+        }),
+        unstable("scrape-tests", |o| {
+            o.optflag("",
+                      "scrape-tests",
+                      "compile the scraped crate as a test crate")
+        }),
+        unstable("with-examples", |o| {
+            o.optmulti("",
+                       "with-examples",
+                       "show the calls written to this file by --scrape-examples-output-path",
+                       "PATH")
+        }),
         unstable("test-builder", |o| {
             o.optflag("",
                       "test-builder",
@@ -423,6 +460,10 @@ fn main_options(options: config::Options) -> i32 {
         (false, false) => {}
     }
 
+    if options.scrape_examples_options.is_some() {
+        return scrape_examples::run(options, &diag);
+    }
+
     // need to move these items separately because we lose them by the time the closure is called,
     // but we can't crates the Handler ahead of time because it's not Send
     let diag_opts = (options.error_format,
@@ -430,6 +471,7 @@ fn main_options(options: config::Options) -> i32 {
                      options.debugging_options.ui_testing,
                      options.edition);
     let show_coverage = options.show_coverage;
+    let output_format = options.output_format;
     rust_input(options, move |out| {
         if show_coverage {
             // if we ran coverage, bail early, we don't need to also generate docs at this point
@@ -441,13 +483,11 @@ fn main_options(options: config::Options) -> i32 {
         info!("going to format");
         let (error_format, treat_err_as_bug, ui_testing, edition) = diag_opts;
         let diag = core::new_handler(error_format, None, treat_err_as_bug, ui_testing);
-        match html::render::run(
-            krate,
-            renderopts,
-            renderinfo,
-            &diag,
-            edition,
-        ) {
+        let res = match output_format {
+            Some(config::OutputFormat::Json) => json::run(krate, renderopts, renderinfo),
+            _ => html::render::run(krate, renderopts, renderinfo, &diag, edition),
+        };
+        match res {
             Ok(_) => rustc_driver::EXIT_SUCCESS,
             Err(e) => {
                 diag.struct_err(&format!("couldn't generate documentation: {}", e.error))
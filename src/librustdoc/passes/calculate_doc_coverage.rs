@@ -1,8 +1,11 @@
 use crate::clean;
+use crate::config::OutputFormat;
 use crate::core::DocContext;
 use crate::fold::{self, DocFolder};
-use crate::passes::Pass;
+use crate::html::markdown::{find_testable_code, ErrorCodes};
+use crate::passes::{Pass, Tests};
 
+use serialize::json::{Json, ToJson};
 use syntax::attr;
 use syntax_pos::FileName;
 use syntax::symbol::sym;
@@ -16,11 +19,16 @@ pub const CALCULATE_DOC_COVERAGE: Pass = Pass {
     description: "counts the number of items with and without documentation",
 };
 
-fn calculate_doc_coverage(krate: clean::Crate, _: &DocContext<'_>) -> clean::Crate {
+fn calculate_doc_coverage(krate: clean::Crate, ctx: &DocContext<'_>) -> clean::Crate {
     let mut calc = CoverageCalculator::default();
+    calc.path.push(krate.name.clone());
     let krate = calc.fold_crate(krate);
 
-    calc.print_results();
+    if ctx.renderinfo.borrow().output_format == Some(OutputFormat::Json) {
+        calc.print_json();
+    } else {
+        calc.print_results();
+    }
 
     krate
 }
@@ -29,15 +37,19 @@ fn calculate_doc_coverage(krate: clean::Crate, _: &DocContext<'_>) -> clean::Cra
 struct ItemCount {
     total: u64,
     with_docs: u64,
+    with_examples: u64,
 }
 
 impl ItemCount {
-    fn count_item(&mut self, has_docs: bool) {
+    fn count_item(&mut self, has_docs: bool, has_doc_example: bool) {
         self.total += 1;
 
         if has_docs {
             self.with_docs += 1;
         }
+        if has_doc_example {
+            self.with_examples += 1;
+        }
     }
 
     fn percentage(&self) -> Option<f64> {
@@ -47,6 +59,14 @@ impl ItemCount {
             None
         }
     }
+
+    fn to_json_object(&self) -> BTreeMap<String, Json> {
+        let mut obj = BTreeMap::new();
+        obj.insert("total".to_string(), self.total.to_json());
+        obj.insert("with_docs".to_string(), self.with_docs.to_json());
+        obj.insert("with_examples".to_string(), self.with_examples.to_json());
+        obj
+    }
 }
 
 impl ops::Sub for ItemCount {
@@ -56,6 +76,7 @@ impl ops::Sub for ItemCount {
         ItemCount {
             total: self.total - rhs.total,
             with_docs: self.with_docs - rhs.with_docs,
+            with_examples: self.with_examples - rhs.with_examples,
         }
     }
 }
@@ -64,30 +85,70 @@ impl ops::AddAssign for ItemCount {
     fn add_assign(&mut self, rhs: Self) {
         self.total += rhs.total;
         self.with_docs += rhs.with_docs;
+        self.with_examples += rhs.with_examples;
+    }
+}
+
+/// An item that was counted but has no documentation.
+struct UndocumentedItem {
+    path: String,
+    span: clean::Span,
+}
+
+impl ToJson for UndocumentedItem {
+    fn to_json(&self) -> Json {
+        let mut obj = BTreeMap::new();
+        obj.insert("path".to_string(), self.path.to_json());
+        obj.insert("begin".to_string(), vec![self.span.loline, self.span.locol].to_json());
+        obj.insert("end".to_string(), vec![self.span.hiline, self.span.hicol].to_json());
+        Json::Object(obj)
     }
 }
 
 #[derive(Default)]
 struct CoverageCalculator {
     items: BTreeMap<FileName, ItemCount>,
+    undocumented: BTreeMap<FileName, Vec<UndocumentedItem>>,
+    /// The path of the item that is currently being folded, starting with the crate name.
+    path: Vec<String>,
 }
 
 impl CoverageCalculator {
+    fn print_json(&self) {
+        let mut total = ItemCount::default();
+        let mut files = BTreeMap::new();
+
+        for (file, &count) in &self.items {
+            let mut obj = count.to_json_object();
+            let undocumented = self.undocumented.get(file).map_or(&[][..], |items| &items[..]);
+            obj.insert("undocumented".to_string(),
+                       Json::Array(undocumented.iter().map(|item| item.to_json()).collect()));
+            files.insert(file.to_string(), Json::Object(obj));
+
+            total += count;
+        }
+
+        let mut output = BTreeMap::new();
+        output.insert("files".to_string(), Json::Object(files));
+        output.insert("total".to_string(), Json::Object(total.to_json_object()));
+        println!("{}", Json::Object(output).pretty());
+    }
+
     fn print_results(&self) {
         let mut total = ItemCount::default();
 
         fn print_table_line() {
-            println!("+-{0:->35}-+-{0:->10}-+-{0:->10}-+-{0:->10}-+", "");
+            println!("+-{0:->35}-+-{0:->10}-+-{0:->10}-+-{0:->10}-+-{0:->10}-+", "");
         }
 
         fn print_table_record(name: &str, count: ItemCount, percentage: f64) {
-            println!("| {:<35} | {:>10} | {:>10} | {:>9.1}% |",
-                     name, count.with_docs, count.total, percentage);
+            println!("| {:<35} | {:>10} | {:>10} | {:>10} | {:>9.1}% |",
+                     name, count.with_docs, count.with_examples, count.total, percentage);
         }
 
         print_table_line();
-        println!("| {:<35} | {:>10} | {:>10} | {:>10} |",
-                 "File", "Documented", "Total", "Percentage");
+        println!("| {:<35} | {:>10} | {:>10} | {:>10} | {:>10} |",
+                 "File", "Documented", "Examples", "Total", "Percentage");
         print_table_line();
 
         for (file, &count) in &self.items {
@@ -115,6 +176,11 @@ impl CoverageCalculator {
 impl fold::DocFolder for CoverageCalculator {
     fn fold_item(&mut self, i: clean::Item) -> Option<clean::Item> {
         let has_docs = !i.attrs.doc_strings.is_empty();
+        let mut tests = Tests { found_tests: 0 };
+        if let Some(dox) = i.attrs.collapsed_doc_value() {
+            find_testable_code(&dox, &mut tests, ErrorCodes::No, false);
+        }
+        let has_doc_example = tests.found_tests > 0;
 
         match i.inner {
             _ if !i.def_id.is_local() => {
@@ -159,10 +225,30 @@ impl fold::DocFolder for CoverageCalculator {
                 debug!("counting {:?} {:?} in {}", i.type_(), i.name, i.source.filename);
                 self.items.entry(i.source.filename.clone())
                           .or_default()
-                          .count_item(has_docs);
+                          .count_item(has_docs, has_doc_example);
+
+                if !has_docs {
+                    let mut path = self.path.clone();
+                    path.extend(i.name.clone().filter(|name| !name.is_empty()));
+                    self.undocumented.entry(i.source.filename.clone())
+                                     .or_default()
+                                     .push(UndocumentedItem {
+                                         path: path.join("::"),
+                                         span: i.source.clone(),
+                                     });
+                }
             }
         }
 
-        self.fold_item_recur(i)
+        // items nested in impls are named after the type the impl is for
+        let segment = match i.inner {
+            clean::ImplItem(ref impl_) => Some(format!("{:#}", impl_.for_.print())),
+            _ => i.name.clone().filter(|name| !name.is_empty()),
+        };
+        let old_len = self.path.len();
+        self.path.extend(segment);
+        let ret = self.fold_item_recur(i);
+        self.path.truncate(old_len);
+        ret
     }
 }
@@ -1,16 +1,15 @@
-use errors::Applicability;
+use errors::{Applicability, DiagnosticBuilder};
 use rustc::hir::def::{Res, DefKind, Namespace::{self, *}, PerNS};
-use rustc::hir::def_id::DefId;
+use rustc::hir::def_id::{DefId, CRATE_DEF_INDEX, LOCAL_CRATE};
 use rustc::hir;
 use rustc::lint as lint;
-use rustc::ty;
+use rustc::ty::{self, DefIdTree};
 use rustc_resolve::ParentScope;
-use syntax;
 use syntax::ast::{self, Ident};
 use syntax::ext::base::SyntaxExtensionKind;
 use syntax::feature_gate::UnstableFeatures;
 use syntax::symbol::Symbol;
-use syntax_pos::DUMMY_SP;
+use syntax_pos::{Span, DUMMY_SP};
 
 use std::ops::Range;
 
@@ -58,7 +57,7 @@ impl<'a, 'tcx> LinkCollector<'a, 'tcx> {
                ns: Namespace,
                current_item: &Option<String>,
                parent_id: Option<hir::HirId>)
-        -> Result<(Res, Option<String>), ()>
+        -> Result<(LinkRes, Option<String>), ()>
     {
         let cx = self.cx;
 
@@ -82,15 +81,15 @@ impl<'a, 'tcx> LinkCollector<'a, 'tcx> {
                     Res::Def(DefKind::AssocTy, _) => false,
                     Res::Def(DefKind::Variant, _) => return handle_variant(cx, res),
                     // Not a trait item; just return what we found.
-                    Res::PrimTy(..) => return Ok((res, Some(path_str.to_owned()))),
-                    _ => return Ok((res, None))
+                    Res::PrimTy(prim) => return Ok((LinkRes::Primitive(prim.into()), None)),
+                    _ => return Ok((LinkRes::Def(res), None))
                 };
 
                 if value != (ns == ValueNS) {
                     return Err(())
                 }
             } else if let Some(prim) = is_primitive(path_str, ns) {
-                return Ok((prim, Some(path_str.to_owned())))
+                return Ok((LinkRes::Primitive(prim), None))
             } else {
                 // If resolution failed, it may still be a method
                 // because methods are not handled by the resolver
@@ -120,14 +119,15 @@ impl<'a, 'tcx> LinkCollector<'a, 'tcx> {
                 }
             }
             if let Some(prim) = is_primitive(&path, TypeNS) {
-                let did = primitive_impl(cx, &path).ok_or(())?;
-                return cx.tcx.associated_items(did)
+                return primitive_impls(cx, prim).into_iter()
+                    .flat_map(|did| cx.tcx.associated_items(did))
                     .find(|item| item.ident.name == item_name)
                     .and_then(|item| match item.kind {
                         ty::AssocKind::Method => Some("method"),
+                        ty::AssocKind::Const => Some("associatedconstant"),
                         _ => None,
                     })
-                    .map(|out| (prim, Some(format!("{}#{}.{}", path, out, item_name))))
+                    .map(|out| (LinkRes::Primitive(prim), Some(format!("{}.{}", out, item_name))))
                     .ok_or(());
             }
 
@@ -146,41 +146,50 @@ impl<'a, 'tcx> LinkCollector<'a, 'tcx> {
                     let item = cx.tcx.inherent_impls(did)
                                      .iter()
                                      .flat_map(|imp| cx.tcx.associated_items(*imp))
-                                     .find(|item| item.ident.name == item_name);
+                                     .find(|item| item.ident.name == item_name)
+                                     .or_else(|| trait_impl_item(cx, did, item_name));
                     if let Some(item) = item {
                         let out = match item.kind {
                             ty::AssocKind::Method if ns == ValueNS => "method",
                             ty::AssocKind::Const if ns == ValueNS => "associatedconstant",
                             _ => return Err(())
                         };
-                        Ok((ty_res, Some(format!("{}.{}", out, item_name))))
+                        Ok((LinkRes::Def(ty_res), Some(format!("{}.{}", out, item_name))))
                     } else {
                         match cx.tcx.type_of(did).kind {
+                            ty::Adt(def, _) if def.is_enum() => {
+                                def.variants.iter()
+                                   .find(|variant| variant.ident.name == item_name)
+                                   .map(|variant| {
+                                       (LinkRes::Def(ty_res),
+                                        Some(format!("variant.{}", variant.ident)))
+                                   })
+                                   .ok_or(())
+                            }
                             ty::Adt(def, _) => {
-                                if let Some(item) = if def.is_enum() {
-                                    def.all_fields().find(|item| item.ident.name == item_name)
-                                } else {
-                                    def.non_enum_variant()
-                                       .fields
-                                       .iter()
-                                       .find(|item| item.ident.name == item_name)
-                                } {
-                                    Ok((ty_res,
-                                        Some(format!("{}.{}",
-                                                     if def.is_enum() {
-                                                         "variant"
-                                                     } else {
-                                                         "structfield"
-                                                     },
-                                                     item.ident))))
-                                } else {
-                                    Err(())
-                                }
+                                def.non_enum_variant()
+                                   .fields
+                                   .iter()
+                                   .find(|field| field.ident.name == item_name)
+                                   .map(|field| {
+                                       (LinkRes::Def(ty_res),
+                                        Some(format!("structfield.{}", field.ident)))
+                                   })
+                                   .ok_or(())
                             }
                             _ => Err(()),
                         }
                     }
                 }
+                Res::Def(DefKind::Variant, did) => {
+                    let variant = cx.tcx.expect_variant_res(ty_res);
+                    let field = variant.fields.iter()
+                                       .find(|field| field.ident.name == item_name)
+                                       .ok_or(())?;
+                    let parent = cx.tcx.parent(did).ok_or(())?;
+                    Ok((LinkRes::Def(Res::Def(DefKind::Enum, parent)),
+                        Some(format!("variant.{}.field.{}", variant.ident, field.ident))))
+                }
                 Res::Def(DefKind::Trait, did) => {
                     let item = cx.tcx.associated_item_def_ids(did).iter()
                                  .map(|item| cx.tcx.associated_item(*item))
@@ -199,7 +208,7 @@ impl<'a, 'tcx> LinkCollector<'a, 'tcx> {
                             _ => return Err(())
                         };
 
-                        Ok((ty_res, Some(format!("{}.{}", kind, item_name))))
+                        Ok((LinkRes::Def(ty_res), Some(format!("{}.{}", kind, item_name))))
                     } else {
                         Err(())
                     }
@@ -211,6 +220,272 @@ impl<'a, 'tcx> LinkCollector<'a, 'tcx> {
             Err(())
         }
     }
+
+    /// Resolves a string as a path in every namespace.
+    fn resolve_all(&self,
+                   path_str: &str,
+                   current_item: &Option<String>,
+                   parent_id: Option<hir::HirId>)
+        -> PerNS<Option<(LinkRes, Option<String>)>>
+    {
+        PerNS {
+            macro_ns: macro_resolve(self.cx, path_str).map(|res| (LinkRes::Def(res), None)),
+            type_ns: self.resolve(path_str, TypeNS, current_item, parent_id).ok(),
+            value_ns: self
+                .resolve(path_str, ValueNS, current_item, parent_id)
+                .ok()
+                .and_then(|(res, fragment)| {
+                    // Constructors are picked up in the type namespace.
+                    match res {
+                        LinkRes::Def(Res::Def(DefKind::Ctor(..), _)) |
+                        LinkRes::Def(Res::SelfCtor(..)) => None,
+                        _ => Some((res, fragment))
+                    }
+                }),
+        }
+    }
+
+    /// Resolves the intra-doc links of `dox`, returning them as they are stored in
+    /// `Attributes::links`. The links that can't be resolved are reported through `diag_info`,
+    /// if any.
+    fn resolve_links(&self,
+                     dox: &str,
+                     current_item: &Option<String>,
+                     parent_node: Option<hir::HirId>,
+                     diag_info: Option<&DiagnosticInfo<'_>>)
+        -> Vec<(String, Option<DefId>, Option<String>)>
+    {
+        let cx = self.cx;
+        let mut links = Vec::new();
+
+        for (ori_link, link_range) in markdown_links(dox) {
+            // Bail early for real links.
+            if ori_link.contains('/') {
+                continue;
+            }
+
+            // [] is mostly likely not supposed to be a link
+            if ori_link.is_empty() {
+                continue;
+            }
+
+            let link = ori_link.replace("`", "");
+            let (disambiguator, path_str) = Disambiguator::from_link(&link);
+            let path_str = path_str.trim();
+
+            if path_str.contains(|ch: char| !(ch.is_alphanumeric() ||
+                                              ch == ':' || ch == '_')) {
+                continue;
+            }
+
+            let primitive = match disambiguator {
+                Some(Disambiguator::Primitive) => is_primitive(path_str, TypeNS),
+                _ => None,
+            };
+            let (res, fragment) = match (primitive, disambiguator.map(Disambiguator::ns)) {
+                // Items named after primitives, like the `std::str` module, shadow them unless a
+                // primitive is asked for.
+                (Some(prim), _) => (LinkRes::Primitive(prim), None),
+                (None, Some(MacroNS)) => {
+                    if let Some(res) = macro_resolve(cx, path_str) {
+                        (LinkRes::Def(res), None)
+                    } else {
+                        let candidates = self.resolve_all(path_str, current_item, parent_node);
+                        resolution_failure(cx, diag_info, path_str, dox, link_range, candidates);
+                        continue
+                    }
+                }
+                (None, Some(ns)) => {
+                    if let Ok(res) = self.resolve(path_str, ns, current_item, parent_node) {
+                        res
+                    } else {
+                        let candidates = self.resolve_all(path_str, current_item, parent_node);
+                        resolution_failure(cx, diag_info, path_str, dox, link_range, candidates);
+                        // This could just be a normal link or a broken link
+                        // we could potentially check if something is
+                        // "intra-doc-link-like" and warn in that case.
+                        continue;
+                    }
+                }
+                (None, None) => {
+                    // Try everything!
+                    let candidates = self.resolve_all(path_str, current_item, parent_node);
+
+                    if candidates.is_empty() {
+                        resolution_failure(cx, diag_info, path_str, dox, link_range, candidates);
+                        // this could just be a normal link
+                        continue;
+                    }
+
+                    let is_unambiguous = candidates.clone().present_items().count() == 1;
+                    if is_unambiguous {
+                        candidates.present_items().next().unwrap()
+                    } else {
+                        ambiguity_error(
+                            cx,
+                            diag_info,
+                            path_str,
+                            dox,
+                            link_range,
+                            candidates.map(|candidate| candidate.map(|(res, _)| res)),
+                        );
+                        continue;
+                    }
+                }
+            };
+
+            if let Some(disambiguator) = disambiguator {
+                // Links to associated items, variants and fields resolve to their parent, so
+                // only the kind of the items that are linked to directly can be checked.
+                if fragment.is_none() && !disambiguator.matches(res) {
+                    disambiguator_error(
+                        cx, diag_info, path_str, dox, link_range, disambiguator, res,
+                    );
+                    continue;
+                }
+            }
+
+            match res {
+                LinkRes::Primitive(prim) => {
+                    // Primitives have no `DefId`, so their links are built from the fragment,
+                    // which starts with the name of the primitive.
+                    let fragment = match fragment {
+                        Some(fragment) => format!("{}#{}", prim.as_str(), fragment),
+                        None => prim.as_str().to_owned(),
+                    };
+                    links.push((ori_link, None, Some(fragment)));
+                }
+                LinkRes::Def(res) => {
+                    let id = register_res(cx, res);
+                    links.push((ori_link, Some(id), fragment));
+                }
+            }
+        }
+
+        links
+    }
+}
+
+/// What an intra-doc link resolved to. Primitive types are kept apart from the other items, since
+/// most of them have no `Res`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum LinkRes {
+    Def(Res),
+    Primitive(PrimitiveType),
+}
+
+impl LinkRes {
+    fn article(self) -> &'static str {
+        match self {
+            LinkRes::Def(res) => res.article(),
+            LinkRes::Primitive(_) => "a",
+        }
+    }
+
+    fn descr(self) -> &'static str {
+        match self {
+            LinkRes::Def(res) => res.descr(),
+            LinkRes::Primitive(_) => "builtin type",
+        }
+    }
+}
+
+/// Where the diagnostics about the links of some documentation are reported.
+struct DiagnosticInfo<'a> {
+    /// The node whose lint level applies.
+    hir_id: hir::HirId,
+    /// The attributes the documentation comes from.
+    attrs: &'a Attributes,
+    /// The span used when the documentation has no span of its own.
+    span: Span,
+}
+
+/// A hint in an intra-doc link about what it links to, like `struct@` in `[struct@Foo]` or
+/// `()` in `[foo()]`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Disambiguator {
+    /// The kind of the item, like `struct@`. Functions and methods are not told apart.
+    Kind(DefKind),
+    /// The namespace of the item, like `type@`, `value@` or `macro@`.
+    Namespace(Namespace),
+    /// A primitive type, with `prim@` or `primitive@`.
+    Primitive,
+}
+
+impl Disambiguator {
+    /// Splits the disambiguator off a link, if it has one.
+    fn from_link(link: &str) -> (Option<Disambiguator>, &str) {
+        use Disambiguator::{Kind, Namespace as NS, Primitive};
+
+        const PREFIXES: &[(&str, Disambiguator)] = &[
+            ("struct@", Kind(DefKind::Struct)),
+            ("enum@", Kind(DefKind::Enum)),
+            ("union@", Kind(DefKind::Union)),
+            ("trait@", Kind(DefKind::Trait)),
+            ("mod@", Kind(DefKind::Mod)),
+            ("module@", Kind(DefKind::Mod)),
+            ("const@", Kind(DefKind::Const)),
+            ("constant@", Kind(DefKind::Const)),
+            ("static@", Kind(DefKind::Static)),
+            ("fn@", Kind(DefKind::Fn)),
+            ("function@", Kind(DefKind::Fn)),
+            ("method@", Kind(DefKind::Method)),
+            ("type@", NS(TypeNS)),
+            ("value@", NS(ValueNS)),
+            ("macro@", NS(MacroNS)),
+            ("prim@", Primitive),
+            ("primitive@", Primitive),
+        ];
+
+        if let Some(&(prefix, disambiguator)) = PREFIXES.iter()
+            .find(|(prefix, _)| link.starts_with(prefix))
+        {
+            (Some(disambiguator), &link[prefix.len()..])
+        } else if link.ends_with("()") {
+            (Some(NS(ValueNS)), &link[..link.len() - 2])
+        } else if link.ends_with('!') {
+            (Some(NS(MacroNS)), &link[..link.len() - 1])
+        } else {
+            (None, link)
+        }
+    }
+
+    /// The namespace the link is resolved in.
+    fn ns(self) -> Namespace {
+        match self {
+            Disambiguator::Namespace(ns) => ns,
+            Disambiguator::Primitive => TypeNS,
+            Disambiguator::Kind(kind) => match kind {
+                DefKind::Const | DefKind::Static | DefKind::Fn | DefKind::Method => ValueNS,
+                _ => TypeNS,
+            },
+        }
+    }
+
+    /// Whether an item that was resolved in the namespace of the disambiguator has the kind it
+    /// asks for.
+    fn matches(self, res: LinkRes) -> bool {
+        match (self, res) {
+            (Disambiguator::Namespace(_), _) => true,
+            (Disambiguator::Primitive, LinkRes::Primitive(_)) => true,
+            (Disambiguator::Kind(DefKind::Fn), LinkRes::Def(Res::Def(DefKind::Method, _))) |
+            (Disambiguator::Kind(DefKind::Method), LinkRes::Def(Res::Def(DefKind::Fn, _))) => true,
+            (Disambiguator::Kind(kind), LinkRes::Def(Res::Def(res_kind, _))) => kind == res_kind,
+            (Disambiguator::Primitive, _) | (Disambiguator::Kind(_), _) => false,
+        }
+    }
+
+    /// Describes the items the disambiguator asks for, with an article.
+    fn descr(self) -> String {
+        match self {
+            Disambiguator::Kind(kind) => {
+                // Any local `DefId` gives the general description of the kind.
+                format!("{} {}", kind.article(), kind.descr(DefId::local(CRATE_DEF_INDEX)))
+            }
+            Disambiguator::Namespace(ns) => format!("an item of the {} namespace", ns.descr()),
+            Disambiguator::Primitive => "a builtin type".to_string(),
+        }
+    }
 }
 
 impl<'a, 'tcx> DocFolder for LinkCollector<'a, 'tcx> {
@@ -277,131 +552,35 @@ impl<'a, 'tcx> DocFolder for LinkCollector<'a, 'tcx> {
 
         look_for_tests(&cx, &dox, &item, true);
 
-        for (ori_link, link_range) in markdown_links(&dox) {
-            // Bail early for real links.
-            if ori_link.contains('/') {
-                continue;
-            }
-
-            // [] is mostly likely not supposed to be a link
-            if ori_link.is_empty() {
-                continue;
-            }
-
-            let link = ori_link.replace("`", "");
-            let (res, fragment) = {
-                let mut kind = None;
-                let path_str = if let Some(prefix) =
-                    ["struct@", "enum@", "type@",
-                     "trait@", "union@"].iter()
-                                      .find(|p| link.starts_with(**p)) {
-                    kind = Some(TypeNS);
-                    link.trim_start_matches(prefix)
-                } else if let Some(prefix) =
-                    ["const@", "static@",
-                     "value@", "function@", "mod@",
-                     "fn@", "module@", "method@"]
-                        .iter().find(|p| link.starts_with(**p)) {
-                    kind = Some(ValueNS);
-                    link.trim_start_matches(prefix)
-                } else if link.ends_with("()") {
-                    kind = Some(ValueNS);
-                    link.trim_end_matches("()")
-                } else if link.starts_with("macro@") {
-                    kind = Some(MacroNS);
-                    link.trim_start_matches("macro@")
-                } else if link.ends_with('!') {
-                    kind = Some(MacroNS);
-                    link.trim_end_matches('!')
-                } else {
-                    &link[..]
-                }.trim();
-
-                if path_str.contains(|ch: char| !(ch.is_alphanumeric() ||
-                                                  ch == ':' || ch == '_')) {
-                    continue;
-                }
-
-                match kind {
-                    Some(ns @ ValueNS) => {
-                        if let Ok(res) = self.resolve(path_str, ns, &current_item, parent_node) {
-                            res
-                        } else {
-                            resolution_failure(cx, &item, path_str, &dox, link_range);
-                            // This could just be a normal link or a broken link
-                            // we could potentially check if something is
-                            // "intra-doc-link-like" and warn in that case.
-                            continue;
-                        }
-                    }
-                    Some(ns @ TypeNS) => {
-                        if let Ok(res) = self.resolve(path_str, ns, &current_item, parent_node) {
-                            res
-                        } else {
-                            resolution_failure(cx, &item, path_str, &dox, link_range);
-                            // This could just be a normal link.
-                            continue;
-                        }
-                    }
-                    None => {
-                        // Try everything!
-                        let candidates = PerNS {
-                            macro_ns: macro_resolve(cx, path_str).map(|res| (res, None)),
-                            type_ns: self
-                                .resolve(path_str, TypeNS, &current_item, parent_node)
-                                .ok(),
-                            value_ns: self
-                                .resolve(path_str, ValueNS, &current_item, parent_node)
-                                .ok()
-                                .and_then(|(res, fragment)| {
-                                    // Constructors are picked up in the type namespace.
-                                    match res {
-                                        Res::Def(DefKind::Ctor(..), _) | Res::SelfCtor(..) => None,
-                                        _ => Some((res, fragment))
-                                    }
-                                }),
-                        };
-
-                        if candidates.is_empty() {
-                            resolution_failure(cx, &item, path_str, &dox, link_range);
-                            // this could just be a normal link
-                            continue;
-                        }
-
-                        let is_unambiguous = candidates.clone().present_items().count() == 1;
-                        if is_unambiguous {
-                            candidates.present_items().next().unwrap()
-                        } else {
-                            ambiguity_error(
-                                cx,
-                                &item,
-                                path_str,
-                                &dox,
-                                link_range,
-                                candidates.map(|candidate| candidate.map(|(res, _)| res)),
-                            );
-                            continue;
-                        }
-                    }
-                    Some(MacroNS) => {
-                        if let Some(res) = macro_resolve(cx, path_str) {
-                            (res, None)
-                        } else {
-                            resolution_failure(cx, &item, path_str, &dox, link_range);
-                            continue
-                        }
-                    }
+        let diag_info = cx.as_local_hir_id(item.def_id).map(|hir_id| DiagnosticInfo {
+            hir_id,
+            attrs: &item.attrs,
+            span: item.source.span(),
+        });
+        let links = self.resolve_links(&dox, &current_item, parent_node, diag_info.as_ref());
+
+        // The documentation of items inlined from other crates isn't checked, but the part of it
+        // that was written on their re-export is.
+        if diag_info.is_none() {
+            let reexport = cx.inlined_reexports.borrow().get(&item.def_id).cloned();
+            if let Some(reexport) = reexport {
+                let mut attrs = cx.tcx.hir().attrs(reexport).clean(cx);
+                attrs.collapse_doc_comments();
+                attrs.unindent_doc_comments();
+                if let Some(reexport_dox) = attrs.collapsed_doc_value() {
+                    let diag_info = DiagnosticInfo {
+                        hir_id: reexport,
+                        attrs: &attrs,
+                        span: cx.tcx.hir().span(reexport),
+                    };
+                    self.resolve_links(&reexport_dox, &current_item, parent_node,
+                                       Some(&diag_info));
                 }
-            };
-
-            if let Res::PrimTy(_) = res {
-                item.attrs.links.push((ori_link, None, fragment));
-            } else {
-                let id = register_res(cx, res);
-                item.attrs.links.push((ori_link, Some(id), fragment));
             }
         }
 
+        item.attrs.links.extend(links);
+
         if item.is_mod() && !item.attrs.inner_docs {
             self.mod_ids.push(item_hir_id.unwrap());
         }
@@ -445,80 +624,125 @@ fn macro_resolve(cx: &DocContext<'_>, path_str: &str) -> Option<Res> {
     })
 }
 
-/// Reports a resolution failure diagnostic.
+/// Reports a diagnostic about an intra-doc link, if `diag_info` says where.
 ///
-/// If we cannot find the exact source span of the resolution failure, we use the span of the
-/// documentation attributes themselves. This is a little heavy-handed, so we display the markdown
-/// line containing the failure as a note as well.
-fn resolution_failure(
+/// If we cannot find the exact source span of the link, we use the span of the documentation
+/// attributes themselves. This is a little heavy-handed, so we display the markdown line
+/// containing the link as a note as well. `decorate` is given the span of the link, if it was
+/// found, to add labels and suggestions.
+fn report_diagnostic(
     cx: &DocContext<'_>,
-    item: &Item,
-    path_str: &str,
+    msg: &str,
+    diag_info: Option<&DiagnosticInfo<'_>>,
     dox: &str,
     link_range: Option<Range<usize>>,
+    decorate: impl FnOnce(&mut DiagnosticBuilder<'_>, Option<Span>),
 ) {
-    let hir_id = match cx.as_local_hir_id(item.def_id) {
-        Some(hir_id) => hir_id,
+    let diag_info = match diag_info {
+        Some(diag_info) => diag_info,
         None => {
             // If non-local, no need to check anything.
             return;
         }
     };
-    let attrs = &item.attrs;
-    let sp = span_of_attrs(attrs).unwrap_or(item.source.span());
+    let attrs = diag_info.attrs;
+    let sp = span_of_attrs(attrs).unwrap_or(diag_info.span);
 
     let mut diag = cx.tcx.struct_span_lint_hir(
         lint::builtin::INTRA_DOC_LINK_RESOLUTION_FAILURE,
-        hir_id,
+        diag_info.hir_id,
         sp,
-        &format!("`[{}]` cannot be resolved, ignoring it...", path_str),
+        msg,
     );
-    if let Some(link_range) = link_range {
-        if let Some(sp) = super::source_span_for_markdown_range(cx, dox, &link_range, attrs) {
-            diag.set_span(sp);
-            diag.span_label(sp, "cannot be resolved, ignoring");
-        } else {
-            // blah blah blah\nblah\nblah [blah] blah blah\nblah blah
-            //                       ^     ~~~~
-            //                       |     link_range
-            //                       last_new_line_offset
-            let last_new_line_offset = dox[..link_range.start].rfind('\n').map_or(0, |n| n + 1);
-            let line = dox[last_new_line_offset..].lines().next().unwrap_or("");
-
-            // Print the line containing the `link_range` and manually mark it with '^'s.
-            diag.note(&format!(
-                "the link appears in this line:\n\n{line}\n\
-                 {indicator: <before$}{indicator:^<found$}",
-                line=line,
-                indicator="",
-                before=link_range.start - last_new_line_offset,
-                found=link_range.len(),
-            ));
+
+    let link_range = match link_range {
+        Some(link_range) => link_range,
+        None => {
+            decorate(&mut diag, None);
+            diag.emit();
+            return;
         }
     };
-    diag.help("to escape `[` and `]` characters, just add '\\' before them like \
-               `\\[` or `\\]`");
+
+    if let Some(sp) = super::source_span_for_markdown_range(cx, dox, &link_range, attrs) {
+        diag.set_span(sp);
+        decorate(&mut diag, Some(sp));
+    } else {
+        // blah blah blah\nblah\nblah [blah] blah blah\nblah blah
+        //                       ^     ~~~~
+        //                       |     link_range
+        //                       last_new_line_offset
+        let last_new_line_offset = dox[..link_range.start].rfind('\n').map_or(0, |n| n + 1);
+        let line = dox[last_new_line_offset..].lines().next().unwrap_or("");
+
+        // Print the line containing the `link_range` and manually mark it with '^'s.
+        diag.note(&format!(
+            "the link appears in this line:\n\n{line}\n\
+             {indicator: <before$}{indicator:^<found$}",
+            line=line,
+            indicator="",
+            before=link_range.start - last_new_line_offset,
+            found=link_range.len(),
+        ));
+        decorate(&mut diag, None);
+    }
+
     diag.emit();
 }
 
-fn ambiguity_error(
+/// Reports a resolution failure diagnostic. If the path resolves in other namespaces than the
+/// one the link asked for, the links to those items are suggested.
+fn resolution_failure(
     cx: &DocContext<'_>,
-    item: &Item,
+    diag_info: Option<&DiagnosticInfo<'_>>,
     path_str: &str,
     dox: &str,
     link_range: Option<Range<usize>>,
-    candidates: PerNS<Option<Res>>,
+    candidates: PerNS<Option<(LinkRes, Option<String>)>>,
 ) {
-    let hir_id = match cx.as_local_hir_id(item.def_id) {
-        Some(hir_id) => hir_id,
-        None => {
-            // If non-local, no need to check anything.
-            return;
-        }
-    };
-    let attrs = &item.attrs;
-    let sp = span_of_attrs(attrs).unwrap_or(item.source.span());
+    report_diagnostic(
+        cx,
+        &format!("`[{}]` cannot be resolved, ignoring it...", path_str),
+        diag_info,
+        dox,
+        link_range.clone(),
+        |diag, sp| {
+            if let Some(sp) = sp {
+                diag.span_label(sp, "cannot be resolved, ignoring");
+            }
+            for &ns in &[TypeNS, ValueNS, MacroNS] {
+                let res = match candidates[ns] {
+                    Some((res, _)) => res,
+                    None => continue,
+                };
+                match (sp, &link_range) {
+                    (Some(sp), Some(link_range)) => {
+                        suggest_disambiguator(diag, sp, res, ns, path_str, dox, link_range);
+                    }
+                    _ => {
+                        diag.note(&format!(
+                            "`{}` is {} {}",
+                            path_str,
+                            res.article(),
+                            res.descr(),
+                        ));
+                    }
+                }
+            }
+            diag.help("to escape `[` and `]` characters, just add '\\' before them like \
+                       `\\[` or `\\]`");
+        },
+    );
+}
 
+fn ambiguity_error(
+    cx: &DocContext<'_>,
+    diag_info: Option<&DiagnosticInfo<'_>>,
+    path_str: &str,
+    dox: &str,
+    link_range: Option<Range<usize>>,
+    candidates: PerNS<Option<LinkRes>>,
+) {
     let mut msg = format!("`{}` is ", path_str);
 
     let candidates = [TypeNS, ValueNS, MacroNS].iter().filter_map(|&ns| {
@@ -546,83 +770,94 @@ fn ambiguity_error(
         }
     }
 
-    let mut diag = cx.tcx.struct_span_lint_hir(
-        lint::builtin::INTRA_DOC_LINK_RESOLUTION_FAILURE,
-        hir_id,
-        sp,
-        &msg,
-    );
-
-    if let Some(link_range) = link_range {
-        if let Some(sp) = super::source_span_for_markdown_range(cx, dox, &link_range, attrs) {
-            diag.set_span(sp);
+    report_diagnostic(cx, &msg, diag_info, dox, link_range.clone(), |diag, sp| {
+        if let (Some(sp), Some(link_range)) = (sp, &link_range) {
             diag.span_label(sp, "ambiguous link");
 
             for (res, ns) in candidates {
-                let (action, mut suggestion) = match res {
-                    Res::Def(DefKind::Method, _) | Res::Def(DefKind::Fn, _) => {
-                        ("add parentheses", format!("{}()", path_str))
-                    }
-                    Res::Def(DefKind::Macro(..), _) => {
-                        ("add an exclamation mark", format!("{}!", path_str))
-                    }
-                    _ => {
-                        let type_ = match (res, ns) {
-                            (Res::Def(DefKind::Const, _), _) => "const",
-                            (Res::Def(DefKind::Static, _), _) => "static",
-                            (Res::Def(DefKind::Struct, _), _) => "struct",
-                            (Res::Def(DefKind::Enum, _), _) => "enum",
-                            (Res::Def(DefKind::Union, _), _) => "union",
-                            (Res::Def(DefKind::Trait, _), _) => "trait",
-                            (Res::Def(DefKind::Mod, _), _) => "module",
-                            (_, TypeNS) => "type",
-                            (_, ValueNS) => "value",
-                            (_, MacroNS) => "macro",
-                        };
+                suggest_disambiguator(diag, sp, res, ns, path_str, dox, link_range);
+            }
+        }
+    });
+}
 
-                        // FIXME: if this is an implied shortcut link, it's bad style to suggest `@`
-                        ("prefix with the item type", format!("{}@{}", type_, path_str))
-                    }
-                };
+/// Reports a link whose disambiguator asks for another kind of item than the one it resolved to.
+fn disambiguator_error(
+    cx: &DocContext<'_>,
+    diag_info: Option<&DiagnosticInfo<'_>>,
+    path_str: &str,
+    dox: &str,
+    link_range: Option<Range<usize>>,
+    disambiguator: Disambiguator,
+    res: LinkRes,
+) {
+    let msg = format!("incompatible link kind for `{}`", path_str);
+    report_diagnostic(cx, &msg, diag_info, dox, link_range.clone(), |diag, sp| {
+        let note = format!(
+            "this link resolved to {} {}, which is not {}",
+            res.article(),
+            res.descr(),
+            disambiguator.descr(),
+        );
+        diag.note(&note);
+        if let (Some(sp), Some(link_range)) = (sp, &link_range) {
+            suggest_disambiguator(diag, sp, res, disambiguator.ns(), path_str, dox, link_range);
+        }
+    });
+}
 
-                if dox.bytes().nth(link_range.start) == Some(b'`') {
-                    suggestion = format!("`{}`", suggestion);
-                }
+/// Suggests replacing the link at `sp` with a link that unambiguously resolves to `res`, which
+/// was found in the namespace `ns`.
+fn suggest_disambiguator(
+    diag: &mut DiagnosticBuilder<'_>,
+    sp: Span,
+    res: LinkRes,
+    ns: Namespace,
+    path_str: &str,
+    dox: &str,
+    link_range: &Range<usize>,
+) {
+    let (action, mut suggestion) = match res {
+        LinkRes::Def(Res::Def(DefKind::Method, _)) | LinkRes::Def(Res::Def(DefKind::Fn, _)) => {
+            ("add parentheses", format!("{}()", path_str))
+        }
+        LinkRes::Def(Res::Def(DefKind::Macro(..), _)) => {
+            ("add an exclamation mark", format!("{}!", path_str))
+        }
+        _ => {
+            let type_ = match (res, ns) {
+                (LinkRes::Def(Res::Def(DefKind::Const, _)), _) => "const",
+                (LinkRes::Def(Res::Def(DefKind::Static, _)), _) => "static",
+                (LinkRes::Def(Res::Def(DefKind::Struct, _)), _) => "struct",
+                (LinkRes::Def(Res::Def(DefKind::Enum, _)), _) => "enum",
+                (LinkRes::Def(Res::Def(DefKind::Union, _)), _) => "union",
+                (LinkRes::Def(Res::Def(DefKind::Trait, _)), _) => "trait",
+                (LinkRes::Def(Res::Def(DefKind::Mod, _)), _) => "module",
+                (LinkRes::Primitive(_), _) => "prim",
+                (_, TypeNS) => "type",
+                (_, ValueNS) => "value",
+                (_, MacroNS) => "macro",
+            };
 
-                diag.span_suggestion(
-                    sp,
-                    &format!("to link to the {}, {}", res.descr(), action),
-                    suggestion,
-                    Applicability::MaybeIncorrect,
-                );
-            }
-        } else {
-            // blah blah blah\nblah\nblah [blah] blah blah\nblah blah
-            //                       ^     ~~~~
-            //                       |     link_range
-            //                       last_new_line_offset
-            let last_new_line_offset = dox[..link_range.start].rfind('\n').map_or(0, |n| n + 1);
-            let line = dox[last_new_line_offset..].lines().next().unwrap_or("");
-
-            // Print the line containing the `link_range` and manually mark it with '^'s.
-            diag.note(&format!(
-                "the link appears in this line:\n\n{line}\n\
-                 {indicator: <before$}{indicator:^<found$}",
-                line=line,
-                indicator="",
-                before=link_range.start - last_new_line_offset,
-                found=link_range.len(),
-            ));
+            // FIXME: if this is an implied shortcut link, it's bad style to suggest `@`
+            ("prefix with the item type", format!("{}@{}", type_, path_str))
         }
+    };
+
+    if dox.bytes().nth(link_range.start) == Some(b'`') {
+        suggestion = format!("`{}`", suggestion);
     }
 
-    diag.emit();
+    diag.span_suggestion(
+        sp,
+        &format!("to link to the {}, {}", res.descr(), action),
+        suggestion,
+        Applicability::MaybeIncorrect,
+    );
 }
 
 /// Given an enum variant's res, return the res of its enum and the associated fragment.
-fn handle_variant(cx: &DocContext<'_>, res: Res) -> Result<(Res, Option<String>), ()> {
-    use rustc::ty::DefIdTree;
-
+fn handle_variant(cx: &DocContext<'_>, res: Res) -> Result<(LinkRes, Option<String>), ()> {
     let parent = if let Some(parent) = cx.tcx.parent(res.def_id()) {
         parent
     } else {
@@ -630,57 +865,77 @@ fn handle_variant(cx: &DocContext<'_>, res: Res) -> Result<(Res, Option<String>)
     };
     let parent_def = Res::Def(DefKind::Enum, parent);
     let variant = cx.tcx.expect_variant_res(res);
-    Ok((parent_def, Some(format!("{}.v", variant.ident.name))))
+    Ok((LinkRes::Def(parent_def), Some(format!("{}.v", variant.ident.name))))
+}
+
+/// Looks for an associated item named `item_name` in the traits implemented by the type `did`.
+///
+/// The impls of all the traits known to the documented crate are looked at, so that impls in
+/// the crate of the type, of the trait, or in any other crate are found. Blanket impls are
+/// ignored, since they may not apply to the type.
+fn trait_impl_item(cx: &DocContext<'_>, did: DefId, item_name: Symbol) -> Option<ty::AssocItem> {
+    let tcx = cx.tcx;
+    let ty = tcx.type_of(did);
+    let adt_did = match ty.kind {
+        ty::Adt(def, _) => def.did,
+        _ => return None,
+    };
+    tcx.all_traits(LOCAL_CRATE).iter().find_map(|&trait_did| {
+        // The items of the trait also cover the provided methods that the impl doesn't override.
+        let item = tcx.associated_items(trait_did).find(|item| item.ident.name == item_name)?;
+        let mut implemented = false;
+        tcx.for_each_relevant_impl(trait_did, ty, |impl_did| {
+            implemented |= tcx.impl_trait_ref(impl_did).map_or(false, |trait_ref| {
+                match trait_ref.self_ty().kind {
+                    ty::Adt(def, _) => def.did == adt_did,
+                    _ => false,
+                }
+            });
+        });
+        if implemented { Some(item) } else { None }
+    })
 }
 
-const PRIMITIVES: &[(&str, Res)] = &[
-    ("u8",    Res::PrimTy(hir::PrimTy::Uint(syntax::ast::UintTy::U8))),
-    ("u16",   Res::PrimTy(hir::PrimTy::Uint(syntax::ast::UintTy::U16))),
-    ("u32",   Res::PrimTy(hir::PrimTy::Uint(syntax::ast::UintTy::U32))),
-    ("u64",   Res::PrimTy(hir::PrimTy::Uint(syntax::ast::UintTy::U64))),
-    ("u128",  Res::PrimTy(hir::PrimTy::Uint(syntax::ast::UintTy::U128))),
-    ("usize", Res::PrimTy(hir::PrimTy::Uint(syntax::ast::UintTy::Usize))),
-    ("i8",    Res::PrimTy(hir::PrimTy::Int(syntax::ast::IntTy::I8))),
-    ("i16",   Res::PrimTy(hir::PrimTy::Int(syntax::ast::IntTy::I16))),
-    ("i32",   Res::PrimTy(hir::PrimTy::Int(syntax::ast::IntTy::I32))),
-    ("i64",   Res::PrimTy(hir::PrimTy::Int(syntax::ast::IntTy::I64))),
-    ("i128",  Res::PrimTy(hir::PrimTy::Int(syntax::ast::IntTy::I128))),
-    ("isize", Res::PrimTy(hir::PrimTy::Int(syntax::ast::IntTy::Isize))),
-    ("f32",   Res::PrimTy(hir::PrimTy::Float(syntax::ast::FloatTy::F32))),
-    ("f64",   Res::PrimTy(hir::PrimTy::Float(syntax::ast::FloatTy::F64))),
-    ("str",   Res::PrimTy(hir::PrimTy::Str)),
-    ("bool",  Res::PrimTy(hir::PrimTy::Bool)),
-    ("char",  Res::PrimTy(hir::PrimTy::Char)),
-];
-
-fn is_primitive(path_str: &str, ns: Namespace) -> Option<Res> {
+fn is_primitive(path_str: &str, ns: Namespace) -> Option<PrimitiveType> {
     if ns == TypeNS {
-        PRIMITIVES.iter().find(|x| x.0 == path_str).map(|x| x.1)
+        PrimitiveType::from_str(path_str)
     } else {
         None
     }
 }
 
-fn primitive_impl(cx: &DocContext<'_>, path_str: &str) -> Option<DefId> {
-    let tcx = cx.tcx;
-    match path_str {
-        "u8" => tcx.lang_items().u8_impl(),
-        "u16" => tcx.lang_items().u16_impl(),
-        "u32" => tcx.lang_items().u32_impl(),
-        "u64" => tcx.lang_items().u64_impl(),
-        "u128" => tcx.lang_items().u128_impl(),
-        "usize" => tcx.lang_items().usize_impl(),
-        "i8" => tcx.lang_items().i8_impl(),
-        "i16" => tcx.lang_items().i16_impl(),
-        "i32" => tcx.lang_items().i32_impl(),
-        "i64" => tcx.lang_items().i64_impl(),
-        "i128" => tcx.lang_items().i128_impl(),
-        "isize" => tcx.lang_items().isize_impl(),
-        "f32" => tcx.lang_items().f32_impl(),
-        "f64" => tcx.lang_items().f64_impl(),
-        "str" => tcx.lang_items().str_impl(),
-        "bool" => tcx.lang_items().bool_impl(),
-        "char" => tcx.lang_items().char_impl(),
-        _ => None,
-    }
+/// Returns the inherent impls of a primitive type. Some types have several, since their methods
+/// are split between `core`, `alloc` and `std`.
+fn primitive_impls(cx: &DocContext<'_>, prim: PrimitiveType) -> Vec<DefId> {
+    let lang_items = cx.tcx.lang_items();
+    let impls = match prim {
+        PrimitiveType::U8 => vec![lang_items.u8_impl()],
+        PrimitiveType::U16 => vec![lang_items.u16_impl()],
+        PrimitiveType::U32 => vec![lang_items.u32_impl()],
+        PrimitiveType::U64 => vec![lang_items.u64_impl()],
+        PrimitiveType::U128 => vec![lang_items.u128_impl()],
+        PrimitiveType::Usize => vec![lang_items.usize_impl()],
+        PrimitiveType::I8 => vec![lang_items.i8_impl()],
+        PrimitiveType::I16 => vec![lang_items.i16_impl()],
+        PrimitiveType::I32 => vec![lang_items.i32_impl()],
+        PrimitiveType::I64 => vec![lang_items.i64_impl()],
+        PrimitiveType::I128 => vec![lang_items.i128_impl()],
+        PrimitiveType::Isize => vec![lang_items.isize_impl()],
+        PrimitiveType::F32 => vec![lang_items.f32_impl(), lang_items.f32_runtime_impl()],
+        PrimitiveType::F64 => vec![lang_items.f64_impl(), lang_items.f64_runtime_impl()],
+        PrimitiveType::Str => vec![lang_items.str_impl(), lang_items.str_alloc_impl()],
+        PrimitiveType::Bool => vec![lang_items.bool_impl()],
+        PrimitiveType::Char => vec![lang_items.char_impl()],
+        PrimitiveType::Slice => vec![
+            lang_items.slice_impl(),
+            lang_items.slice_u8_impl(),
+            lang_items.slice_alloc_impl(),
+            lang_items.slice_u8_alloc_impl(),
+        ],
+        PrimitiveType::RawPointer => {
+            vec![lang_items.const_ptr_impl(), lang_items.mut_ptr_impl()]
+        }
+        _ => vec![],
+    };
+    impls.into_iter().flatten().collect()
 }
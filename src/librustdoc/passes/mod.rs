@@ -308,6 +308,17 @@ impl DocFolder for ImportStripper {
     }
 }
 
+/// Counts the code examples found in a documentation string.
+crate struct Tests {
+    crate found_tests: usize,
+}
+
+impl crate::test::Tester for Tests {
+    fn add_test(&mut self, _: String, _: LangString, _: usize) {
+        self.found_tests += 1;
+    }
+}
+
 pub fn look_for_tests<'tcx>(
     cx: &DocContext<'tcx>,
     dox: &str,
@@ -322,16 +333,6 @@ pub fn look_for_tests<'tcx>(
         }
     };
 
-    struct Tests {
-        found_tests: usize,
-    }
-
-    impl crate::test::Tester for Tests {
-        fn add_test(&mut self, _: String, _: LangString, _: usize) {
-            self.found_tests += 1;
-        }
-    }
-
     let mut tests = Tests {
         found_tests: 0,
     };
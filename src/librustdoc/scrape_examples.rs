@@ -0,0 +1,394 @@
+//! Finds the places where the functions of a crate are called in its examples and tests, so that
+//! they can be shown as usage examples in the crate's documentation.
+//!
+//! This happens in two steps. First, rustdoc is run on each example with
+//! `--scrape-examples-output-path` and `--scrape-examples-target-crate`, which type-checks the
+//! example and writes the locations of the calls to functions of the target crates to a file.
+//! Then, those files are passed to the run that documents the target crate with
+//! `--with-examples`, and the calls are rendered on the pages of the called functions.
+//!
+//! Functions are identified by their `DefPathHash`, which is the same whether the function is
+//! seen from the crate that defines it or from a crate that depends on it.
+
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::mem;
+use std::path::PathBuf;
+
+use errors;
+use getopts;
+use rustc::hir::{self, intravisit};
+use rustc::hir::def_id::{DefId, LOCAL_CRATE};
+use rustc::session::{config, DiagnosticOutput};
+use rustc::ty::{self, TyCtxt};
+use rustc::util::common::ErrorReported;
+use rustc_data_structures::fx::FxHashMap;
+use rustc_interface::interface;
+use serialize::json::{Json, ToJson};
+use syntax::feature_gate::UnstableFeatures;
+use syntax::source_map::FileName;
+use syntax_pos::Span;
+
+use crate::config::Options;
+
+/// Options for the run that scrapes the calls from an example.
+#[derive(Clone, Debug)]
+pub struct ScrapeExamplesOptions {
+    /// The file to write the calls to.
+    pub output_path: PathBuf,
+    /// The crates whose functions we look for calls to.
+    pub target_crates: Vec<String>,
+    /// Whether the scraped crate is an integration test, which is compiled with `--test`.
+    pub scrape_tests: bool,
+}
+
+impl ScrapeExamplesOptions {
+    pub fn new(
+        matches: &getopts::Matches,
+        diag: &errors::Handler,
+    ) -> Result<Option<ScrapeExamplesOptions>, i32> {
+        let output_path = matches.opt_str("scrape-examples-output-path");
+        let target_crates = matches.opt_strs("scrape-examples-target-crate");
+        let scrape_tests = matches.opt_present("scrape-tests");
+        match (output_path, target_crates.is_empty(), scrape_tests) {
+            (Some(output_path), false, _) => Ok(Some(ScrapeExamplesOptions {
+                output_path: PathBuf::from(output_path),
+                target_crates,
+                scrape_tests,
+            })),
+            (Some(_), true, _) | (None, false, _) => {
+                diag.err("must use --scrape-examples-output-path and \
+                          --scrape-examples-target-crate together");
+                Err(1)
+            }
+            (None, true, true) => {
+                diag.err("must use --scrape-examples-output-path and \
+                          --scrape-examples-target-crate with --scrape-tests");
+                Err(1)
+            }
+            (None, true, false) => Ok(None),
+        }
+    }
+}
+
+/// A range of source code, both as bytes and as lines, which start at 1.
+#[derive(Clone, Debug)]
+pub struct SyntaxRange {
+    pub byte_span: (u32, u32),
+    pub line_span: (usize, usize),
+}
+
+/// A call to a function, and the item that contains it.
+#[derive(Clone, Debug)]
+pub struct CallLocation {
+    pub call_expr: SyntaxRange,
+    pub enclosing_item: SyntaxRange,
+}
+
+/// The calls to a function in one file.
+#[derive(Clone, Debug)]
+pub struct CallData {
+    pub locations: Vec<CallLocation>,
+    /// The path of the file, as it is shown to readers.
+    pub display_name: String,
+}
+
+/// The calls to a function, keyed by the path of the file that contains them.
+pub type FnCallLocations = BTreeMap<String, CallData>;
+
+/// The calls to all functions, keyed by the `DefPathHash` of the function.
+pub type AllCallLocations = BTreeMap<String, FnCallLocations>;
+
+impl ToJson for SyntaxRange {
+    fn to_json(&self) -> Json {
+        let mut obj = BTreeMap::new();
+        obj.insert("byte_span".to_string(), vec![self.byte_span.0, self.byte_span.1].to_json());
+        obj.insert("line_span".to_string(), vec![self.line_span.0, self.line_span.1].to_json());
+        Json::Object(obj)
+    }
+}
+
+impl ToJson for CallLocation {
+    fn to_json(&self) -> Json {
+        let mut obj = BTreeMap::new();
+        obj.insert("call_expr".to_string(), self.call_expr.to_json());
+        obj.insert("enclosing_item".to_string(), self.enclosing_item.to_json());
+        Json::Object(obj)
+    }
+}
+
+impl ToJson for CallData {
+    fn to_json(&self) -> Json {
+        let mut obj = BTreeMap::new();
+        obj.insert("locations".to_string(), self.locations.to_json());
+        obj.insert("display_name".to_string(), self.display_name.to_json());
+        Json::Object(obj)
+    }
+}
+
+fn pair(json: &Json) -> Option<(u64, u64)> {
+    match json.as_array()?.as_slice() {
+        [lo, hi] => Some((lo.as_u64()?, hi.as_u64()?)),
+        _ => None,
+    }
+}
+
+impl SyntaxRange {
+    fn from_json(json: &Json) -> Option<SyntaxRange> {
+        let (lo, hi) = pair(json.find("byte_span")?)?;
+        let (first, last) = pair(json.find("line_span")?)?;
+        Some(SyntaxRange {
+            byte_span: (lo as u32, hi as u32),
+            line_span: (first as usize, last as usize),
+        })
+    }
+}
+
+impl CallData {
+    fn from_json(json: &Json) -> Option<CallData> {
+        let locations = json.find("locations")?.as_array()?.iter().map(|location| {
+            Some(CallLocation {
+                call_expr: SyntaxRange::from_json(location.find("call_expr")?)?,
+                enclosing_item: SyntaxRange::from_json(location.find("enclosing_item")?)?,
+            })
+        }).collect::<Option<Vec<_>>>()?;
+        Some(CallData {
+            locations,
+            display_name: json.find("display_name")?.as_string()?.to_string(),
+        })
+    }
+}
+
+/// Reads the calls written by the scraping runs of rustdoc into the files in `paths`.
+pub fn load_call_locations(
+    paths: Vec<String>,
+    diag: &errors::Handler,
+) -> Result<AllCallLocations, i32> {
+    let mut all_calls = AllCallLocations::new();
+
+    for path in paths {
+        let json = match fs::read_to_string(&path) {
+            Ok(contents) => match Json::from_str(&contents) {
+                Ok(json) => json,
+                Err(e) => {
+                    diag.err(&format!("failed to parse examples from `{}`: {}", path, e));
+                    return Err(1);
+                }
+            },
+            Err(e) => {
+                diag.err(&format!("failed to read examples from `{}`: {}", path, e));
+                return Err(1);
+            }
+        };
+
+        let calls = json.as_object().and_then(|functions| {
+            functions.iter().map(|(function, files)| {
+                let files = files.as_object()?.iter().map(|(file, calls)| {
+                    Some((file.clone(), CallData::from_json(calls)?))
+                }).collect::<Option<Vec<_>>>()?;
+                Some((function.clone(), files))
+            }).collect::<Option<Vec<_>>>()
+        });
+        let calls = match calls {
+            Some(calls) => calls,
+            None => {
+                diag.err(&format!("failed to parse examples from `{}`: unexpected format", path));
+                return Err(1);
+            }
+        };
+
+        for (function, files) in calls {
+            let fn_calls = all_calls.entry(function).or_default();
+            for (file, mut call_data) in files {
+                match fn_calls.get_mut(&file) {
+                    Some(existing) => existing.locations.append(&mut call_data.locations),
+                    None => {
+                        fn_calls.insert(file, call_data);
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(all_calls)
+}
+
+/// Looks up the local functions that the calls in `call_locations` refer to.
+pub fn resolve_call_locations(
+    tcx: TyCtxt<'_>,
+    call_locations: &AllCallLocations,
+) -> FxHashMap<DefId, FnCallLocations> {
+    if call_locations.is_empty() {
+        return FxHashMap::default();
+    }
+
+    let mut hashes = FxHashMap::default();
+    tcx.hir().definitions().def_path_table().add_def_path_hashes_to(LOCAL_CRATE, &mut hashes);
+    hashes.into_iter().filter_map(|(hash, def_id)| {
+        call_locations.get(&hash.0.to_hex()).map(|calls| (def_id, calls.clone()))
+    }).collect()
+}
+
+/// Type-checks the example or test given in `options` and writes the calls it makes to
+/// functions of the target crates into the output file.
+pub fn run(options: Options, diag: &errors::Handler) -> i32 {
+    let scrape_options = options.scrape_examples_options.clone()
+        .expect("scraping examples without scrape options");
+
+    let crate_types = if options.proc_macro_crate {
+        vec![config::CrateType::ProcMacro]
+    } else {
+        vec![config::CrateType::Rlib]
+    };
+
+    // Unlike the other modes of rustdoc, we need the bodies of functions, so we don't set
+    // `actually_rustdoc`.
+    let sessopts = config::Options {
+        maybe_sysroot: options.maybe_sysroot.clone(),
+        search_paths: options.libs.clone(),
+        crate_types,
+        cg: options.codegen_options.clone(),
+        externs: options.externs.clone(),
+        unstable_features: UnstableFeatures::from_environment(),
+        lint_cap: Some(::rustc::lint::Level::Allow),
+        test: scrape_options.scrape_tests,
+        debugging_opts: options.debugging_options.clone(),
+        error_format: options.error_format,
+        edition: options.edition,
+        target_triple: options.target.clone(),
+        ..config::Options::default()
+    };
+
+    let mut cfgs = options.cfgs.clone();
+    cfgs.push("rustdoc".to_owned());
+    let config = interface::Config {
+        opts: sessopts,
+        crate_cfg: config::parse_cfgspecs(cfgs),
+        input: config::Input::File(options.input.clone()),
+        input_path: Some(options.input.clone()),
+        output_file: None,
+        output_dir: None,
+        file_loader: None,
+        diagnostic_output: DiagnosticOutput::Default,
+        stderr: None,
+        crate_name: options.crate_name.clone(),
+        lint_caps: Default::default(),
+    };
+
+    let calls = interface::run_compiler_in_existing_thread_pool(config, |compiler| {
+        let mut global_ctxt = compiler.global_ctxt()?.take();
+        global_ctxt.enter(|tcx| -> Result<_, ErrorReported> {
+            tcx.analysis(LOCAL_CRATE)?;
+
+            let mut finder = FindCalls {
+                tcx,
+                target_crates: &scrape_options.target_crates,
+                tables: None,
+                calls: AllCallLocations::new(),
+            };
+            intravisit::walk_crate(&mut finder, tcx.hir().krate());
+            Ok(finder.calls)
+        })
+    });
+    let calls = match calls {
+        Ok(calls) => calls,
+        Err(ErrorReported) => return rustc_driver::EXIT_FAILURE,
+    };
+
+    let output_path = &scrape_options.output_path;
+    if let Err(e) = fs::write(output_path, calls.to_json().to_string()) {
+        diag.err(&format!("failed to write examples to `{}`: {}", output_path.display(), e));
+        return rustc_driver::EXIT_FAILURE;
+    }
+
+    rustc_driver::EXIT_SUCCESS
+}
+
+/// Visits the bodies of a crate and records the calls to functions of the target crates.
+struct FindCalls<'a, 'tcx> {
+    tcx: TyCtxt<'tcx>,
+    target_crates: &'a [String],
+    /// The type-check results of the body that is being visited.
+    tables: Option<&'tcx ty::TypeckTables<'tcx>>,
+    calls: AllCallLocations,
+}
+
+impl<'a, 'tcx> FindCalls<'a, 'tcx> {
+    fn syntax_range(&self, span: Span) -> SyntaxRange {
+        let source_map = self.tcx.sess.source_map();
+        let lo = source_map.lookup_byte_offset(span.lo());
+        let hi = source_map.lookup_byte_offset(span.hi());
+        SyntaxRange {
+            byte_span: (lo.pos.0, hi.pos.0),
+            line_span: (source_map.lookup_char_pos(span.lo()).line,
+                        source_map.lookup_char_pos(span.hi()).line),
+        }
+    }
+
+    fn record_call(&mut self, expr: &hir::Expr, callee: DefId) {
+        let tcx = self.tcx;
+        // Calls that come from macros aren't written in the example, so there is nothing to
+        // show.
+        if expr.span.from_expansion() {
+            return;
+        }
+        let crate_name = tcx.crate_name(callee.krate);
+        if !self.target_crates.iter().any(|name| *name == *crate_name.as_str()) {
+            return;
+        }
+
+        let enclosing_item = tcx.hir().span(tcx.hir().get_parent_item(expr.hir_id));
+        let path = match tcx.sess.source_map().span_to_filename(expr.span) {
+            FileName::Real(path) => path,
+            _ => return,
+        };
+        let display_name = env::current_dir().ok()
+            .and_then(|cur_dir| path.strip_prefix(cur_dir).ok().map(|path| path.to_owned()))
+            .unwrap_or_else(|| path.clone());
+
+        let location = CallLocation {
+            call_expr: self.syntax_range(expr.span),
+            enclosing_item: self.syntax_range(enclosing_item),
+        };
+        let hash = tcx.def_path_hash(callee).0.to_hex();
+        self.calls.entry(hash).or_default()
+            .entry(path.display().to_string())
+            .or_insert_with(|| CallData {
+                locations: Vec::new(),
+                display_name: display_name.display().to_string(),
+            })
+            .locations.push(location);
+    }
+}
+
+impl<'a, 'tcx> intravisit::Visitor<'tcx> for FindCalls<'a, 'tcx> {
+    fn nested_visit_map<'this>(&'this mut self) -> intravisit::NestedVisitorMap<'this, 'tcx> {
+        intravisit::NestedVisitorMap::All(self.tcx.hir())
+    }
+
+    fn visit_nested_body(&mut self, id: hir::BodyId) {
+        let old_tables = mem::replace(&mut self.tables, Some(self.tcx.body_tables(id)));
+        let body = self.tcx.hir().body(id);
+        self.visit_body(body);
+        self.tables = old_tables;
+    }
+
+    fn visit_expr(&mut self, expr: &'tcx hir::Expr) {
+        if let Some(tables) = self.tables {
+            let callee = match expr.kind {
+                hir::ExprKind::Call(ref func, _) => match tables.node_type(func.hir_id).kind {
+                    ty::FnDef(def_id, _) => Some(def_id),
+                    _ => None,
+                },
+                hir::ExprKind::MethodCall(..) => tables.type_dependent_def_id(expr.hir_id),
+                _ => None,
+            };
+            if let Some(callee) = callee {
+                self.record_call(expr, callee);
+            }
+        }
+
+        intravisit::walk_expr(self, expr);
+    }
+}
@@ -1,3 +1,4 @@
+use rustc_data_structures::fx::FxHashSet;
 use rustc_data_structures::sync::Lrc;
 use rustc_interface::interface;
 use rustc_target::spec::TargetTriple;
@@ -12,10 +13,12 @@ use syntax::edition::Edition;
 use syntax::feature_gate::UnstableFeatures;
 use std::env;
 use std::io::{self, Write};
+use std::mem;
 use std::panic;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process::{self, Command, Stdio};
 use std::str;
+use std::sync::Arc;
 use syntax::symbol::sym;
 use syntax_pos::{BytePos, DUMMY_SP, Pos, Span, FileName};
 use tempfile::Builder as TempFileBuilder;
@@ -113,6 +116,7 @@ pub fn run(options: Options) -> i32 {
             });
         });
 
+        collector.merge_tests();
         Ok(collector.tests)
     }).expect("compiler aborted in rustdoc!");
 
@@ -179,6 +183,48 @@ enum TestFailure {
     UnexpectedRunPass,
 }
 
+/// The directory a doctest is compiled in.
+enum DirState {
+    Temp(tempfile::TempDir),
+    Perm(PathBuf),
+}
+
+impl DirState {
+    fn path(&self) -> &std::path::Path {
+        match self {
+            DirState::Temp(t) => t.path(),
+            DirState::Perm(p) => p.as_path(),
+        }
+    }
+}
+
+/// Creates the command that compiles a doctest, with the flags shared by all doctests.
+fn rustc_command(options: &Options, edition: Edition, target: &TargetTriple) -> Command {
+    let rustc_binary = options.test_builder.as_ref().map(|v| &**v).unwrap_or_else(|| {
+        rustc_interface::util::rustc_path().expect("found rustc")
+    });
+    let mut compiler = Command::new(&rustc_binary);
+    for cfg in &options.cfgs {
+        compiler.arg("--cfg").arg(&cfg);
+    }
+    if let Some(ref sysroot) = options.maybe_sysroot {
+        compiler.arg("--sysroot").arg(sysroot);
+    }
+    compiler.arg("--edition").arg(&edition.to_string());
+    for lib_str in &options.lib_strs {
+        compiler.arg("-L").arg(&lib_str);
+    }
+    for extern_str in &options.extern_strs {
+        compiler.arg("--extern").arg(&extern_str);
+    }
+    compiler.arg("-Ccodegen-units=1");
+    for codegen_options_str in &options.codegen_options_strs {
+        compiler.arg("-C").arg(&codegen_options_str);
+    }
+    compiler.arg("--target").arg(target.to_string());
+    compiler
+}
+
 fn run_test(
     test: &str,
     cratename: &str,
@@ -215,21 +261,7 @@ fn run_test(
         _ => PathBuf::from(r"doctest.rs"),
     };
 
-    enum DirState {
-        Temp(tempfile::TempDir),
-        Perm(PathBuf),
-    }
-
-    impl DirState {
-        fn path(&self) -> &std::path::Path {
-            match self {
-                DirState::Temp(t) => t.path(),
-                DirState::Perm(p) => p.as_path(),
-            }
-        }
-    }
-
-    let outdir = if let Some(mut path) = options.persist_doctests {
+    let outdir = if let Some(mut path) = options.persist_doctests.clone() {
         path.push(format!("{}_{}",
             filename
                 .to_string()
@@ -251,18 +283,8 @@ fn run_test(
     };
     let output_file = outdir.path().join("rust_out");
 
-    let rustc_binary = options.test_builder.as_ref().map(|v| &**v).unwrap_or_else(|| {
-        rustc_interface::util::rustc_path().expect("found rustc")
-    });
-    let mut compiler = Command::new(&rustc_binary);
+    let mut compiler = rustc_command(&options, edition, &target);
     compiler.arg("--crate-type").arg("bin");
-    for cfg in &options.cfgs {
-        compiler.arg("--cfg").arg(&cfg);
-    }
-    if let Some(sysroot) = options.maybe_sysroot {
-        compiler.arg("--sysroot").arg(sysroot);
-    }
-    compiler.arg("--edition").arg(&edition.to_string());
     compiler.env("UNSTABLE_RUSTDOC_TEST_PATH", path);
     compiler.env("UNSTABLE_RUSTDOC_TEST_LINE",
                  format!("{}", line as isize - line_offset as isize));
@@ -270,20 +292,9 @@ fn run_test(
     if as_test_harness {
         compiler.arg("--test");
     }
-    for lib_str in &options.lib_strs {
-        compiler.arg("-L").arg(&lib_str);
-    }
-    for extern_str in &options.extern_strs {
-        compiler.arg("--extern").arg(&extern_str);
-    }
-    compiler.arg("-Ccodegen-units=1");
-    for codegen_options_str in &options.codegen_options_strs {
-        compiler.arg("-C").arg(&codegen_options_str);
-    }
     if no_run {
         compiler.arg("--emit=metadata");
     }
-    compiler.arg("--target").arg(target.to_string());
 
     compiler.arg("-");
     compiler.stdin(Stdio::piped());
@@ -329,6 +340,18 @@ fn run_test(
     }
 
     // Run the code!
+    run_binary(&output_file, &[], runtool, runtool_args, should_panic)
+}
+
+/// Runs a compiled doctest with the arguments `args`, and checks that it exits successfully, or
+/// unsuccessfully if it should panic.
+fn run_binary(
+    output_file: &Path,
+    args: &[&str],
+    runtool: Option<String>,
+    runtool_args: Vec<String>,
+    should_panic: bool,
+) -> Result<(), TestFailure> {
     let mut cmd;
 
     if let Some(tool) = runtool {
@@ -338,6 +361,7 @@ fn run_test(
     } else {
         cmd = Command::new(output_file);
     }
+    cmd.args(args);
 
     match cmd.output() {
         Err(e) => return Err(TestFailure::ExecutionError(e)),
@@ -631,6 +655,17 @@ pub struct Collector {
     position: Span,
     source_map: Option<Lrc<SourceMap>>,
     filename: Option<PathBuf>,
+    /// The tests that `merge_tests` will compile into a single binary.
+    merged: Vec<MergeableTest>,
+}
+
+/// A doctest that can be compiled together with other doctests.
+struct MergeableTest {
+    name: String,
+    test: String,
+    config: LangString,
+    filename: FileName,
+    line: usize,
 }
 
 impl Collector {
@@ -648,6 +683,7 @@ impl Collector {
             position: DUMMY_SP,
             source_map,
             filename,
+            merged: Vec::new(),
         }
     }
 
@@ -676,32 +712,40 @@ impl Collector {
             FileName::Custom("input".to_owned())
         }
     }
-}
 
-impl Tester for Collector {
-    fn add_test(&mut self, test: String, config: LangString, line: usize) {
-        let filename = self.get_filename();
-        let name = self.generate_name(line, &filename);
+    fn is_ignored(&self, config: &LangString) -> bool {
+        match config.ignore {
+            Ignore::All => true,
+            Ignore::None => false,
+            Ignore::Some(ref ignores) => {
+                let target_str = self.options.target.to_string();
+                ignores.iter().any(|s| target_str.contains(s))
+            }
+        }
+    }
+
+    /// Creates a test that compiles and runs the doctest `test` on its own.
+    fn separate_test(
+        &self,
+        name: String,
+        test: String,
+        config: LangString,
+        filename: FileName,
+        line: usize,
+    ) -> testing::TestDescAndFn {
         let cratename = self.cratename.to_string();
         let opts = self.opts.clone();
-        let edition = config.edition.unwrap_or(self.options.edition.clone());
+        let edition = config.edition.unwrap_or(self.options.edition);
         let options = self.options.clone();
         let runtool = self.options.runtool.clone();
         let runtool_args = self.options.runtool_args.clone();
         let target = self.options.target.clone();
-        let target_str = target.to_string();
 
         debug!("creating test {}: {}", name, test);
-        self.tests.push(testing::TestDescAndFn {
+        testing::TestDescAndFn {
             desc: testing::TestDesc {
-                name: testing::DynTestName(name.clone()),
-                ignore: match config.ignore {
-                    Ignore::All => true,
-                    Ignore::None => false,
-                    Ignore::Some(ref ignores) => {
-                        ignores.iter().any(|s| target_str.contains(s))
-                    },
-                },
+                name: testing::DynTestName(name),
+                ignore: self.is_ignored(&config),
                 // compiler failures are test failures
                 should_panic: testing::ShouldPanic::No,
                 allow_fail: config.allow_fail,
@@ -727,64 +771,312 @@ impl Tester for Collector {
                 );
 
                 if let Err(err) = res {
-                    match err {
-                        TestFailure::CompileError => {
-                            eprint!("Couldn't compile the test.");
-                        }
-                        TestFailure::UnexpectedCompilePass => {
-                            eprint!("Test compiled successfully, but it's marked `compile_fail`.");
-                        }
-                        TestFailure::UnexpectedRunPass => {
-                            eprint!("Test executable succeeded, but it's marked `should_panic`.");
-                        }
-                        TestFailure::MissingErrorCodes(codes) => {
-                            eprint!("Some expected error codes were not found: {:?}", codes);
-                        }
-                        TestFailure::ExecutionError(err) => {
-                            eprint!("Couldn't run the test: {}", err);
-                            if err.kind() == io::ErrorKind::PermissionDenied {
-                                eprint!(" - maybe your tempdir is mounted with noexec?");
-                            }
-                        }
-                        TestFailure::ExecutionFailure(out) => {
-                            let reason = if let Some(code) = out.status.code() {
-                                format!("exit code {}", code)
-                            } else {
-                                String::from("terminated by signal")
-                            };
-
-                            eprintln!("Test executable failed ({}).", reason);
-
-                            // FIXME(#12309): An unfortunate side-effect of capturing the test
-                            // executable's output is that the relative ordering between the test's
-                            // stdout and stderr is lost. However, this is better than the
-                            // alternative: if the test executable inherited the parent's I/O
-                            // handles the output wouldn't be captured at all, even on success.
-                            //
-                            // The ordering could be preserved if the test process' stderr was
-                            // redirected to stdout, but that functionality does not exist in the
-                            // standard library, so it may not be portable enough.
-                            let stdout = str::from_utf8(&out.stdout).unwrap_or_default();
-                            let stderr = str::from_utf8(&out.stderr).unwrap_or_default();
-
-                            if !stdout.is_empty() || !stderr.is_empty() {
-                                eprintln!();
-
-                                if !stdout.is_empty() {
-                                    eprintln!("stdout:\n{}", stdout);
-                                }
-
-                                if !stderr.is_empty() {
-                                    eprintln!("stderr:\n{}", stderr);
-                                }
+                    report_failure(err);
+                }
+            }),
+        }
+    }
+
+    /// Compiles the doctests that `add_test` set aside into a single test crate, with one module
+    /// per doctest, and adds a test for each of them that runs its module's test in that binary.
+    ///
+    /// If the merged crate fails to build, for example because a doctest only compiles as the root
+    /// of its own crate, all of its doctests are compiled and run separately instead.
+    ///
+    /// Only the doctests selected by the filters of the test arguments are merged. The others are
+    /// added as separate tests, which are only compiled if they are run.
+    pub fn merge_tests(&mut self) {
+        let merged = mem::replace(&mut self.merged, Vec::new());
+        let (merged, filtered_out) = self.partition_filtered(merged);
+        for test in filtered_out {
+            let test = self.separate_test(test.name, test.test, test.config, test.filename,
+                                          test.line);
+            self.tests.push(test);
+        }
+        if merged.is_empty() {
+            return;
+        }
+
+        let mut module_opts = self.opts.clone();
+        // The crate-level attributes are added to the root of the merged crate instead.
+        module_opts.attrs = Vec::new();
+        module_opts.display_warnings = true;
+
+        let edition = self.options.edition;
+        let cratename = &self.cratename;
+        let mut modules = Vec::new();
+        let mut separate = Vec::new();
+        for test in merged {
+            let source = panic::catch_unwind(|| {
+                make_test(&test.test, Some(cratename), false, &module_opts, edition).0
+            });
+            match source {
+                Ok(source) => modules.push((test, source)),
+                // The test doesn't parse, so let it report the error on its own.
+                Err(cause) if cause.is::<errors::FatalErrorMarker>() => separate.push(test),
+                Err(cause) => panic::resume_unwind(cause),
+            }
+        }
+
+        let crate_source = make_merged_test(
+            modules.iter().map(|&(_, ref source)| &**source),
+            &self.opts,
+        );
+        match compile_merged_test(&crate_source, &self.options) {
+            Ok(outdir) => {
+                let outdir = Arc::new(outdir);
+                for (idx, (test, _)) in modules.into_iter().enumerate() {
+                    let outdir = outdir.clone();
+                    let runtool = self.options.runtool.clone();
+                    let runtool_args = self.options.runtool_args.clone();
+                    let should_panic = test.config.should_panic;
+
+                    debug!("creating merged test {}: {}", test.name, test.test);
+                    self.tests.push(testing::TestDescAndFn {
+                        desc: testing::TestDesc {
+                            name: testing::DynTestName(test.name),
+                            ignore: false,
+                            should_panic: testing::ShouldPanic::No,
+                            allow_fail: test.config.allow_fail,
+                            test_type: testing::TestType::DocTest,
+                        },
+                        testfn: testing::DynTestFn(box move || {
+                            let output_file = outdir.path().join("rust_out");
+                            let name = format!("__doctest_{}::__doctest", idx);
+                            let args = [&*name, "--exact", "--test-threads=1"];
+                            let res = run_binary(
+                                &output_file,
+                                &args,
+                                runtool,
+                                runtool_args,
+                                should_panic,
+                            );
+
+                            if let Err(err) = res {
+                                report_failure(err);
                             }
-                        }
-                    }
+                        }),
+                    });
+                }
+            }
+            Err(()) => {
+                separate.extend(modules.into_iter().map(|(test, _)| test));
+            }
+        }
+
+        for test in separate {
+            let test = self.separate_test(test.name, test.test, test.config, test.filename,
+                                          test.line);
+            self.tests.push(test);
+        }
+    }
+
+    /// Splits `tests` into the ones that the filters of the test arguments select and the others,
+    /// the same way as libtest will.
+    fn partition_filtered(&self, tests: Vec<MergeableTest>)
+        -> (Vec<MergeableTest>, Vec<MergeableTest>)
+    {
+        let mut args = vec!["rustdoctest".to_string()];
+        args.extend(self.options.test_args.iter().cloned());
+        let opts = match testing::parse_opts(&args) {
+            Some(Ok(opts)) => opts,
+            // Nothing will be run, and `test_main` reports why.
+            _ => return (Vec::new(), tests),
+        };
+
+        let descs = tests.iter().map(|test| testing::TestDescAndFn {
+            desc: testing::TestDesc {
+                name: testing::DynTestName(test.name.clone()),
+                ignore: false,
+                should_panic: testing::ShouldPanic::No,
+                allow_fail: test.config.allow_fail,
+                test_type: testing::TestType::DocTest,
+            },
+            testfn: testing::DynTestFn(box || {}),
+        }).collect();
+        let selected = testing::filter_tests(&opts, descs).into_iter()
+            .map(|test| test.desc.name.to_string())
+            .collect::<FxHashSet<_>>();
+
+        tests.into_iter().partition(|test| selected.contains(&test.name))
+    }
+}
+
+/// Returns whether the doctest `test` can be compiled as a module of a merged test crate of the
+/// given edition.
+///
+/// Doctests are merged unless they need to be the root of their own crate: they have crate
+/// attributes, their own `fn main`, or `#[macro_use] extern crate` items, or they use their own
+/// test harness or are only meant to be compiled.
+fn can_merge(test: &str, config: &LangString, edition: Edition, crate_edition: Edition) -> bool {
+    let (crate_attrs, _, crates) = partition_source(test);
+
+    edition == crate_edition &&
+        !config.compile_fail &&
+        !config.no_run &&
+        !config.test_harness &&
+        !crate_attrs.lines().any(|line| line.trim().starts_with("#![")) &&
+        !crates.contains("#[macro_use]") &&
+        // Exported macros would conflict between doctests, since they all end up at the root
+        // of the merged crate.
+        !test.contains("macro_export") &&
+        !test.contains("fn main")
+}
+
+/// Builds the source of a test crate with one module for each of the doctests in `tests`, which
+/// are the output of `make_test`. Each module contains a `#[test]` function that runs the
+/// doctest's `main`.
+///
+/// The `extern crate` items of the doctests are hoisted to the root of the merged crate, since on
+/// the 2015 edition paths in `use` items are resolved from the crate root.
+fn make_merged_test<'a>(tests: impl Iterator<Item = &'a str>, opts: &TestOptions) -> String {
+    let mut prog = String::new();
+
+    if opts.attrs.is_empty() && !opts.display_warnings {
+        prog.push_str("#![allow(unused)]\n");
+    }
+    for attr in &opts.attrs {
+        prog.push_str(&format!("#![{}]\n", attr));
+    }
+
+    let mut extern_crates = Vec::new();
+    let mut modules = String::new();
+    for (idx, test) in tests.enumerate() {
+        let (before, everything_else, crates) = partition_source(test);
+        for line in crates.lines() {
+            let line = line.trim();
+            if line.starts_with("extern crate") && !extern_crates.iter().any(|c| c == line) {
+                extern_crates.push(line.to_string());
+            }
+        }
+
+        modules.push_str(&format!("mod __doctest_{} {{\n{}{}", idx, before, everything_else));
+        modules.push_str("#[test]\nfn __doctest() { main() }\n}\n");
+    }
+
+    for krate in extern_crates {
+        prog.push_str(&krate);
+        prog.push_str("\n");
+    }
+    prog.push_str(&modules);
+
+    debug!("merged doctests:\n{}", prog);
+
+    prog
+}
+
+/// Compiles the merged test crate `source` into `rust_out` in the returned directory.
+fn compile_merged_test(source: &str, options: &Options) -> Result<DirState, ()> {
+    let outdir = if let Some(ref path) = options.persist_doctests {
+        let path = path.join("merged_doctests");
+        std::fs::create_dir_all(&path)
+            .expect("Couldn't create directory for doctest executables");
+
+        DirState::Perm(path)
+    } else {
+        DirState::Temp(TempFileBuilder::new()
+                        .prefix("rustdoctest")
+                        .tempdir()
+                        .expect("rustdoc needs a tempdir"))
+    };
+
+    let mut compiler = rustc_command(options, options.edition, &options.target);
+    compiler.arg("--test");
+    compiler.arg("--crate-name").arg("rust_out");
+    compiler.arg("-o").arg(outdir.path().join("rust_out"));
+    compiler.arg("-");
+    compiler.stdin(Stdio::piped());
+    compiler.stderr(Stdio::piped());
+
+    let mut child = compiler.spawn().expect("Failed to spawn rustc process");
+    {
+        let stdin = child.stdin.as_mut().expect("Failed to open stdin");
+        stdin.write_all(source.as_bytes()).expect("could write out test sources");
+    }
+    let output = child.wait_with_output().expect("Failed to read stdout");
+
+    if output.status.success() {
+        Ok(outdir)
+    } else {
+        debug!("merged doctests failed to compile:\n{}", String::from_utf8_lossy(&output.stderr));
+        Err(())
+    }
+}
+
+/// Prints why a doctest failed and fails the test.
+fn report_failure(err: TestFailure) -> ! {
+    match err {
+        TestFailure::CompileError => {
+            eprint!("Couldn't compile the test.");
+        }
+        TestFailure::UnexpectedCompilePass => {
+            eprint!("Test compiled successfully, but it's marked `compile_fail`.");
+        }
+        TestFailure::UnexpectedRunPass => {
+            eprint!("Test executable succeeded, but it's marked `should_panic`.");
+        }
+        TestFailure::MissingErrorCodes(codes) => {
+            eprint!("Some expected error codes were not found: {:?}", codes);
+        }
+        TestFailure::ExecutionError(err) => {
+            eprint!("Couldn't run the test: {}", err);
+            if err.kind() == io::ErrorKind::PermissionDenied {
+                eprint!(" - maybe your tempdir is mounted with noexec?");
+            }
+        }
+        TestFailure::ExecutionFailure(out) => {
+            let reason = if let Some(code) = out.status.code() {
+                format!("exit code {}", code)
+            } else {
+                String::from("terminated by signal")
+            };
+
+            eprintln!("Test executable failed ({}).", reason);
+
+            // FIXME(#12309): An unfortunate side-effect of capturing the test
+            // executable's output is that the relative ordering between the test's
+            // stdout and stderr is lost. However, this is better than the
+            // alternative: if the test executable inherited the parent's I/O
+            // handles the output wouldn't be captured at all, even on success.
+            //
+            // The ordering could be preserved if the test process' stderr was
+            // redirected to stdout, but that functionality does not exist in the
+            // standard library, so it may not be portable enough.
+            let stdout = str::from_utf8(&out.stdout).unwrap_or_default();
+            let stderr = str::from_utf8(&out.stderr).unwrap_or_default();
 
-                    panic::resume_unwind(box ());
+            if !stdout.is_empty() || !stderr.is_empty() {
+                eprintln!();
+
+                if !stdout.is_empty() {
+                    eprintln!("stdout:\n{}", stdout);
                 }
-            }),
-        });
+
+                if !stderr.is_empty() {
+                    eprintln!("stderr:\n{}", stderr);
+                }
+            }
+        }
+    }
+
+    panic::resume_unwind(box ());
+}
+
+impl Tester for Collector {
+    fn add_test(&mut self, test: String, config: LangString, line: usize) {
+        let filename = self.get_filename();
+        let name = self.generate_name(line, &filename);
+        let edition = config.edition.unwrap_or(self.options.edition);
+
+        if self.options.merge_doctests && !self.is_ignored(&config) &&
+            can_merge(&test, &config, edition, self.options.edition)
+        {
+            debug!("merging test {}: {}", name, test);
+            self.merged.push(MergeableTest { name, test, config, filename, line });
+        } else {
+            let test = self.separate_test(name, test, config, filename, line);
+            self.tests.push(test);
+        }
     }
 
     fn get_line(&self) -> usize {
@@ -1,5 +1,6 @@
-use super::{TestOptions, make_test};
-use syntax::edition::DEFAULT_EDITION;
+use super::{TestOptions, can_merge, make_merged_test, make_test};
+use crate::html::markdown::LangString;
+use syntax::edition::{Edition, DEFAULT_EDITION};
 
 #[test]
 fn make_test_basic() {
@@ -297,3 +298,127 @@ test_wrapper! {
     let output = make_test(input, Some("my_crate"), false, &opts, DEFAULT_EDITION);
     assert_eq!(output, (expected, 1));
 }
+
+#[test]
+fn can_merge_basic() {
+    let config = LangString::all_false();
+    let input =
+"use std::collections::HashMap;
+let mut map = HashMap::new();
+map.insert(1, 2);";
+    assert!(can_merge(input, &config, DEFAULT_EDITION, DEFAULT_EDITION));
+}
+
+#[test]
+fn can_merge_rejects_crate_roots() {
+    // Crate attributes, a `main` function and `#[macro_use] extern crate` only work at the root
+    // of a crate.
+    let config = LangString::all_false();
+    assert!(!can_merge("#![feature(box_syntax)]\nlet x = box 1;", &config,
+                       DEFAULT_EDITION, DEFAULT_EDITION));
+    assert!(!can_merge("fn main() {}", &config, DEFAULT_EDITION, DEFAULT_EDITION));
+    assert!(!can_merge("#[macro_use] extern crate foo;\nbar!();", &config,
+                       DEFAULT_EDITION, DEFAULT_EDITION));
+    assert!(!can_merge("#[macro_export]\nmacro_rules! foo { () => {} }", &config,
+                       DEFAULT_EDITION, DEFAULT_EDITION));
+
+    // Plain `extern crate` items and comments before the code are fine.
+    assert!(can_merge("// a comment\nextern crate foo;\nfoo::bar();", &config,
+                      DEFAULT_EDITION, DEFAULT_EDITION));
+}
+
+#[test]
+fn can_merge_rejects_configs() {
+    let input = "assert!(true);";
+    let mut config = LangString::all_false();
+    config.compile_fail = true;
+    assert!(!can_merge(input, &config, DEFAULT_EDITION, DEFAULT_EDITION));
+
+    let mut config = LangString::all_false();
+    config.no_run = true;
+    assert!(!can_merge(input, &config, DEFAULT_EDITION, DEFAULT_EDITION));
+
+    let mut config = LangString::all_false();
+    config.test_harness = true;
+    assert!(!can_merge(input, &config, DEFAULT_EDITION, DEFAULT_EDITION));
+
+    let config = LangString::all_false();
+    assert!(!can_merge(input, &config, Edition::Edition2018, Edition::Edition2015));
+
+    // `should_panic` tests are run in their own process, so they can be merged.
+    let mut config = LangString::all_false();
+    config.should_panic = true;
+    assert!(can_merge(input, &config, DEFAULT_EDITION, DEFAULT_EDITION));
+}
+
+#[test]
+fn make_merged_test_basic() {
+    let opts = TestOptions::default();
+    let tests = ["fn main() {\nassert!(true);\n}", "fn main() {\nassert!(false);\n}"];
+    let expected =
+"#![allow(unused)]
+mod __doctest_0 {
+fn main() {
+assert!(true);
+}
+#[test]
+fn __doctest() { main() }
+}
+mod __doctest_1 {
+fn main() {
+assert!(false);
+}
+#[test]
+fn __doctest() { main() }
+}
+".to_string();
+    let output = make_merged_test(tests.iter().cloned(), &opts);
+    assert_eq!(output, expected);
+}
+
+#[test]
+fn make_merged_test_crate_attrs() {
+    // Attributes from `#![doc(test(attr(...)))]` go to the root of the merged crate.
+    let mut opts = TestOptions::default();
+    opts.attrs.push("deny(warnings)".to_string());
+    let tests = ["fn main() {\n}"];
+    let expected =
+"#![deny(warnings)]
+mod __doctest_0 {
+fn main() {
+}
+#[test]
+fn __doctest() { main() }
+}
+".to_string();
+    let output = make_merged_test(tests.iter().cloned(), &opts);
+    assert_eq!(output, expected);
+}
+
+#[test]
+fn make_merged_test_extern_crates() {
+    // `extern crate` items go to the root of the merged crate, once each.
+    let opts = TestOptions::default();
+    let tests = ["extern crate foo;\nfn main() {\nuse foo::Foo;\n}",
+                 "extern crate foo;\nextern crate bar;\nfn main() {\n}"];
+    let expected =
+"#![allow(unused)]
+extern crate foo;
+extern crate bar;
+mod __doctest_0 {
+fn main() {
+use foo::Foo;
+}
+#[test]
+fn __doctest() { main() }
+}
+mod __doctest_1 {
+fn main() {
+}
+#[test]
+fn __doctest() { main() }
+}
+".to_string();
+    let output = make_merged_test(tests.iter().cloned(), &opts);
+    assert_eq!(output, expected);
+}
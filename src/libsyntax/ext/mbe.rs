@@ -7,9 +7,11 @@ crate mod transcribe;
 crate mod macro_check;
 crate mod macro_parser;
 crate mod macro_rules;
+crate mod metavar_expr;
 crate mod quoted;
 
 use crate::ast;
+use crate::ext::mbe::metavar_expr::MetaVarExpr;
 use crate::parse::token::{self, Token, TokenKind};
 use crate::tokenstream::{DelimSpan};
 
@@ -99,6 +101,8 @@ enum TokenTree {
         ast::Ident, /* name to bind */
         ast::Ident, /* kind of nonterminal */
     ),
+    /// e.g., `${count(var)}`. This is only used in the right hand side of MBE macros.
+    MetaVarExpr(DelimSpan, MetaVarExpr),
 }
 
 impl TokenTree {
@@ -156,7 +160,9 @@ impl TokenTree {
             TokenTree::Token(Token { span, .. })
             | TokenTree::MetaVar(span, _)
             | TokenTree::MetaVarDecl(span, _, _) => span,
-            TokenTree::Delimited(span, _) | TokenTree::Sequence(span, _) => span.entire(),
+            TokenTree::Delimited(span, _)
+            | TokenTree::MetaVarExpr(span, _)
+            | TokenTree::Sequence(span, _) => span.entire(),
         }
     }
 
@@ -106,6 +106,7 @@
 //! bound.
 use crate::ast::NodeId;
 use crate::early_buffered_lints::BufferedEarlyLintId;
+use crate::ext::mbe::metavar_expr::MetaVarExpr;
 use crate::ext::mbe::{KleeneToken, TokenTree};
 use crate::parse::token::TokenKind;
 use crate::parse::token::{DelimToken, Token};
@@ -276,6 +277,14 @@ fn check_binders(
                 binders.insert(name, BinderInfo { span, ops: ops.into() });
             }
         }
+        // Like `MetaVar`, this can only happen when checking a nested macro, and it refers to the
+        // meta-variables of an outer macro.
+        TokenTree::MetaVarExpr(dl, _) => {
+            if macros.is_empty() {
+                sess.span_diagnostic.span_bug(dl.entire(), "unexpected MetaVarExpr in lhs");
+            }
+            check_occurrences(sess, node_id, lhs, macros, binders, ops, valid);
+        }
         TokenTree::Delimited(_, ref del) => {
             for tt in &del.tts {
                 check_binders(sess, node_id, tt, macros, binders, ops, valid);
@@ -332,6 +341,9 @@ fn check_occurrences(
         TokenTree::MetaVar(span, name) => {
             check_ops_is_prefix(sess, node_id, macros, binders, ops, span, name);
         }
+        TokenTree::MetaVarExpr(dl, ref expr) => {
+            check_metavar_expr(sess, node_id, macros, binders, ops, dl.entire(), expr, valid);
+        }
         TokenTree::Delimited(_, ref del) => {
             check_nested_occurrences(sess, node_id, &del.tts, macros, binders, ops, valid);
         }
@@ -575,6 +587,95 @@ fn check_ops_is_prefix(
     buffer_lint(sess, span.into(), node_id, &format!("unknown macro variable `{}`", name));
 }
 
+/// Checks that a meta-variable expression is valid at its position in a RHS, and sets `valid` to
+/// false in case of errors.
+///
+/// Unlike plain meta-variable occurrences, misuses of meta-variable expressions are errors rather
+/// than lints, since they would fail during transcription anyway.
+///
+/// Arguments:
+/// - `sess` is used to emit diagnostics and lints
+/// - `node_id` is used to emit lints
+/// - `macros` is the stack of possible outer macros
+/// - `binders` contains the binders of the associated LHS
+/// - `ops` is the stack of Kleene operators from the RHS
+/// - `span` is the span of the meta-variable expression to check
+/// - `expr` is the meta-variable expression to check
+/// - `valid` is set in case of errors
+fn check_metavar_expr(
+    sess: &ParseSess,
+    node_id: NodeId,
+    macros: &Stack<'_, MacroState<'_>>,
+    binders: &Binders,
+    ops: &Stack<'_, KleeneToken>,
+    span: Span,
+    expr: &MetaVarExpr,
+    valid: &mut bool,
+) {
+    let mut error = |msg: &str| {
+        sess.span_diagnostic.span_err(span, msg);
+        *valid = false;
+    };
+    let macros = macros.push(MacroState { binders, ops: ops.into() });
+    // The requested depth and the number of repetitions it may reach.
+    let (depth, limit) = match *expr {
+        MetaVarExpr::Ignore(name) => {
+            if get_binder_info(&macros, binders, name).is_none() {
+                error(&format!("unknown macro variable `{}`", name));
+            }
+            return;
+        }
+        MetaVarExpr::Index(depth) | MetaVarExpr::Length(depth) => {
+            let repetitions: usize = (&macros).map(|state| state.ops.len()).sum();
+            if repetitions == 0 {
+                error(&format!(
+                    "meta-variable expression `{}` must be placed inside a repetition",
+                    expr.name(),
+                ));
+                return;
+            }
+            (depth, repetitions)
+        }
+        MetaVarExpr::Count(name, depth) => {
+            // The number of repetitions between the binder of `name` and this expression.
+            let mut repetitions = 0;
+            let mut binder = None;
+            for state in &macros {
+                repetitions += state.ops.len();
+                if let Some(info) = state.binders.get(&name) {
+                    binder = Some(info);
+                    break;
+                }
+            }
+            let binder = match binder {
+                Some(binder) => binder,
+                None => {
+                    error(&format!("unknown macro variable `{}`", name));
+                    return;
+                }
+            };
+            if binder.ops.len() <= repetitions {
+                error(&format!(
+                    "`count` can not be placed inside the inner-most repetition of `{}`",
+                    name,
+                ));
+                return;
+            }
+            match depth {
+                Some(depth) => (depth, binder.ops.len() - repetitions),
+                None => return,
+            }
+        }
+    };
+    if depth >= limit {
+        error(&format!(
+            "depth parameter on meta-variable expression `{}` must be less than {}",
+            expr.name(),
+            limit,
+        ));
+    }
+}
+
 /// Returns whether `binder_ops` is a prefix of `occurrence_ops`.
 ///
 /// The stack of Kleene operators of a meta-variable occurrence just needs to have the stack of
@@ -289,6 +289,7 @@ pub(super) fn count_names(ms: &[TokenTree]) -> usize {
             TokenTree::Delimited(_, ref delim) => count_names(&delim.tts),
             TokenTree::MetaVar(..) => 0,
             TokenTree::MetaVarDecl(..) => 1,
+            TokenTree::MetaVarExpr(..) => 0,
             TokenTree::Token(..) => 0,
         }
     })
@@ -396,7 +397,7 @@ fn nameize<I: Iterator<Item = NamedMatch>>(
                     }
                 }
             }
-            TokenTree::MetaVar(..) | TokenTree::Token(..) => (),
+            TokenTree::MetaVar(..) | TokenTree::MetaVarExpr(..) | TokenTree::Token(..) => (),
         }
 
         Ok(())
@@ -616,7 +617,7 @@ fn inner_parse_loop<'root, 'tt>(
                 // rules. NOTE that this is not necessarily an error unless _all_ items in
                 // `cur_items` end up doing this. There may still be some other matchers that do
                 // end up working out.
-                TokenTree::Token(..) | TokenTree::MetaVar(..) => {}
+                TokenTree::Token(..) | TokenTree::MetaVar(..) | TokenTree::MetaVarExpr(..) => {}
             }
         }
     }
@@ -0,0 +1,166 @@
+//! Meta-variable expressions such as `${count(x)}`, which can appear in the RHS of a macro
+//! definition and are evaluated during transcription.
+
+use crate::ast::Ident;
+use crate::parse::token::{self, Token};
+use crate::parse::{ParseSess, PResult};
+use crate::print::pprust;
+use crate::tokenstream::{self, TokenStream};
+
+use syntax_pos::Span;
+
+/// A meta-variable expression, for expansions based on properties of meta-variables.
+#[derive(Debug, Clone, PartialEq, RustcEncodable, RustcDecodable)]
+crate enum MetaVarExpr {
+    /// The number of repetitions of an identifier, optionally limited to a number
+    /// of outer-most repetition depths. If the depth limit is `None` then the depth is unlimited.
+    Count(Ident, Option<usize>),
+
+    /// Ignore a meta-variable for repetition without expansion.
+    Ignore(Ident),
+
+    /// The index of the repetition at a particular depth, where 0 is the inner-most
+    /// repetition. The `usize` is the depth.
+    Index(usize),
+
+    /// The length of the repetition at a particular depth, where 0 is the inner-most
+    /// repetition. The `usize` is the depth.
+    Length(usize),
+}
+
+impl MetaVarExpr {
+    /// Attempt to parse a meta-variable expression from a token stream.
+    crate fn parse<'sess>(
+        input: &TokenStream,
+        outer_span: Span,
+        sess: &'sess ParseSess,
+    ) -> PResult<'sess, MetaVarExpr> {
+        let mut tts = input.trees();
+        let ident = parse_ident(&mut tts, sess, outer_span)?;
+        let args = match tts.next() {
+            Some(tokenstream::TokenTree::Delimited(_, token::Paren, args)) => args,
+            _ => {
+                let msg = "meta-variable expression parameter must be wrapped in parentheses";
+                return Err(sess.span_diagnostic.struct_span_err(ident.span, msg));
+            }
+        };
+        check_trailing_token(&mut tts, sess)?;
+        let mut iter = args.trees();
+        let rslt = match &*ident.as_str() {
+            "count" => parse_count(&mut iter, sess, ident.span)?,
+            "ignore" => MetaVarExpr::Ignore(parse_ident(&mut iter, sess, ident.span)?),
+            "index" => MetaVarExpr::Index(parse_depth(&mut iter, sess)?),
+            "length" => MetaVarExpr::Length(parse_depth(&mut iter, sess)?),
+            _ => {
+                let mut err = sess.span_diagnostic.struct_span_err(
+                    ident.span,
+                    "unrecognized meta-variable expression",
+                );
+                err.help("supported expressions are `count`, `ignore`, `index` and `length`");
+                return Err(err);
+            }
+        };
+        check_trailing_token(&mut iter, sess)?;
+        Ok(rslt)
+    }
+
+    /// Returns the meta-variable this expression refers to, if any.
+    crate fn ident(&self) -> Option<Ident> {
+        match *self {
+            MetaVarExpr::Count(ident, _) | MetaVarExpr::Ignore(ident) => Some(ident),
+            MetaVarExpr::Index(..) | MetaVarExpr::Length(..) => None,
+        }
+    }
+
+    /// The name of the expression as written, for use in diagnostics.
+    crate fn name(&self) -> &'static str {
+        match *self {
+            MetaVarExpr::Count(..) => "count",
+            MetaVarExpr::Ignore(..) => "ignore",
+            MetaVarExpr::Index(..) => "index",
+            MetaVarExpr::Length(..) => "length",
+        }
+    }
+}
+
+/// Checks that there are no more tokens left in the given iterator.
+fn check_trailing_token<'sess>(
+    iter: &mut impl Iterator<Item = tokenstream::TokenTree>,
+    sess: &'sess ParseSess,
+) -> PResult<'sess, ()> {
+    if let Some(tt) = iter.next() {
+        let msg = format!("unexpected token: {}", pprust::tt_to_string(tt.clone()));
+        let mut err = sess.span_diagnostic.struct_span_err(tt.span(), &msg);
+        err.note("meta-variable expression must not have trailing tokens");
+        Err(err)
+    } else {
+        Ok(())
+    }
+}
+
+/// Parses the arguments of `count`: a meta-variable, optionally followed by `,` and a depth.
+fn parse_count<'sess>(
+    iter: &mut impl Iterator<Item = tokenstream::TokenTree>,
+    sess: &'sess ParseSess,
+    span: Span,
+) -> PResult<'sess, MetaVarExpr> {
+    let ident = parse_ident(iter, sess, span)?;
+    let depth = match iter.next() {
+        None => None,
+        Some(tokenstream::TokenTree::Token(Token { kind: token::Comma, .. })) => {
+            Some(parse_depth(iter, sess)?)
+        }
+        Some(tt) => {
+            let msg = "expected `,` or `)` after the meta-variable in `count`";
+            return Err(sess.span_diagnostic.struct_span_err(tt.span(), msg));
+        }
+    };
+    Ok(MetaVarExpr::Count(ident, depth))
+}
+
+/// Parses an optional depth, which must be an unsuffixed integer literal. The depth defaults to
+/// 0 (the inner-most repetition) when omitted.
+fn parse_depth<'sess>(
+    iter: &mut impl Iterator<Item = tokenstream::TokenTree>,
+    sess: &'sess ParseSess,
+) -> PResult<'sess, usize> {
+    let tt = match iter.next() {
+        None => return Ok(0),
+        Some(tt) => tt,
+    };
+    let msg = "meta-variable expression depth must be a literal";
+    match tt {
+        tokenstream::TokenTree::Token(Token {
+            kind: token::Literal(token::Lit { kind: token::Integer, symbol, suffix: None }),
+            span,
+        }) => symbol
+            .as_str()
+            .parse::<usize>()
+            .map_err(|_| sess.span_diagnostic.struct_span_err(span, msg)),
+        tt => Err(sess.span_diagnostic.struct_span_err(tt.span(), msg)),
+    }
+}
+
+/// Parses a meta-variable identifier, written without the leading `$`.
+fn parse_ident<'sess>(
+    iter: &mut impl Iterator<Item = tokenstream::TokenTree>,
+    sess: &'sess ParseSess,
+    span: Span,
+) -> PResult<'sess, Ident> {
+    match iter.next() {
+        Some(tokenstream::TokenTree::Token(token)) => match token.ident() {
+            Some((ident, false)) => Ok(ident),
+            _ => {
+                let msg = format!(
+                    "expected identifier, found `{}`",
+                    pprust::token_to_string(&token),
+                );
+                Err(sess.span_diagnostic.struct_span_err(token.span, &msg))
+            }
+        },
+        Some(tt) => {
+            Err(sess.span_diagnostic.struct_span_err(tt.span(), "expected identifier"))
+        }
+        None => Err(sess.span_diagnostic.struct_span_err(span, "expected identifier")),
+    }
+}
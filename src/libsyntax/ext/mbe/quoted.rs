@@ -1,10 +1,12 @@
 use crate::ast;
 use crate::ext::mbe::macro_parser;
+use crate::ext::mbe::metavar_expr::MetaVarExpr;
 use crate::ext::mbe::{TokenTree, KleeneOp, KleeneToken, SequenceRepetition, Delimited};
+use crate::feature_gate::{feature_err, Features, GateIssue};
 use crate::parse::token::{self, Token};
 use crate::parse::ParseSess;
 use crate::print::pprust;
-use crate::symbol::kw;
+use crate::symbol::{kw, sym};
 use crate::tokenstream;
 
 use syntax_pos::Span;
@@ -24,10 +26,7 @@ use rustc_data_structures::sync::Lrc;
 ///   `ident` are "matchers". They are not present in the body of a macro rule -- just in the
 ///   pattern, so we pass a parameter to indicate whether to expect them or not.
 /// - `sess`: the parsing session. Any errors will be emitted to this session.
-/// - `features`, `attrs`: language feature flags and attributes so that we know whether to use
-///   unstable features or not.
-/// - `edition`: which edition are we in.
-/// - `macro_node_id`: the NodeId of the macro we are parsing.
+/// - `features`: language feature flags so that we know whether to use unstable features or not.
 ///
 /// # Returns
 ///
@@ -36,6 +35,7 @@ pub(super) fn parse(
     input: tokenstream::TokenStream,
     expect_matchers: bool,
     sess: &ParseSess,
+    features: &Features,
 ) -> Vec<TokenTree> {
     // Will contain the final collection of `self::TokenTree`
     let mut result = Vec::new();
@@ -51,6 +51,7 @@ pub(super) fn parse(
             &mut trees,
             expect_matchers,
             sess,
+            features,
         );
         match tree {
             TokenTree::MetaVar(start_sp, ident) if expect_matchers => {
@@ -94,18 +95,43 @@ pub(super) fn parse(
 ///   converting `tree`
 /// - `expect_matchers`: same as for `parse` (see above).
 /// - `sess`: the parsing session. Any errors will be emitted to this session.
-/// - `features`, `attrs`: language feature flags and attributes so that we know whether to use
-///   unstable features or not.
+/// - `features`: language feature flags so that we know whether to use unstable features or not.
 fn parse_tree(
     tree: tokenstream::TokenTree,
     trees: &mut impl Iterator<Item = tokenstream::TokenTree>,
     expect_matchers: bool,
     sess: &ParseSess,
+    features: &Features,
 ) -> TokenTree {
     // Depending on what `tree` is, we could be parsing different parts of a macro
     match tree {
         // `tree` is a `$` token. Look at the next token in `trees`
         tokenstream::TokenTree::Token(Token { kind: token::Dollar, span }) => match trees.next() {
+            // `tree` is followed by a `{` in the body of a macro. This is a meta-variable
+            // expression (e.g. `${count(x)}`), which is evaluated during transcription.
+            Some(tokenstream::TokenTree::Delimited(span, token::Brace, tts))
+                if !expect_matchers =>
+            {
+                if !features.macro_metavar_expr {
+                    feature_err(
+                        sess,
+                        sym::macro_metavar_expr,
+                        span.entire(),
+                        GateIssue::Language,
+                        "meta-variable expressions are unstable",
+                    )
+                    .emit();
+                }
+                match MetaVarExpr::parse(&tts, span.entire(), sess) {
+                    Ok(expr) => TokenTree::MetaVarExpr(span, expr),
+                    Err(mut err) => {
+                        err.emit();
+                        // Recover with a plain `$`, the error has already been reported.
+                        TokenTree::token(token::Dollar, span.entire())
+                    }
+                }
+            }
+
             // `tree` is followed by a delimited set of token trees. This indicates the beginning
             // of a repetition sequence in the macro (e.g. `$(pat)*`).
             Some(tokenstream::TokenTree::Delimited(span, delim, tts)) => {
@@ -120,6 +146,7 @@ fn parse_tree(
                     tts.into(),
                     expect_matchers,
                     sess,
+                    features,
                 );
                 // Get the Kleene operator and optional separator
                 let (separator, kleene) = parse_sep_and_kleene_op(trees, span.entire(), sess);
@@ -173,6 +200,7 @@ fn parse_tree(
                     tts.into(),
                     expect_matchers,
                     sess,
+                    features,
                 ),
             }),
         ),
@@ -2,8 +2,10 @@ use crate::ast::{Ident, Mac};
 use crate::ext::base::ExtCtxt;
 use crate::ext::mbe;
 use crate::ext::mbe::macro_parser::{MatchedNonterminal, MatchedSeq, NamedMatch};
+use crate::ext::mbe::metavar_expr::MetaVarExpr;
 use crate::mut_visit::{self, MutVisitor};
 use crate::parse::token::{self, NtTT, Token};
+use crate::symbol::Symbol;
 use crate::tokenstream::{DelimSpan, TokenStream, TokenTree, TreeAndJoint};
 
 use smallvec::{smallvec, SmallVec};
@@ -283,6 +285,12 @@ pub(super) fn transcribe(
                 result.push(tt.into());
             }
 
+            // Replace the meta-variable expression with the result of its evaluation, e.g.
+            // `${count(x)}` with the number of repetitions of `x`.
+            mbe::TokenTree::MetaVarExpr(sp, expr) => {
+                transcribe_metavar_expr(cx, &expr, interp, &mut marker, &repeats, &mut result, sp);
+            }
+
             // There should be no meta-var declarations in the invocation of a macro.
             mbe::TokenTree::MetaVarDecl(..) => panic!("unexpected `TokenTree::MetaVarDecl"),
         }
@@ -393,6 +401,115 @@ fn lockstep_iter_size(
                 _ => LockstepIterSize::Unconstrained,
             }
         }
+        TokenTree::MetaVarExpr(_, ref expr) => match expr.ident() {
+            Some(name) => match lookup_cur_matched(name, interpolations, repeats) {
+                Some(MatchedSeq(ref ads, _)) => LockstepIterSize::Constraint(ads.len(), name),
+                _ => LockstepIterSize::Unconstrained,
+            },
+            None => LockstepIterSize::Unconstrained,
+        },
         TokenTree::Token(..) => LockstepIterSize::Unconstrained,
     }
 }
+
+/// Evaluates a meta-variable expression and pushes the resulting tokens to `result`.
+///
+/// The produced literals are given the span of the whole `${...}` expression, marked with the
+/// current expansion, so that diagnostics about them point into the macro definition.
+fn transcribe_metavar_expr(
+    cx: &ExtCtxt<'_>,
+    expr: &MetaVarExpr,
+    interp: &FxHashMap<Ident, NamedMatch>,
+    marker: &mut Marker,
+    repeats: &[(usize, usize)],
+    result: &mut Vec<TreeAndJoint>,
+    sp: DelimSpan,
+) {
+    let n = match *expr {
+        MetaVarExpr::Count(ident, depth) => match lookup_cur_matched(ident, interp, repeats) {
+            Some(matched) => count_repetitions(cx, depth, matched, 0, sp.entire()),
+            None => cx.span_fatal(
+                sp.entire(),
+                &format!("variable `{}` is not recognized in meta-variable expression", ident),
+            ),
+        },
+        MetaVarExpr::Ignore(_) => return,
+        MetaVarExpr::Index(depth) => repetition_at_depth(cx, expr, depth, repeats, sp.entire()).0,
+        MetaVarExpr::Length(depth) => repetition_at_depth(cx, expr, depth, repeats, sp.entire()).1,
+    };
+    let mut span = sp.entire();
+    marker.visit_span(&mut span);
+    let lit = token::Lit::new(token::Integer, Symbol::intern(&n.to_string()), None);
+    result.push(TokenTree::token(token::Literal(lit), span).into());
+}
+
+/// Used by the `count` meta-variable expression: counts the repetitions of `matched` at the
+/// given depth, or the total number of matches in all nested repetitions if no depth is given.
+///
+/// `declared_depth` is the number of repetitions already descended into by this function, so
+/// that we can tell whether the expression was placed inside the inner-most repetition.
+fn count_repetitions(
+    cx: &ExtCtxt<'_>,
+    depth: Option<usize>,
+    matched: &NamedMatch,
+    declared_depth: usize,
+    sp: Span,
+) -> usize {
+    match *matched {
+        MatchedNonterminal(_) => {
+            if declared_depth == 0 {
+                cx.span_fatal(sp, "`count` can not be placed inside the inner-most repetition");
+            }
+            match depth {
+                None => 1,
+                Some(_) => cx.span_fatal(
+                    sp,
+                    &format!(
+                        "depth parameter on meta-variable expression `count` \
+                         must be less than {}",
+                        declared_depth,
+                    ),
+                ),
+            }
+        }
+        MatchedSeq(ref ads, _) => match depth {
+            Some(0) => ads.len(),
+            _ => ads
+                .iter()
+                .map(|elem| {
+                    let depth = depth.map(|depth| depth - 1);
+                    count_repetitions(cx, depth, elem, declared_depth + 1, sp)
+                })
+                .sum(),
+        },
+    }
+}
+
+/// Used by the `index` and `length` meta-variable expressions: returns the `(index, length)`
+/// pair of the enclosing repetition `depth` levels out from the inner-most one.
+fn repetition_at_depth(
+    cx: &ExtCtxt<'_>,
+    expr: &MetaVarExpr,
+    depth: usize,
+    repeats: &[(usize, usize)],
+    sp: Span,
+) -> (usize, usize) {
+    match repeats.iter().nth_back(depth) {
+        Some(&repeat) => repeat,
+        None if repeats.is_empty() => cx.span_fatal(
+            sp,
+            &format!(
+                "meta-variable expression `{}` must be placed inside a repetition",
+                expr.name(),
+            ),
+        ),
+        None => cx.span_fatal(
+            sp,
+            &format!(
+                "depth parameter on meta-variable expression `{}` must be less than {}",
+                expr.name(),
+                repeats.len(),
+            ),
+        ),
+    }
+}
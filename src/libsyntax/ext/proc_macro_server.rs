@@ -1,15 +1,16 @@
 use crate::ast;
 use crate::ext::base::ExtCtxt;
+use crate::ext::expand::AstFragment;
 use crate::parse::{self, token, ParseSess};
 use crate::parse::lexer::comments;
 use crate::tokenstream::{self, DelimSpan, IsJoint::*, TokenStream, TreeAndJoint};
 
-use errors::Diagnostic;
+use errors::{Diagnostic, SubstitutionPart};
 use rustc_data_structures::sync::Lrc;
 use syntax_pos::{BytePos, FileName, MultiSpan, Pos, SourceFile, Span};
 use syntax_pos::symbol::{kw, sym, Symbol};
 
-use proc_macro::{Delimiter, Level, LineColumn, Spacing};
+use proc_macro::{Applicability, Delimiter, Level, LineColumn, Spacing};
 use proc_macro::bridge::{server, TokenTree};
 use std::{ascii, panic};
 use std::ops::Bound;
@@ -279,6 +280,20 @@ impl ToInternal<errors::Level> for Level {
     }
 }
 
+impl ToInternal<errors::Applicability> for Applicability {
+    fn to_internal(self) -> errors::Applicability {
+        match self {
+            Applicability::MachineApplicable => errors::Applicability::MachineApplicable,
+            Applicability::MaybeIncorrect => errors::Applicability::MaybeIncorrect,
+            Applicability::HasPlaceholders => errors::Applicability::HasPlaceholders,
+            Applicability::Unspecified => errors::Applicability::Unspecified,
+            _ => unreachable!("unknown proc_macro::Applicability variant: {:?}", self),
+        }
+    }
+}
+
+pub struct FreeFunctions;
+
 #[derive(Clone)]
 pub struct TokenStreamIter {
     cursor: tokenstream::Cursor,
@@ -351,21 +366,38 @@ pub struct Literal {
     span: Span,
 }
 
-pub(crate) struct Rustc<'a> {
-    sess: &'a ParseSess,
+impl Literal {
+    /// Decodes the literal exactly like the parser does. Literals built from
+    /// negative numbers keep their `-` in the symbol, so it is split off first
+    /// and returned separately.
+    fn value(&self) -> Option<(bool, ast::LitKind)> {
+        let mut lit = self.lit;
+        let symbol = lit.symbol.as_str();
+        let negative = symbol.starts_with('-');
+        if negative {
+            lit.symbol = Symbol::intern(&symbol[1..]);
+        }
+        ast::LitKind::from_lit_token(lit).ok().map(|kind| (negative, kind))
+    }
+}
+
+pub(crate) struct Rustc<'a, 'b> {
+    ecx: &'a mut ExtCtxt<'b>,
+    sess: &'b ParseSess,
     def_site: Span,
     call_site: Span,
     mixed_site: Span,
 }
 
-impl<'a> Rustc<'a> {
-    pub fn new(cx: &'a ExtCtxt<'_>) -> Self {
-        let expn_data = cx.current_expansion.id.expn_data();
+impl<'a, 'b> Rustc<'a, 'b> {
+    pub fn new(ecx: &'a mut ExtCtxt<'b>) -> Self {
+        let expn_data = ecx.current_expansion.id.expn_data();
         Rustc {
-            sess: cx.parse_sess,
-            def_site: cx.with_def_site_ctxt(expn_data.def_site),
-            call_site: cx.with_call_site_ctxt(expn_data.call_site),
-            mixed_site: cx.with_mixed_site_ctxt(expn_data.call_site),
+            sess: ecx.parse_sess,
+            def_site: ecx.with_def_site_ctxt(expn_data.def_site),
+            call_site: ecx.with_call_site_ctxt(expn_data.call_site),
+            mixed_site: ecx.with_mixed_site_ctxt(expn_data.call_site),
+            ecx,
         }
     }
 
@@ -377,7 +409,8 @@ impl<'a> Rustc<'a> {
     }
 }
 
-impl server::Types for Rustc<'_> {
+impl server::Types for Rustc<'_, '_> {
+    type FreeFunctions = FreeFunctions;
     type TokenStream = TokenStream;
     type TokenStreamBuilder = tokenstream::TokenStreamBuilder;
     type TokenStreamIter = TokenStreamIter;
@@ -391,7 +424,18 @@ impl server::Types for Rustc<'_> {
     type Span = Span;
 }
 
-impl server::TokenStream for Rustc<'_> {
+impl server::FreeFunctions for Rustc<'_, '_> {
+    fn track_env_var(&mut self, var: &str, value: Option<&str>) {
+        self.sess.env_depinfo
+            .borrow_mut()
+            .insert((Symbol::intern(var), value.map(Symbol::intern)));
+    }
+    fn track_path(&mut self, path: &str) {
+        self.sess.file_depinfo.borrow_mut().insert(Symbol::intern(path));
+    }
+}
+
+impl server::TokenStream for Rustc<'_, '_> {
     fn new(&mut self) -> Self::TokenStream {
         TokenStream::empty()
     }
@@ -409,6 +453,51 @@ impl server::TokenStream for Rustc<'_> {
     fn to_string(&mut self, stream: &Self::TokenStream) -> String {
         stream.to_string()
     }
+    fn expand_expr(&mut self, stream: &Self::TokenStream) -> Result<Self::TokenStream, ()> {
+        let mut parser = parse::stream_to_parser(
+            self.sess,
+            stream.clone(),
+            Some("proc_macro expand_expr"),
+        );
+        let expr = match parser.parse_expr() {
+            Ok(expr) => expr,
+            Err(mut err) => {
+                err.cancel();
+                return Err(());
+            }
+        };
+        if parser.token != token::Eof {
+            return Err(());
+        }
+
+        // Eagerly expand the expression, like the built-in macros that accept
+        // e.g. `concat!(...)` in place of a string literal do.
+        let expr = self.ecx.expander().fully_expand_fragment(AstFragment::Expr(expr)).make_expr();
+
+        // Only literals are handed back to the macro; anything else could
+        // depend on the context that the expression ends up in.
+        match &expr.kind {
+            ast::ExprKind::Lit(lit) => match lit.kind {
+                ast::LitKind::Err(_) => Err(()),
+                _ => Ok(lit.tokens()),
+            },
+            ast::ExprKind::Unary(ast::UnOp::Neg, operand) => match &operand.kind {
+                ast::ExprKind::Lit(lit) => match lit.kind {
+                    ast::LitKind::Int(..) |
+                    ast::LitKind::Float(..) |
+                    ast::LitKind::FloatUnsuffixed(_) => {
+                        let minus = token::BinOp(token::BinOpToken::Minus);
+                        let minus = tokenstream::TokenTree::token(minus, expr.span);
+                        let lit = tokenstream::TokenTree::token(token::Literal(lit.token), lit.span);
+                        Ok(vec![minus, lit].into_iter().collect())
+                    }
+                    _ => Err(()),
+                },
+                _ => Err(()),
+            },
+            _ => Err(()),
+        }
+    }
     fn from_token_tree(
         &mut self,
         tree: TokenTree<Self::Group, Self::Punct, Self::Ident, Self::Literal>,
@@ -423,7 +512,7 @@ impl server::TokenStream for Rustc<'_> {
     }
 }
 
-impl server::TokenStreamBuilder for Rustc<'_> {
+impl server::TokenStreamBuilder for Rustc<'_, '_> {
     fn new(&mut self) -> Self::TokenStreamBuilder {
         tokenstream::TokenStreamBuilder::new()
     }
@@ -435,7 +524,7 @@ impl server::TokenStreamBuilder for Rustc<'_> {
     }
 }
 
-impl server::TokenStreamIter for Rustc<'_> {
+impl server::TokenStreamIter for Rustc<'_, '_> {
     fn next(
         &mut self,
         iter: &mut Self::TokenStreamIter,
@@ -462,7 +551,7 @@ impl server::TokenStreamIter for Rustc<'_> {
     }
 }
 
-impl server::Group for Rustc<'_> {
+impl server::Group for Rustc<'_, '_> {
     fn new(&mut self, delimiter: Delimiter, stream: Self::TokenStream) -> Self::Group {
         Group {
             delimiter,
@@ -490,7 +579,7 @@ impl server::Group for Rustc<'_> {
     }
 }
 
-impl server::Punct for Rustc<'_> {
+impl server::Punct for Rustc<'_, '_> {
     fn new(&mut self, ch: char, spacing: Spacing) -> Self::Punct {
         Punct::new(ch, spacing == Spacing::Joint, server::Span::call_site(self))
     }
@@ -512,7 +601,7 @@ impl server::Punct for Rustc<'_> {
     }
 }
 
-impl server::Ident for Rustc<'_> {
+impl server::Ident for Rustc<'_, '_> {
     fn new(&mut self, string: &str, span: Self::Span, is_raw: bool) -> Self::Ident {
         Ident::new(Symbol::intern(string), is_raw, span)
     }
@@ -524,7 +613,7 @@ impl server::Ident for Rustc<'_> {
     }
 }
 
-impl server::Literal for Rustc<'_> {
+impl server::Literal for Rustc<'_, '_> {
     // FIXME(eddyb) `Literal` should not expose internal `Debug` impls.
     fn debug(&mut self, literal: &Self::Literal) -> String {
         format!("{:?}", literal)
@@ -565,6 +654,99 @@ impl server::Literal for Rustc<'_> {
             .collect::<String>();
         self.lit(token::ByteStr, Symbol::intern(&string), None)
     }
+    fn from_str(&mut self, s: &str) -> Result<Self::Literal, ()> {
+        let (negative, src) = if s.starts_with('-') { (true, &s[1..]) } else { (false, s) };
+        if src.is_empty() {
+            return Err(());
+        }
+        let (kind, suffix_start) = match rustc_lexer::first_token(src) {
+            rustc_lexer::Token {
+                kind: rustc_lexer::TokenKind::Literal { kind, suffix_start },
+                len,
+            } if len == src.len() => (kind, suffix_start),
+            _ => return Err(()),
+        };
+
+        // Work out the token kind and how many bytes of quotes, hashes and
+        // prefixes surround the symbol, like the lexer's `cook_lexer_literal`.
+        use rustc_lexer::LiteralKind as L;
+        let (kind, start, end) = match kind {
+            L::Char { terminated: true } => (token::Char, 1, 1),
+            L::Byte { terminated: true } => (token::Byte, 2, 1),
+            L::Str { terminated: true } => (token::Str, 1, 1),
+            L::ByteStr { terminated: true } => (token::ByteStr, 2, 1),
+            L::RawStr { n_hashes, started: true, terminated: true }
+                if n_hashes <= u16::max_value() as usize =>
+                (token::StrRaw(n_hashes as u16), 2 + n_hashes, 1 + n_hashes),
+            L::RawByteStr { n_hashes, started: true, terminated: true }
+                if n_hashes <= u16::max_value() as usize =>
+                (token::ByteStrRaw(n_hashes as u16), 3 + n_hashes, 1 + n_hashes),
+            L::Int { empty_int: false, .. } => (token::Integer, 0, 0),
+            L::Float { empty_exponent: false, .. } => (token::Float, 0, 0),
+            _ => return Err(()),
+        };
+        if negative && kind != token::Integer && kind != token::Float {
+            return Err(());
+        }
+
+        let suffix = if suffix_start < src.len() {
+            Some(Symbol::intern(&src[suffix_start..]))
+        } else {
+            None
+        };
+        let symbol = Symbol::intern(&src[start..suffix_start - end]);
+        let lit = token::Lit::new(kind, symbol, suffix);
+        // Reject everything that the parser would reject, e.g. invalid escapes,
+        // unknown suffixes or out-of-range integers.
+        if ast::LitKind::from_lit_token(lit).is_err() {
+            return Err(());
+        }
+
+        let symbol = if negative { Symbol::intern(&s[..suffix_start + 1]) } else { symbol };
+        Ok(self.lit(kind, symbol, suffix))
+    }
+    fn suffix(&mut self, literal: &Self::Literal) -> Option<String> {
+        literal.lit.suffix.map(|suffix| suffix.to_string())
+    }
+    fn str_value(&mut self, literal: &Self::Literal) -> Option<String> {
+        match literal.value() {
+            Some((false, ast::LitKind::Str(s, _))) => Some(s.to_string()),
+            _ => None,
+        }
+    }
+    fn byte_str_value(&mut self, literal: &Self::Literal) -> Option<Vec<u8>> {
+        match literal.value() {
+            Some((false, ast::LitKind::ByteStr(bytes))) => Some(bytes.to_vec()),
+            _ => None,
+        }
+    }
+    fn char_value(&mut self, literal: &Self::Literal) -> Option<char> {
+        match literal.value() {
+            Some((false, ast::LitKind::Char(c))) => Some(c),
+            _ => None,
+        }
+    }
+    fn byte_value(&mut self, literal: &Self::Literal) -> Option<u8> {
+        match literal.value() {
+            Some((false, ast::LitKind::Byte(b))) => Some(b),
+            _ => None,
+        }
+    }
+    fn int_value(&mut self, literal: &Self::Literal) -> Option<u128> {
+        match literal.value() {
+            Some((false, ast::LitKind::Int(n, _))) => Some(n),
+            _ => None,
+        }
+    }
+    fn float_value(&mut self, literal: &Self::Literal) -> Option<String> {
+        match literal.value() {
+            Some((negative, ast::LitKind::Float(symbol, _)))
+            | Some((negative, ast::LitKind::FloatUnsuffixed(symbol))) => {
+                Some(format!("{}{}", if negative { "-" } else { "" }, symbol))
+            }
+            _ => None,
+        }
+    }
     fn span(&mut self, literal: &Self::Literal) -> Self::Span {
         literal.span
     }
@@ -609,7 +791,7 @@ impl server::Literal for Rustc<'_> {
     }
 }
 
-impl server::SourceFile for Rustc<'_> {
+impl server::SourceFile for Rustc<'_, '_> {
     fn eq(&mut self, file1: &Self::SourceFile, file2: &Self::SourceFile) -> bool {
         Lrc::ptr_eq(file1, file2)
     }
@@ -627,7 +809,7 @@ impl server::SourceFile for Rustc<'_> {
     }
 }
 
-impl server::MultiSpan for Rustc<'_> {
+impl server::MultiSpan for Rustc<'_, '_> {
     fn new(&mut self) -> Self::MultiSpan {
         vec![]
     }
@@ -636,7 +818,7 @@ impl server::MultiSpan for Rustc<'_> {
     }
 }
 
-impl server::Diagnostic for Rustc<'_> {
+impl server::Diagnostic for Rustc<'_, '_> {
     fn new(&mut self, level: Level, msg: &str, spans: Self::MultiSpan) -> Self::Diagnostic {
         let mut diag = Diagnostic::new(level.to_internal(), msg);
         diag.set_span(MultiSpan::from_spans(spans));
@@ -651,12 +833,32 @@ impl server::Diagnostic for Rustc<'_> {
     ) {
         diag.sub(level.to_internal(), msg, MultiSpan::from_spans(spans), None);
     }
-    fn emit(&mut self, diag: Self::Diagnostic) {
+    fn suggestion(
+        &mut self,
+        diag: &mut Self::Diagnostic,
+        msg: &str,
+        applicability: Applicability,
+    ) {
+        // The parts are filled in by the `suggestion_part` calls that follow.
+        diag.multipart_suggestion(msg, vec![], applicability.to_internal());
+    }
+    fn suggestion_part(&mut self, diag: &mut Self::Diagnostic, span: Self::Span, snippet: &str) {
+        let suggestion = diag.suggestions.last_mut()
+            .expect("proc_macro: `suggestion_part` called before `suggestion`");
+        suggestion.substitutions[0].parts.push(SubstitutionPart {
+            span,
+            snippet: snippet.to_string(),
+        });
+    }
+    fn emit(&mut self, mut diag: Self::Diagnostic) {
+        // A macro may have suggested replacing nothing at all, which the
+        // emitters can't render.
+        diag.suggestions.retain(|sugg| sugg.substitutions.iter().all(|s| !s.parts.is_empty()));
         self.sess.span_diagnostic.emit_diagnostic(&diag);
     }
 }
 
-impl server::Span for Rustc<'_> {
+impl server::Span for Rustc<'_, '_> {
     fn debug(&mut self, span: Self::Span) -> String {
         format!("{:?} bytes({}..{})", span.ctxt(), span.lo().0, span.hi().0)
     }
@@ -528,6 +528,21 @@ declare_features! (
     /// Enable accurate caller location reporting during panic (RFC 2091).
     (active, track_caller, "1.40.0", Some(47809), None),
 
+    /// Allows setting the number of steps const evaluation may take
+    /// with `#![const_eval_limit = "N"]`.
+    (active, const_eval_limit, "1.40.0", None, None),
+
+    /// Allows `format_args!` and friends to capture named arguments
+    /// implicitly from the surrounding scope, as in `format!("{x}")`.
+    (active, format_args_capture, "1.40.0", None, None),
+
+    /// Allows meta-variable expressions such as `${count(x)}` in the body of
+    /// declarative macros.
+    (active, macro_metavar_expr, "1.40.0", None, None),
+
+    /// Allows `#[derive(Default)]` on enums, with the default variant marked `#[default]`.
+    (active, derive_default_enum, "1.40.0", None, None),
+
     // -------------------------------------------------------------------------
     // feature-group-end: actual feature gates
     // -------------------------------------------------------------------------
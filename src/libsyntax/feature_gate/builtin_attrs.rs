@@ -9,7 +9,7 @@ use super::active::Features;
 
 use crate::ast;
 use crate::attr::AttributeTemplate;
-use crate::symbol::{Symbol, sym};
+use crate::symbol::{Symbol, kw, sym};
 use crate::parse::ParseSess;
 
 use syntax_pos::Span;
@@ -325,6 +325,19 @@ pub const BUILTIN_ATTRIBUTES: &[BuiltinAttribute] = &[
 
     gated!(ffi_returns_twice, Whitelisted, template!(Word), experimental!(ffi_returns_twice)),
     gated!(track_caller, Whitelisted, template!(Word), experimental!(track_caller)),
+    gated!(
+        const_eval_limit, CrateLevel, template!(NameValueStr: "N"),
+        experimental!(const_eval_limit),
+    ),
+    // `default` is a keyword, so this can't use `gated!`.
+    (
+        kw::Default, Normal, template!(Word),
+        Gated(
+            Stability::Unstable, sym::derive_default_enum,
+            "the `#[default]` attribute is an experimental feature",
+            cfg_fn!(derive_default_enum),
+        ),
+    ),
 
     // ==========================================================================
     // Internal attributes: Stability, deprecation, and unsafe:
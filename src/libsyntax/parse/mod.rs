@@ -80,6 +80,10 @@ pub struct ParseSess {
     pub ambiguous_block_expr_parse: Lock<FxHashMap<Span, Span>>,
     pub injected_crate_name: Once<Symbol>,
     pub gated_spans: GatedSpans,
+    /// Environment variables read by proc macros, and their values if they were set.
+    pub env_depinfo: Lock<FxHashSet<(Symbol, Option<Symbol>)>>,
+    /// Files other than source files that proc macros read.
+    pub file_depinfo: Lock<FxHashSet<Symbol>>,
 }
 
 impl ParseSess {
@@ -108,6 +112,8 @@ impl ParseSess {
             ambiguous_block_expr_parse: Lock::new(FxHashMap::default()),
             injected_crate_name: Once::new(),
             gated_spans: GatedSpans::default(),
+            env_depinfo: Default::default(),
+            file_depinfo: Default::default(),
         }
     }
 
@@ -2,11 +2,12 @@ use crate::deriving::path_std;
 use crate::deriving::generic::*;
 use crate::deriving::generic::ty::*;
 
-use syntax::ast::{Expr, MetaItem};
+use syntax::ast::{self, Expr, MetaItem};
+use syntax::attr;
 use syntax::ext::base::{Annotatable, DummyResult, ExtCtxt};
 use syntax::ptr::P;
 use syntax::symbol::{kw, sym};
-use syntax::span_err;
+use syntax::struct_span_err;
 use syntax_pos::Span;
 
 pub fn expand_deriving_default(cx: &mut ExtCtxt<'_>,
@@ -69,12 +70,60 @@ fn default_substructure(cx: &mut ExtCtxt<'_>,
                 }
             }
         }
-        StaticEnum(..) => {
-            span_err!(cx, trait_span, E0665,
-                      "`Default` cannot be derived for enums, only structs");
-            // let compilation continue
-            DummyResult::raw_expr(trait_span, true)
+        StaticEnum(enum_def, _) => {
+            match default_variant(cx, trait_span, enum_def) {
+                Some(variant) => {
+                    let path = cx.path(trait_span, vec![substr.type_ident, variant.ident]);
+                    cx.expr_path(path)
+                }
+                // let compilation continue
+                None => DummyResult::raw_expr(trait_span, true),
+            }
         }
         _ => cx.span_bug(trait_span, "Non-static method in `derive(Default)`"),
     };
 }
+
+/// Finds the unit variant marked with `#[default]`, reporting an error and returning `None` if
+/// there isn't exactly one such variant.
+fn default_variant<'a>(cx: &ExtCtxt<'_>,
+                       trait_span: Span,
+                       enum_def: &'a ast::EnumDef)
+                       -> Option<&'a ast::Variant> {
+    // `contains_name` also marks the attributes as used.
+    let default_variants = enum_def.variants.iter()
+        .filter(|variant| attr::contains_name(&variant.attrs, kw::Default))
+        .collect::<Vec<_>>();
+
+    let variant = match default_variants.len() {
+        0 => {
+            let mut err = struct_span_err!(cx.parse_sess.span_diagnostic, trait_span, E0665,
+                                           "no default declared");
+            err.help("make a unit variant default by placing `#[default]` above it");
+            err.emit();
+            return None;
+        }
+        1 => default_variants[0],
+        _ => {
+            let mut err = cx.struct_span_err(trait_span, "multiple declared defaults");
+            err.span_label(default_variants[0].span, "first default");
+            for variant in &default_variants[1..] {
+                err.span_label(variant.span, "additional default");
+            }
+            err.note("only one variant can be default");
+            err.emit();
+            return None;
+        }
+    };
+
+    if let ast::VariantData::Unit(..) = variant.data {
+        Some(variant)
+    } else {
+        let attr = attr::find_by_name(&variant.attrs, kw::Default).unwrap();
+        cx.struct_span_err(attr.span,
+                           "the `#[default]` attribute may only be used on unit enum variants")
+            .help("consider a manual implementation of `Default`")
+            .emit();
+        None
+    }
+}
@@ -85,7 +85,7 @@ https://doc.rust-lang.org/unstable-book/language-features/asm.html
 "##,
 
 E0665: r##"
-The `Default` trait was derived on an enum.
+The `Default` trait was derived on an enum without a default variant.
 
 Erroneous code example:
 
@@ -97,12 +97,26 @@ enum Food {
 }
 ```
 
-The `Default` cannot be derived on an enum for the simple reason that the
-compiler doesn't know which value to pick by default whereas it can for a
-struct as long as all its fields implement the `Default` trait as well.
+The `Default` cannot be derived on an enum unless one of its variants is
+marked as the default, since the compiler doesn't know which value to pick
+otherwise. It can for a struct as long as all its fields implement the
+`Default` trait as well.
 
-If you still want to implement `Default` on your enum, you'll have to do it "by
-hand":
+You can pick a unit variant as the default with the `#[default]` attribute
+(this is currently unstable):
+
+```
+#![feature(derive_default_enum)]
+
+#[derive(Default)]
+enum Food {
+    #[default]
+    Sweet,
+    Salty,
+}
+```
+
+Or you can implement `Default` on your enum "by hand":
 
 ```
 enum Food {
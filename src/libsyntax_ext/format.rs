@@ -57,6 +57,15 @@ struct Context<'a, 'b> {
     arg_unique_types: Vec<Vec<ArgumentType>>,
     /// Map from named arguments to their resolved indices.
     names: FxHashMap<Symbol, usize>,
+    /// Named arguments that were captured implicitly from the surrounding
+    /// scope rather than passed as `name = expr`.
+    captured_names: FxHashSet<Symbol>,
+    /// Number of captured arguments. They are appended to the end of `args`,
+    /// after the explicit arguments, and positional references can't reach them.
+    num_captured_args: usize,
+    /// Pieces whose argument is a captured one, which don't count as
+    /// positional arguments in diagnostics.
+    captured_pieces: FxHashSet<usize>,
 
     /// The latest consecutive literal strings, or empty if there weren't any.
     literal: String,
@@ -264,8 +273,14 @@ impl<'a, 'b> Context<'a, 'b> {
         }
     }
 
+    /// Number of arguments passed explicitly to the macro, leaving out the
+    /// captured ones.
+    fn num_args(&self) -> usize {
+        self.args.len() - self.num_captured_args
+    }
+
     fn describe_num_args(&self) -> Cow<'_, str> {
-        match self.args.len() {
+        match self.num_args() {
             0 => "no arguments were given".into(),
             1 => "there is 1 argument".into(),
             x => format!("there are {} arguments", x).into(),
@@ -279,7 +294,12 @@ impl<'a, 'b> Context<'a, 'b> {
     fn report_invalid_references(&self, numbered_position_args: bool) {
         let mut e;
         let sp = if self.is_literal {
-            MultiSpan::from_spans(self.arg_spans.clone())
+            MultiSpan::from_spans(self.arg_spans
+                .iter()
+                .enumerate()
+                .filter(|(i, _)| !self.captured_pieces.contains(i))
+                .map(|(_, &sp)| sp)
+                .collect())
         } else {
             MultiSpan::from_span(self.fmtsp)
         };
@@ -290,11 +310,12 @@ impl<'a, 'b> Context<'a, 'b> {
 
         let mut zero_based_note = false;
 
-        let count = self.pieces.len() + self.arg_with_formatting
+        let count = self.pieces.len() - self.captured_pieces.len() + self.arg_with_formatting
             .iter()
             .filter(|fmt| fmt.precision_span.is_some())
             .count();
-        if self.names.is_empty() && !numbered_position_args && count != self.args.len() {
+        let no_explicit_names = self.names.len() == self.num_captured_args;
+        if no_explicit_names && !numbered_position_args && count != self.num_args() {
             e = self.ecx.struct_span_err(
                 sp,
                 &format!(
@@ -347,7 +368,7 @@ impl<'a, 'b> Context<'a, 'b> {
             if let Some(span) = fmt.precision_span {
                 let span = self.fmtsp.from_inner(span);
                 match fmt.precision {
-                    parse::CountIsParam(pos) if pos > self.args.len() => {
+                    parse::CountIsParam(pos) if pos > self.num_args() => {
                         e.span_label(span, &format!(
                             "this precision flag expects an `usize` argument at position {}, \
                              but {}",
@@ -357,7 +378,8 @@ impl<'a, 'b> Context<'a, 'b> {
                         zero_based_note = true;
                     }
                     parse::CountIsParam(pos) => {
-                        let count = self.pieces.len() + self.arg_with_formatting
+                        let count = self.pieces.len() - self.captured_pieces.len() +
+                            self.arg_with_formatting
                             .iter()
                             .filter(|fmt| fmt.precision_span.is_some())
                             .count();
@@ -383,7 +405,7 @@ impl<'a, 'b> Context<'a, 'b> {
             if let Some(span) = fmt.width_span {
                 let span = self.fmtsp.from_inner(span);
                 match fmt.width {
-                    parse::CountIsParam(pos) if pos > self.args.len() => {
+                    parse::CountIsParam(pos) if pos > self.num_args() => {
                         e.span_label(span, &format!(
                             "this width flag expects an `usize` argument at position {}, \
                              but {}",
@@ -412,46 +434,63 @@ impl<'a, 'b> Context<'a, 'b> {
     fn verify_arg_type(&mut self, arg: Position, ty: ArgumentType) {
         match arg {
             Exact(arg) => {
-                if self.args.len() <= arg {
+                if self.num_args() <= arg {
                     self.invalid_refs.push((arg, self.curpiece));
                     return;
                 }
-                match ty {
-                    Placeholder(_) => {
-                        // record every (position, type) combination only once
-                        let ref mut seen_ty = self.arg_unique_types[arg];
-                        let i = seen_ty.iter().position(|x| *x == ty).unwrap_or_else(|| {
-                            let i = seen_ty.len();
-                            seen_ty.push(ty);
-                            i
-                        });
-                        self.arg_types[arg].push(i);
-                    }
-                    Count => {
-                        if let Entry::Vacant(e) = self.count_positions.entry(arg) {
-                            let i = self.count_positions_count;
-                            e.insert(i);
-                            self.count_args.push(Exact(arg));
-                            self.count_positions_count += 1;
-                        }
-                    }
-                }
+                self.record_arg_type(arg, ty);
             }
 
             Named(name) => {
-                match self.names.get(&name) {
-                    Some(&idx) => {
-                        // Treat as positional arg.
-                        self.verify_arg_type(Exact(idx), ty)
+                let sp = if self.is_literal {
+                    *self.arg_spans.get(self.curpiece).unwrap_or(&self.fmtsp)
+                } else {
+                    self.fmtsp
+                };
+                let capture = self.ecx.ecfg.features.map_or(false, |f| f.format_args_capture);
+                match self.names.get(&name).cloned() {
+                    Some(_) if ty == Count && self.captured_names.contains(&name) => {
+                        self.report_captured_count(sp, name);
+                    }
+                    Some(idx) => {
+                        if self.captured_names.contains(&name) {
+                            self.captured_pieces.insert(self.curpiece);
+                        }
+                        self.record_arg_type(idx, ty)
+                    }
+                    None if capture && self.is_literal && ty != Count => {
+                        // Capture the identifier from the scope surrounding the
+                        // macro call. The placeholder's span carries the syntax
+                        // context of the format string, so name resolution sees
+                        // the same bindings the user wrote the literal next to,
+                        // and reports "cannot find value" there if it's missing.
+                        let idx = self.args.len();
+                        let expr = self.ecx.expr_ident(sp, ast::Ident::new(name, sp));
+                        self.args.push(expr);
+                        self.arg_types.push(Vec::new());
+                        self.arg_unique_types.push(Vec::new());
+                        self.names.insert(name, idx);
+                        self.captured_names.insert(name);
+                        self.num_captured_args += 1;
+                        self.captured_pieces.insert(self.curpiece);
+                        self.record_arg_type(idx, ty)
+                    }
+                    None if capture && ty == Count => {
+                        self.report_captured_count(sp, name);
                     }
                     None => {
                         let msg = format!("there is no argument named `{}`", name);
-                        let sp = if self.is_literal {
-                            *self.arg_spans.get(self.curpiece).unwrap_or(&self.fmtsp)
-                        } else {
-                            self.fmtsp
-                        };
                         let mut err = self.ecx.struct_span_err(sp, &msg[..]);
+                        if capture {
+                            err.note("implicit capture of named arguments is only supported \
+                                      when the format string is a literal");
+                        } else if self.is_literal && ty != Count {
+                            err.help(&format!(
+                                "if you intended to capture `{}` from the surrounding scope, \
+                                 add `#![feature(format_args_capture)]` to the crate attributes",
+                                name,
+                            ));
+                        }
                         err.emit();
                     }
                 }
@@ -459,6 +498,40 @@ impl<'a, 'b> Context<'a, 'b> {
         }
     }
 
+    /// Records a use of the argument at index `arg`, which is known to exist.
+    fn record_arg_type(&mut self, arg: usize, ty: ArgumentType) {
+        match ty {
+            Placeholder(_) => {
+                // record every (position, type) combination only once
+                let ref mut seen_ty = self.arg_unique_types[arg];
+                let i = seen_ty.iter().position(|x| *x == ty).unwrap_or_else(|| {
+                    let i = seen_ty.len();
+                    seen_ty.push(ty);
+                    i
+                });
+                self.arg_types[arg].push(i);
+            }
+            Count => {
+                if let Entry::Vacant(e) = self.count_positions.entry(arg) {
+                    let i = self.count_positions_count;
+                    e.insert(i);
+                    self.count_args.push(Exact(arg));
+                    self.count_positions_count += 1;
+                }
+            }
+        }
+    }
+
+    /// Width and precision arguments must be passed explicitly, since they
+    /// have to be `usize` and are not checked against a formatting trait.
+    fn report_captured_count(&self, sp: Span, name: Symbol) {
+        let msg = format!("width or precision `{}$` cannot refer to a captured argument", name);
+        let mut err = self.ecx.struct_span_err(sp, &msg[..]);
+        err.span_label(sp, "implicitly captured arguments can't be used here");
+        err.help(&format!("pass it explicitly, as in `{0} = {0}`", name));
+        err.emit();
+    }
+
     /// Builds the mapping between format placeholders and argument objects.
     fn build_index_map(&mut self) {
         // NOTE: Keep the ordering the same as `into_expr`'s expansion would do!
@@ -1030,6 +1103,9 @@ pub fn expand_preparsed_format_args(
         arg_types,
         arg_unique_types,
         names,
+        captured_names: FxHashSet::default(),
+        num_captured_args: 0,
+        captured_pieces: FxHashSet::default(),
         curarg: 0,
         curpiece: 0,
         arg_index_map: Vec::new(),
@@ -1093,6 +1169,7 @@ pub fn expand_preparsed_format_args(
     let errs = cx.arg_types
                  .iter()
                  .enumerate()
+                 .take(cx.num_args())
                  .filter(|(i, ty)| ty.is_empty() && !cx.count_positions.contains_key(&i))
                  .map(|(i, _)| {
                     let msg = if named_pos.contains(&i) {
@@ -1108,7 +1185,7 @@ pub fn expand_preparsed_format_args(
 
     let errs_len = errs.len();
     if !errs.is_empty() {
-        let args_used = cx.arg_types.len() - errs_len;
+        let args_used = cx.num_args() - errs_len;
         let args_unused = errs_len;
 
         let mut diag = {
@@ -196,6 +196,7 @@ symbols! {
         console,
         const_compare_raw_pointers,
         const_constructor,
+        const_eval_limit,
         const_extern_fn,
         const_fn,
         const_fn_union,
@@ -239,6 +240,7 @@ symbols! {
         deref,
         deref_mut,
         derive,
+        derive_default_enum,
         diagnostic,
         direct,
         doc,
@@ -301,6 +303,7 @@ symbols! {
         fn_must_use,
         forbid,
         format_args,
+        format_args_capture,
         format_args_nl,
         from,
         From,
@@ -402,6 +405,7 @@ symbols! {
         macro_export,
         macro_lifetime_matcher,
         macro_literal_matcher,
+        macro_metavar_expr,
         macro_reexport,
         macro_rules,
         macros_in_extern,
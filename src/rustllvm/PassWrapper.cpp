@@ -510,7 +510,7 @@ static TargetMachine::CodeGenFileType fromRust(LLVMRustFileType Type) {
 
 extern "C" LLVMRustResult
 LLVMRustWriteOutputFile(LLVMTargetMachineRef Target, LLVMPassManagerRef PMR,
-                        LLVMModuleRef M, const char *Path,
+                        LLVMModuleRef M, const char *Path, const char *DwoPath,
                         LLVMRustFileType RustFileType) {
   llvm::legacy::PassManager *PM = unwrap<llvm::legacy::PassManager>(PMR);
   auto FileType = fromRust(RustFileType);
@@ -527,8 +527,29 @@ LLVMRustWriteOutputFile(LLVMTargetMachineRef Target, LLVMPassManagerRef PMR,
 
 #if LLVM_VERSION_GE(7, 0)
   buffer_ostream BOS(OS);
+  if (DwoPath) {
+    raw_fd_ostream DOS(DwoPath, EC, sys::fs::F_None);
+    if (EC)
+      ErrorInfo = EC.message();
+    if (ErrorInfo != "") {
+      LLVMRustSetLastError(ErrorInfo.c_str());
+      return LLVMRustResult::Failure;
+    }
+    // LLVM only splits the DWARF out of the object file when the target
+    // machine has a split DWARF file set.
+    unwrap(Target)->Options.MCOptions.SplitDwarfFile = DwoPath;
+    buffer_ostream DBOS(DOS);
+    unwrap(Target)->addPassesToEmitFile(*PM, BOS, &DBOS, FileType, false);
+    PM->run(*unwrap(M));
+    delete PM;
+    return LLVMRustResult::Success;
+  }
   unwrap(Target)->addPassesToEmitFile(*PM, BOS, nullptr, FileType, false);
 #else
+  if (DwoPath) {
+    LLVMRustSetLastError("split debuginfo requires LLVM 7 or later");
+    return LLVMRustResult::Failure;
+  }
   unwrap(Target)->addPassesToEmitFile(*PM, OS, FileType, false);
 #endif
   PM->run(*unwrap(M));
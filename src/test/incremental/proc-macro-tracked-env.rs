@@ -0,0 +1,35 @@
+// This test makes sure that changing an environment variable read by a proc
+// macro through `proc_macro::tracked_env` invalidates the code the macro
+// generated, and only that code.
+
+// aux-build:tracked_env_proc_macro.rs
+// revisions: rpass1 rpass2 rpass3
+// compile-flags: -Z query-dep-graph
+//[rpass1] rustc-env:TRACKED_VALUE=1
+//[rpass2] rustc-env:TRACKED_VALUE=1
+//[rpass3] rustc-env:TRACKED_VALUE=2
+
+#![feature(rustc_attrs)]
+#![rustc_partition_reused(module="proc_macro_tracked_env-generated", cfg="rpass2")]
+#![rustc_partition_codegened(module="proc_macro_tracked_env-generated", cfg="rpass3")]
+#![rustc_partition_reused(module="proc_macro_tracked_env-unrelated", cfg="rpass3")]
+
+extern crate tracked_env_proc_macro;
+
+mod generated {
+    tracked_env_proc_macro::tracked_value!();
+}
+
+mod unrelated {
+    pub fn value() -> u32 {
+        42
+    }
+}
+
+fn main() {
+    #[cfg(any(rpass1, rpass2))]
+    assert_eq!(generated::value(), 1);
+    #[cfg(rpass3)]
+    assert_eq!(generated::value(), 2);
+    assert_eq!(unrelated::value(), 42);
+}
@@ -0,0 +1,5 @@
+#![crate_type = "rlib"]
+
+pub fn first() -> u32 {
+    1
+}
@@ -0,0 +1,5 @@
+#![crate_type = "rlib"]
+
+pub fn second() -> u32 {
+    2
+}
@@ -0,0 +1,8 @@
+#[inline(never)]
+fn double<T: Clone + std::ops::Add<Output = T>>(x: T) -> T {
+    x.clone() + x
+}
+
+fn main() {
+    println!("{} {}", double(1u8), double(2.0f64));
+}
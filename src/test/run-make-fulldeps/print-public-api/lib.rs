@@ -0,0 +1,43 @@
+#![crate_type = "lib"]
+
+use std::cell::Cell;
+
+pub use inner::Reexported;
+
+mod inner {
+    pub struct Reexported;
+
+    impl Reexported {
+        pub fn get(&self) -> u32 {
+            0
+        }
+    }
+
+    pub struct Hidden;
+}
+
+pub fn answer(x: u32) -> u32 {
+    x + private_helper()
+}
+
+fn private_helper() -> u32 {
+    42
+}
+
+#[derive(Clone)]
+pub struct Widget {
+    pub size: usize,
+    id: Cell<*const u8>,
+}
+
+impl Widget {
+    pub fn new(size: usize) -> Widget {
+        Widget { size, id: Cell::new(std::ptr::null()) }
+    }
+
+    fn id(&self) -> *const u8 {
+        self.id.get()
+    }
+}
+
+unsafe impl Sync for Widget {}
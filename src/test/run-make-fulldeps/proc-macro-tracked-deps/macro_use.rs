@@ -0,0 +1,7 @@
+#[macro_use]
+extern crate macro_def;
+
+access_env_vars!();
+access_tracked_paths!();
+
+fn main() {}
@@ -0,0 +1,84 @@
+#![feature(rustc_private)]
+
+extern crate rustc;
+extern crate rustc_interface;
+extern crate rustc_driver as _;
+extern crate syntax;
+
+use rustc::session::DiagnosticOutput;
+use rustc::session::config::{Input, Options, OutputType, OutputTypes};
+use rustc_interface::interface;
+use syntax::source_map::FileName;
+
+use std::path::PathBuf;
+
+fn main() {
+    let args: Vec<String> = std::env::args().collect();
+
+    if args.len() < 4 {
+        panic!("expected rustc path");
+    }
+
+    let tmpdir = PathBuf::from(&args[1]);
+
+    let mut sysroot = PathBuf::from(&args[3]);
+    sysroot.pop();
+    sysroot.pop();
+
+    let good = r#"
+    fn answer() -> u32 { 42 }
+    fn double(x: i64) -> i64 { x * 2 }
+    fn main() { answer(); double(1); }
+    "#;
+    let types = analyze(good.to_string(), tmpdir.join("out"), sysroot.clone())
+        .expect("analysis of a well-typed crate failed");
+    for (name, ty) in types {
+        println!("{}: {}", name, ty);
+    }
+
+    let bad = r#"
+    fn main() { let _: u32 = "not a number"; }
+    "#;
+    match analyze(bad.to_string(), tmpdir.join("out"), sysroot) {
+        Ok(_) => panic!("analysis of an ill-typed crate succeeded"),
+        Err(()) => println!("type error reported"),
+    }
+}
+
+/// Runs the analysis passes on `code`, and returns the name of every function along with the
+/// type of its body's value, as recorded in the typeck tables.
+fn analyze(code: String, output: PathBuf, sysroot: PathBuf) -> Result<Vec<(String, String)>, ()> {
+    let mut opts = Options::default();
+    opts.output_types = OutputTypes::new(&[(OutputType::Exe, None)]);
+    opts.maybe_sysroot = Some(sysroot);
+
+    let name = FileName::anon_source_code(&code);
+    let input = Input::Str { name, input: code };
+
+    let config = interface::Config {
+        opts,
+        crate_cfg: Default::default(),
+        input,
+        input_path: None,
+        output_file: Some(output),
+        output_dir: None,
+        file_loader: None,
+        diagnostic_output: DiagnosticOutput::Default,
+        stderr: None,
+        crate_name: None,
+        lint_caps: Default::default(),
+    };
+
+    interface::run_compiler(config, |compiler| {
+        compiler.enter_analysis(|tcx| {
+            let mut types = tcx.body_owners().map(|def_id| {
+                let hir_id = tcx.hir().as_local_hir_id(def_id).unwrap();
+                let body = tcx.hir().body(tcx.hir().body_owned_by(hir_id));
+                let ty = tcx.typeck_tables_of(def_id).node_type(body.value.hir_id);
+                (tcx.def_path_str(def_id), ty.to_string())
+            }).collect::<Vec<_>>();
+            types.sort();
+            types
+        }).map_err(|_| ())
+    })
+}
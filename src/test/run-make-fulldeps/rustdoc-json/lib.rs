@@ -0,0 +1,30 @@
+use std::fmt::Debug;
+
+/// A wrapper around a value.
+pub struct Wrapper<T> {
+    pub value: T,
+}
+
+impl<T> Wrapper<T> where T: Debug {
+    pub fn new(value: T) -> Self {
+        Wrapper { value }
+    }
+}
+
+pub enum Shape {
+    Circle { radius: f64 },
+    Square(f64),
+}
+
+pub trait Area {
+    fn area(&self) -> f64;
+}
+
+impl Area for Shape {
+    fn area(&self) -> f64 {
+        match *self {
+            Shape::Circle { radius } => 3.14 * radius * radius,
+            Shape::Square(side) => side * side,
+        }
+    }
+}
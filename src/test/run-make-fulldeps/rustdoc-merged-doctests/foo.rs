@@ -0,0 +1,15 @@
+/// ```
+/// use foo::Foo;
+/// assert_eq!(Foo::new().0, 1);
+/// ```
+pub struct Foo(pub u32);
+
+impl Foo {
+    /// ```
+    /// let foo = foo::Foo::new();
+    /// assert_eq!(foo.0, 1);
+    /// ```
+    pub fn new() -> Foo {
+        Foo(1)
+    }
+}
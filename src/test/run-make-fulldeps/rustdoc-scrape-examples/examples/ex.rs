@@ -0,0 +1,13 @@
+use foobar::Describe;
+
+fn main() {
+    let x = foobar::ok(1);
+    println!("{}", x);
+
+    let counter = foobar::Counter;
+    println!("{} {}", counter.get(), describe(&counter));
+}
+
+fn describe<T: Describe>(value: &T) -> String {
+    value.describe()
+}
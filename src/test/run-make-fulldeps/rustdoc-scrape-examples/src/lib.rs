@@ -0,0 +1,29 @@
+/// Returns its argument.
+pub fn ok(x: u32) -> u32 {
+    x
+}
+
+/// Is never called by the examples.
+pub fn unused() {}
+
+/// Counts nothing.
+pub struct Counter;
+
+impl Counter {
+    /// Returns the count.
+    pub fn get(&self) -> u32 {
+        0
+    }
+}
+
+/// Describes a value.
+pub trait Describe {
+    /// Returns the description.
+    fn describe(&self) -> String;
+}
+
+impl Describe for Counter {
+    fn describe(&self) -> String {
+        "counter".to_string()
+    }
+}
@@ -0,0 +1,2 @@
+/// Links to [Missing].
+pub struct Foo;
@@ -0,0 +1,7 @@
+#![crate_type = "lib"]
+
+pub fn foo() -> u32 {
+    let x: u32 = "not a number";
+    let unused = 1;
+    x
+}
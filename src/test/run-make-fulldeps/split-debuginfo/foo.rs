@@ -0,0 +1,9 @@
+mod a {
+    pub fn hello() -> &'static str {
+        "hello"
+    }
+}
+
+fn main() {
+    println!("{}", a::hello());
+}
@@ -0,0 +1,9 @@
+// exact-check
+
+const QUERY = '&str, &[&str] -> String';
+
+const EXPECTED = {
+    'others': [
+        { 'path': 'search_by_signature_multiple_inputs', 'name': 'join' },
+    ],
+};
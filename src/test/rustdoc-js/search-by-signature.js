@@ -0,0 +1,15 @@
+// exact-check
+
+const QUERY = 'vec<u8> -> string';
+
+const EXPECTED = {
+    'others': [
+        { 'path': 'search_by_signature', 'name': 'from_bytes' },
+        { 'path': 'search_by_signature', 'name': 'from_bytes_with_capacity' },
+    ],
+    'returned': [
+        { 'path': 'search_by_signature', 'name': 'join' },
+        { 'path': 'search_by_signature', 'name': 'from_bytes' },
+        { 'path': 'search_by_signature', 'name': 'from_bytes_with_capacity' },
+    ],
+};
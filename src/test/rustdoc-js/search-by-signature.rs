@@ -0,0 +1,8 @@
+pub struct Data;
+
+pub fn from_bytes(_v: Vec<u8>) -> String { String::new() }
+pub fn from_bytes_with_capacity(_v: Vec<u8>, _cap: usize) -> String { String::new() }
+pub fn to_bytes(_s: &str) -> Vec<u8> { Vec::new() }
+pub fn join(_parts: &[&str], _sep: &str) -> String { String::new() }
+pub fn split_at(_v: &[u8], _mid: usize) -> (Data, Data) { (Data, Data) }
+pub fn clear(_s: &mut String) {}
@@ -0,0 +1,2 @@
+/// The links of the original documentation, like [Missing], aren't checked.
+pub struct Dep;
@@ -0,0 +1,33 @@
+// compile-flags:-Z unstable-options --show-coverage
+// build-pass (FIXME(62277): could be check-pass?)
+
+//! This crate has docs, but no examples.
+
+/// Documented, with an example:
+///
+/// ```
+/// let x = 1 + 1;
+/// assert_eq!(x, 2);
+/// ```
+pub fn with_example() {}
+
+/// Documented, but without an example.
+pub fn without_example() {}
+
+/// Code blocks in other languages are not examples:
+///
+/// ```text
+/// not rust
+/// ```
+pub struct Foo;
+
+impl Foo {
+    /// Examples on methods count too.
+    ///
+    /// ```
+    /// examples::Foo.method();
+    /// ```
+    pub fn method(&self) {}
+
+    pub fn undocumented(&self) {}
+}
@@ -0,0 +1,15 @@
+// compile-flags:-Z unstable-options --output-format json --show-coverage
+// build-pass (FIXME(62277): could be check-pass?)
+
+//! Crate docs.
+
+/// A function with an example.
+///
+/// ```
+/// json::foo();
+/// ```
+pub fn foo() {}
+
+pub fn bar() {}
+
+pub struct Baz;
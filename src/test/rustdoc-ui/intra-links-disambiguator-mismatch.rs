@@ -0,0 +1,31 @@
+#![deny(intra_doc_link_resolution_failure)]
+
+pub struct S {}
+
+pub mod m {}
+
+pub fn f() {}
+
+/// Link to [struct@m]
+//~^ ERROR incompatible link kind
+pub fn a() {}
+
+/// Link to [enum@S]
+//~^ ERROR incompatible link kind
+pub fn b() {}
+
+/// Link to [const@f]
+//~^ ERROR incompatible link kind
+pub fn c() {}
+
+/// Link to [prim@S]
+//~^ ERROR incompatible link kind
+pub fn d() {}
+
+/// Link to [f!]
+//~^ ERROR `[f]` cannot be resolved
+pub fn e() {}
+
+/// Link to [S()]
+//~^ ERROR `[S]` cannot be resolved
+pub fn g() {}
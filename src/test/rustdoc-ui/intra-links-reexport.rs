@@ -0,0 +1,9 @@
+// aux-build:intra-links-reexport-dep.rs
+#![deny(intra_doc_link_resolution_failure)]
+
+extern crate intra_links_reexport_dep;
+
+/// Re-exported with a broken link to [Nothing].
+//~^ ERROR `[Nothing]` cannot be resolved
+#[doc(inline)]
+pub use intra_links_reexport_dep::Dep;
@@ -0,0 +1,23 @@
+// build-pass
+// compile-flags:--test -Z unstable-options --merge-doctests --test-args --test-threads=1
+// normalize-stdout-test: "src/test/rustdoc-ui" -> "$$DIR"
+
+/// ```
+/// assert_eq!(1 + 1, 2);
+/// ```
+///
+/// ```should_panic
+/// panic!("merged doctests can panic");
+/// ```
+///
+/// ```
+/// #![allow(dead_code)]
+/// assert!(true);
+/// ```
+///
+/// ```
+/// fn main() {
+///     assert!(true);
+/// }
+/// ```
+pub struct Foo;
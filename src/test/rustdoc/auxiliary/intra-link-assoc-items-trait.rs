@@ -0,0 +1,9 @@
+#![crate_name = "spin"]
+
+extern crate wheel;
+
+pub trait Spin {
+    fn spin(&self) {}
+}
+
+impl Spin for wheel::Wheel {}
@@ -0,0 +1,3 @@
+#![crate_name = "wheel"]
+
+pub struct Wheel;
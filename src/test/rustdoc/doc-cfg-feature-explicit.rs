@@ -0,0 +1,14 @@
+// compile-flags: --cfg feature="std"
+
+#![feature(doc_cfg)]
+
+// An explicit `#[doc(cfg)]` takes precedence over the feature requirements.
+// @has doc_cfg_feature_explicit/index.html
+// @matches - '//*[@class="module-item"]//*[@class="stab portability"]' '\AUnix\Z'
+
+// @has doc_cfg_feature_explicit/fn.explicit.html
+// @has - '//*[@id="main"]/*[@class="stability"]/*[@class="stab portability"]' \
+//        'This is supported on Unix only.'
+#[cfg(feature = "std")]
+#[doc(cfg(unix))]
+pub fn explicit() {}
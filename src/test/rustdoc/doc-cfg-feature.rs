@@ -0,0 +1,45 @@
+// compile-flags: --cfg feature="serde" --cfg feature="std"
+
+// @has doc_cfg_feature/index.html
+// @matches - '//*[@class="module-item"]//*[@class="stab portability"]' '\Aserde\Z'
+
+// @has doc_cfg_feature/struct.Serializer.html
+// @has - '//*[@id="main"]/*[@class="stability"]/*[@class="stab portability"]' \
+//        'This is supported with crate feature serde only.'
+// The impl has no requirements besides the ones of the type.
+// @!has - '//h3[@class="impl"]/following-sibling::*[@class="stability"]' ''
+#[cfg(feature = "serde")]
+pub struct Serializer;
+
+#[cfg(feature = "serde")]
+impl Serializer {
+    pub fn flush(&self) {}
+}
+
+// @has doc_cfg_feature/struct.Buffer.html
+// @!has - '//*[@id="main"]/*[@class="stability"]/*[@class="stab portability"]' ''
+// @has - '//h3[@class="impl"]/following-sibling::*[@class="stability"]' \
+//        'This is supported with crate feature std only.'
+// @has - '//*[@id="method.to_vec"]/following-sibling::*[@class="stability"]' \
+//        'This is supported with crate feature std only.'
+pub struct Buffer;
+
+#[cfg(feature = "std")]
+impl Buffer {
+    pub fn to_vec(&self) -> Vec<u8> {
+        Vec::new()
+    }
+}
+
+// Only the feature requirements are shown.
+// @has doc_cfg_feature/serde/fn.to_string.html
+// @has - '//*[@id="main"]/*[@class="stability"]/*[@class="stab portability"]' \
+//        'This is supported on crate feature serde and crate feature std only.'
+#[cfg(all(not(test), feature = "serde"))]
+pub mod serde {
+    // A requirement that is already on the parent isn't repeated.
+    #[cfg(all(feature = "serde", feature = "std"))]
+    pub fn to_string() -> String {
+        String::new()
+    }
+}
@@ -0,0 +1,16 @@
+// aux-build:intra-link-assoc-items-type.rs
+// aux-build:intra-link-assoc-items-trait.rs
+
+// The impl of a trait method can live in neither the crate of the type nor the documented crate.
+
+#![deny(intra_doc_link_resolution_failure)]
+
+extern crate spin;
+extern crate wheel;
+
+pub use wheel::Wheel;
+
+// @has intra_link_assoc_items_extern/fn.links.html
+// @has - '//a/@href' '../intra_link_assoc_items_extern/struct.Wheel.html#method.spin'
+/// Links to [`Wheel::spin`].
+pub fn links() {}
@@ -0,0 +1,36 @@
+#![deny(intra_doc_link_resolution_failure)]
+
+// @has intra_link_assoc_items/fn.links.html
+// @has - '//a/@href' '../intra_link_assoc_items/struct.Foo.html#method.clone'
+// @has - '//a/@href' '../intra_link_assoc_items/struct.Foo.html#method.clone_from'
+// @has - '//a/@href' '../intra_link_assoc_items/struct.Foo.html#method.go'
+// @has - '//a/@href' '../intra_link_assoc_items/enum.Shape.html#variant.Circle.field.radius'
+// @has - '//a/@href' 'https://doc.rust-lang.org/nightly/std/primitive.slice.html#method.sort'
+// @has - '//a/@href' 'https://doc.rust-lang.org/nightly/std/primitive.pointer.html#method.is_null'
+// @has - '//a/@href' 'https://doc.rust-lang.org/nightly/std/primitive.f64.html#method.sqrt'
+// @has - '//a/@href' 'https://doc.rust-lang.org/nightly/std/primitive.str.html'
+// @has - '//a/@href' 'https://doc.rust-lang.org/nightly/std/primitive.array.html'
+// @has - '//a/@href' '../intra_link_assoc_items/inner/index.html'
+/// Links to trait items: [`Foo::clone`], [`Foo::clone_from`] and [`Foo::go`].
+///
+/// To the field of a variant: [`Shape::Circle::radius`].
+///
+/// To methods of primitives: [`slice::sort`], [`pointer::is_null`] and [`f64::sqrt`].
+///
+/// With disambiguators: [`prim@str`], [`prim@array`] and [`mod@inner`].
+pub fn links() {}
+
+#[derive(Clone)]
+pub struct Foo;
+
+pub trait Go {
+    fn go(&self) {}
+}
+
+impl Go for Foo {}
+
+pub enum Shape {
+    Circle { radius: f64 },
+}
+
+pub mod inner {}
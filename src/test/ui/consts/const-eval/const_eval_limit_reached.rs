@@ -0,0 +1,15 @@
+#![feature(const_eval_limit)]
+#![const_eval_limit = "500"]
+
+fn main() {
+    // Takes more than 500 steps, but terminates.
+    let _ = [(); {
+        let mut x = 0;
+        while x != 1000 {
+        //~^ ERROR constant contains unimplemented expression type
+        //~| ERROR constant contains unimplemented expression type
+            x += 1; //~ ERROR evaluation of constant value failed
+        }
+        x
+    }];
+}
@@ -1,8 +1,10 @@
+// compile-flags: -Z const-eval-limit=2000000
+
 fn main() {
     // Tests the Collatz conjecture with an incorrect base case (0 instead of 1).
     // The value of `n` will loop indefinitely (4 - 2 - 1 - 4).
     let _ = [(); {
-        //~^ WARNING Constant evaluating a complex constant, this might take some time
+        //~^ WARNING constant evaluation is taking a long time
         let mut n = 113383; // #20 in https://oeis.org/A006884
         while n != 0 {
         //~^ ERROR constant contains unimplemented expression type
@@ -1,6 +1,8 @@
+// compile-flags: -Z const-eval-limit=2000000
+
 fn main() {
     let _ = [(); {
-        //~^ WARNING Constant evaluating a complex constant, this might take some time
+        //~^ WARNING constant evaluation is taking a long time
         let mut x = &0;
         let mut n = 0;
         while n < 5 {
@@ -0,0 +1,31 @@
+#![feature(derive_default_enum)]
+
+#[derive(Default)] //~ ERROR no default declared
+enum NoDefault {
+    A,
+    B,
+}
+
+#[derive(Default)] //~ ERROR multiple declared defaults
+enum MultipleDefaults {
+    #[default]
+    A,
+    #[default]
+    B,
+    C,
+}
+
+#[derive(Default)]
+enum NonUnitDefault {
+    #[default] //~ ERROR the `#[default]` attribute may only be used on unit enum variants
+    A(u8),
+    B,
+}
+
+#[derive(Default)]
+enum DefaultWithValue {
+    #[default = 1] //~ ERROR malformed `default` attribute input
+    A,
+}
+
+fn main() {}
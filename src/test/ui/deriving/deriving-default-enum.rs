@@ -0,0 +1,29 @@
+// run-pass
+
+#![feature(derive_default_enum)]
+
+// nb: does not impl Default
+#[derive(Debug, PartialEq)]
+struct NotDefault;
+
+#[derive(Debug, Default, PartialEq)]
+enum Foo {
+    #[default]
+    Alpha,
+    #[allow(dead_code)]
+    Beta(NotDefault),
+}
+
+// `#[default]` doesn't have to be on the first variant.
+#[derive(Debug, Default, PartialEq)]
+enum Bar<T> {
+    #[allow(dead_code)]
+    Some(T),
+    #[default]
+    None,
+}
+
+fn main() {
+    assert_eq!(Foo::default(), Foo::Alpha);
+    assert_eq!(Bar::<u8>::default(), Bar::None);
+}
@@ -0,0 +1,3 @@
+#![const_eval_limit = "42"] //~ ERROR the `#[const_eval_limit]` attribute is an experimental feature
+
+fn main() {}
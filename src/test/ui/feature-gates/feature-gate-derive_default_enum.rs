@@ -0,0 +1,7 @@
+#[derive(Default)]
+enum Foo {
+    #[default] //~ ERROR the `#[default]` attribute is an experimental feature
+    Alpha,
+}
+
+fn main() {}
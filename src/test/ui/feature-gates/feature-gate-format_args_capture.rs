@@ -0,0 +1,6 @@
+fn main() {
+    format!("{foo}");                //~ ERROR: there is no argument named `foo`
+
+    let bar = 1;
+    format!("{foo} {bar}", foo=1);   //~ ERROR: there is no argument named `bar`
+}
@@ -0,0 +1,9 @@
+#![crate_type = "lib"]
+
+extern crate proc_macro;
+
+use proc_macro::Literal;
+
+pub fn parse(s: &str) -> bool {
+    Literal::from_str(s).is_ok() //~ ERROR use of unstable library feature 'proc_macro_literal_value'
+}
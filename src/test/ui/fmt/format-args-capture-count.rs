@@ -0,0 +1,16 @@
+#![feature(format_args_capture)]
+
+fn main() {
+    let x = 1;
+    let width = 4;
+    let precision = 2;
+
+    format!("{x:width$}");
+    //~^ ERROR width or precision `width$` cannot refer to a captured argument
+
+    format!("{x:.precision$}");
+    //~^ ERROR width or precision `precision$` cannot refer to a captured argument
+
+    format!("{width} {x:width$}");
+    //~^ ERROR width or precision `width$` cannot refer to a captured argument
+}
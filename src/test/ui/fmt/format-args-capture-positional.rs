@@ -0,0 +1,12 @@
+#![feature(format_args_capture)]
+
+fn main() {
+    let x = 1;
+    let (a, b) = (2, 3);
+    format!("{x} {}");
+    //~^ ERROR 1 positional argument in format string, but no arguments were given
+    format!("{x} {} {}", a);
+    //~^ ERROR 2 positional arguments in format string, but there is 1 argument
+    format!("{} {x}", a, b);
+    //~^ ERROR argument never used
+}
@@ -0,0 +1,61 @@
+// run-pass
+#![feature(format_args_capture)]
+
+fn main() {
+    named_argument_takes_precedence_to_captured();
+    formatting_parameters_can_be_captured();
+    captured_variable_used_several_times();
+    captured_in_nested_macro();
+}
+
+fn named_argument_takes_precedence_to_captured() {
+    let foo = "captured";
+    let s = format!("{foo}", foo="named");
+    assert_eq!(&s, "named");
+
+    let s = format!("{foo}-{foo}-{foo}", foo="named");
+    assert_eq!(&s, "named-named-named");
+
+    let s = format!("{}-{bar}-{foo}", "positional", bar="named");
+    assert_eq!(&s, "positional-named-captured");
+}
+
+fn formatting_parameters_can_be_captured() {
+    let x = 17;
+    let width = 4;
+    let precision = 3;
+
+    let s = format!("{x:width$}", width = width);
+    assert_eq!(&s, "  17");
+
+    let s = format!("{x:<width$}", width = width);
+    assert_eq!(&s, "17  ");
+
+    let s = format!("{x:-^width$}", width = width);
+    assert_eq!(&s, "-17-");
+
+    let x = 0.1;
+    let s = format!("{x:.precision$}", precision = precision);
+    assert_eq!(&s, "0.100");
+}
+
+fn captured_variable_used_several_times() {
+    let a = 1;
+    let b = "two";
+    let s = format!("{a} {b} {a:?} {b:?} {a:x}");
+    assert_eq!(&s, "1 two 1 \"two\" 1");
+
+    let s = format!("{a}", a = a + 1);
+    assert_eq!(&s, "2");
+}
+
+fn captured_in_nested_macro() {
+    let x = 42;
+    let mut buf = String::new();
+    use std::fmt::Write;
+    write!(buf, "{x}").unwrap();
+    assert_eq!(&buf, "42");
+
+    let s = format!("{}", format!("{x}"));
+    assert_eq!(&s, "42");
+}
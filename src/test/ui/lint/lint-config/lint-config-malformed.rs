@@ -0,0 +1,8 @@
+// Malformed entries in a `-Z lint-config` file are reported at their line.
+//
+// compile-flags: -Z lint-config={{src-base}}/lint/lint-config/lint-config-malformed.lints
+// error-pattern: malformed lint configuration entry
+// error-pattern: unknown lint level: `sometimes`
+// error-pattern: unknown lint: `unused_varables`
+
+fn main() {}
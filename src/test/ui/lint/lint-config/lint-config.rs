@@ -0,0 +1,14 @@
+// Lint levels can be read from a file given with `-Z lint-config`. Flags on
+// the command line take precedence over the file, and attributes in the
+// source take precedence over both, unless the file forbids the lint.
+//
+// compile-flags: -Z lint-config={{src-base}}/lint/lint-config/lint-config.lints
+// compile-flags: -W while-true
+
+#[allow(non_camel_case_types)] //~ ERROR overruled by outer forbid(non_camel_case_types)
+struct foo;
+
+fn main() {
+    let _InappropriateCamelCasing = true; //~ ERROR should have a snake case name
+    while true {} //~ WARN denote infinite loops
+}
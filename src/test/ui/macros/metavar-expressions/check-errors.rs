@@ -0,0 +1,28 @@
+#![feature(macro_metavar_expr)]
+
+macro_rules! index_outside_repetition {
+    ( $( $i:ident ),* ) => { ${index()} };
+    //~^ ERROR meta-variable expression `index` must be placed inside a repetition
+}
+
+macro_rules! length_depth_out_of_bounds {
+    ( $( $i:ident ),* ) => { $( ${ignore(i)} ${length(1)} )* };
+    //~^ ERROR depth parameter on meta-variable expression `length` must be less than 1
+}
+
+macro_rules! count_in_innermost_repetition {
+    ( $( $i:ident ),* ) => { $( ${count(i)} )* };
+    //~^ ERROR `count` can not be placed inside the inner-most repetition of `i`
+}
+
+macro_rules! count_depth_out_of_bounds {
+    ( $( [ $( $i:ident ),* ] )* ) => { ${count(i, 2)} };
+    //~^ ERROR depth parameter on meta-variable expression `count` must be less than 2
+}
+
+macro_rules! unknown_variable {
+    ( $( $i:ident ),* ) => { ${count(j)} };
+    //~^ ERROR unknown macro variable `j`
+}
+
+fn main() {}
@@ -0,0 +1,63 @@
+// run-pass
+
+#![feature(macro_metavar_expr)]
+
+macro_rules! count_idents {
+    ( $( $i:ident ),* ) => {
+        ${count(i)}
+    };
+}
+
+macro_rules! count_nested {
+    ( $( [ $( $i:ident ),* ] )* ) => {
+        // Total number of `$i`, number of groups, and number of `$i` per group.
+        (${count(i)}, ${count(i, 0)}, [ $( ${count(i)} ),* ])
+    };
+}
+
+macro_rules! indexes {
+    ( $( $i:ident ),* ) => {
+        [ $( ${ignore(i)} ${index()} ),* ]
+    };
+}
+
+macro_rules! lengths {
+    ( $( $i:ident ),* ) => {
+        [ $( ${ignore(i)} ${length()} ),* ]
+    };
+}
+
+macro_rules! nested_indexes {
+    ( $( [ $( $i:ident )* ] )* ) => {
+        [ $( $( ${ignore(i)} (${index(1)}, ${index()}, ${length(1)}, ${length()}), )* )* ]
+    };
+}
+
+macro_rules! table {
+    ( $( $name:ident ),* ) => {
+        $( const $name: usize = ${index()}; )*
+        const LEN: usize = ${count(name)};
+    };
+}
+
+table!(A, B, C);
+
+fn main() {
+    assert_eq!(count_idents!(), 0);
+    assert_eq!(count_idents!(a), 1);
+    assert_eq!(count_idents!(a, b, c), 3);
+
+    assert_eq!(count_nested!([a, b, c] [] [d]), (4, 3, [3, 0, 1]));
+
+    let empty: [usize; 0] = indexes!();
+    assert_eq!(empty, []);
+    assert_eq!(indexes!(a, b, c), [0, 1, 2]);
+    assert_eq!(lengths!(a, b, c), [3, 3, 3]);
+
+    assert_eq!(
+        nested_indexes!([a b] [c]),
+        [(0, 0, 2, 2), (0, 1, 2, 2), (1, 0, 2, 1)],
+    );
+
+    assert_eq!((A, B, C, LEN), (0, 1, 2, 3));
+}
@@ -0,0 +1,28 @@
+#![feature(macro_metavar_expr)]
+
+macro_rules! unknown_expr {
+    ( $( $i:ident ),* ) => { ${ foo(i) } };
+    //~^ ERROR unrecognized meta-variable expression
+}
+
+macro_rules! missing_parens {
+    ( $( $i:ident ),* ) => { ${ count } };
+    //~^ ERROR meta-variable expression parameter must be wrapped in parentheses
+}
+
+macro_rules! non_literal_depth {
+    ( $( $i:ident ),* ) => { $( ${ignore(i)} ${ index(a) } )* };
+    //~^ ERROR meta-variable expression depth must be a literal
+}
+
+macro_rules! trailing_tokens {
+    ( $( $i:ident ),* ) => { ${ count(i) extra } };
+    //~^ ERROR unexpected token: extra
+}
+
+macro_rules! dollar_in_argument {
+    ( $( $i:ident ),* ) => { ${ count($i) } };
+    //~^ ERROR expected identifier, found `$`
+}
+
+fn main() {}
@@ -0,0 +1,36 @@
+// force-host
+// no-prefer-dynamic
+
+#![crate_type = "proc-macro"]
+#![feature(proc_macro_expand)]
+
+extern crate proc_macro;
+
+use proc_macro::{TokenStream, TokenTree};
+
+/// Splits `expected, expr` at the first top-level comma.
+fn split(input: TokenStream) -> (TokenStream, TokenStream) {
+    let mut trees = input.into_iter();
+    let expected = trees
+        .by_ref()
+        .take_while(|tt| match tt {
+            TokenTree::Punct(p) => p.as_char() != ',',
+            _ => true,
+        })
+        .collect();
+    (expected, trees.collect())
+}
+
+#[proc_macro]
+pub fn expand_expr_is(input: TokenStream) -> TokenStream {
+    let (expected, expr) = split(input);
+    let expanded = expr.expand_expr().expect("expand_expr failed");
+    assert_eq!(expected.to_string(), expanded.to_string());
+    TokenStream::new()
+}
+
+#[proc_macro]
+pub fn expand_expr_fails(input: TokenStream) -> TokenStream {
+    assert!(input.expand_expr().is_err());
+    TokenStream::new()
+}
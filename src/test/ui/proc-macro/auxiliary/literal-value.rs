@@ -0,0 +1,47 @@
+// force-host
+// no-prefer-dynamic
+
+#![feature(proc_macro_literal_value)]
+#![crate_type = "proc-macro"]
+
+extern crate proc_macro;
+
+use proc_macro::{Literal, TokenStream, TokenTree};
+
+fn lit(s: &str) -> Literal {
+    Literal::from_str(s).unwrap_or_else(|_| panic!("`{}` should parse as a literal", s))
+}
+
+#[proc_macro]
+pub fn check_literals(_: TokenStream) -> TokenStream {
+    assert_eq!(lit(r#""a\nb\u{2764}""#).str_value().as_deref(), Some("a\nb\u{2764}"));
+    assert_eq!(lit(r##"r#"a\nb"#"##).str_value().as_deref(), Some("a\\nb"));
+    assert_eq!(lit(r#"b"\x00\xff""#).byte_str_value(), Some(vec![0, 255]));
+    assert_eq!(lit(r"'\''").char_value(), Some('\''));
+    assert_eq!(lit(r"b'\n'").byte_value(), Some(b'\n'));
+    assert_eq!(lit("0x1_0u8").int_value(), Some(16));
+    assert_eq!(lit("0x1_0u8").suffix().as_deref(), Some("u8"));
+    assert_eq!(lit("1.5e3").float_value(), Some(1500.0));
+    assert_eq!(lit("-2.5f32").float_value(), Some(-2.5));
+    assert_eq!(lit("-2").int_value(), None);
+    assert_eq!(lit("'a'").str_value(), None);
+    assert_eq!(lit("1").suffix(), None);
+
+    for s in &["", "-", "a", "1 2", "\"unterminated", r#""\q""#, "1u7", "-'a'", "'ab'"] {
+        assert!(Literal::from_str(s).is_err(), "`{}` should not parse as a literal", s);
+    }
+
+    // Values of literals written by the user are decoded the same way.
+    let input: TokenStream = r#"("x\ty")"#.parse().unwrap();
+    let tt = input.into_iter().next().unwrap();
+    let group = match tt {
+        TokenTree::Group(group) => group,
+        _ => panic!("expected a group"),
+    };
+    match group.stream().into_iter().next().unwrap() {
+        TokenTree::Literal(l) => assert_eq!(l.str_value().as_deref(), Some("x\ty")),
+        _ => panic!("expected a literal"),
+    }
+
+    TokenStream::new()
+}
@@ -0,0 +1,40 @@
+// force-host
+// no-prefer-dynamic
+
+#![crate_type = "proc-macro"]
+#![feature(proc_macro_diagnostic, proc_macro_span)]
+
+extern crate proc_macro;
+
+use proc_macro::{Applicability, Span, TokenStream, TokenTree};
+
+#[proc_macro]
+pub fn three_equals(input: TokenStream) -> TokenStream {
+    let mut count = 0;
+    let mut span: Option<Span> = None;
+    for tree in input {
+        if let TokenTree::Punct(ref tt) = tree {
+            if tt.as_char() == '=' {
+                count += 1;
+                span = span.map_or(Some(tt.span()), |span| span.join(tt.span()));
+                continue
+            }
+        }
+        tree.span().error(format!("expected `=`, found `{}`.", tree)).emit();
+        return TokenStream::new();
+    }
+
+    if count != 3 {
+        let span = span.unwrap_or_else(Span::call_site);
+        span.error(format!("found {} equal signs, need exactly 3", count))
+            .span_suggestion(
+                span,
+                "use exactly three equal signs",
+                "===",
+                Applicability::MachineApplicable,
+            )
+            .emit();
+    }
+
+    TokenStream::new()
+}
@@ -0,0 +1,29 @@
+// run-pass
+// aux-build:expand-expr.rs
+
+extern crate expand_expr;
+
+use expand_expr::{expand_expr_fails, expand_expr_is};
+
+macro_rules! echo {
+    ($e:expr) => { $e };
+}
+
+// Built-in macros are expanded eagerly.
+expand_expr_is!("helloworld", concat!("hello", "world"));
+expand_expr_is!("a b", stringify!(a b));
+expand_expr_is!("42", concat!(42));
+
+// So are `macro_rules` macros, including nested invocations.
+expand_expr_is!(3, echo!(3));
+expand_expr_is!(-1.5, echo!(-1.5));
+expand_expr_is!("ab", echo!(concat!("a", echo!("b"))));
+expand_expr_is!(true, echo!(true));
+
+// Expressions that aren't literals can't be returned.
+expand_expr_fails!(1 + 2);
+expand_expr_fails!(echo!(a + b));
+expand_expr_fails!(-"a");
+expand_expr_fails!(1, 2);
+
+fn main() {}
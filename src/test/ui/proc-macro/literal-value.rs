@@ -0,0 +1,8 @@
+// run-pass
+// aux-build:literal-value.rs
+
+extern crate literal_value;
+
+literal_value::check_literals!();
+
+fn main() {}
@@ -0,0 +1,12 @@
+// aux-build:suggestion.rs
+// run-rustfix
+
+#![feature(proc_macro_hygiene)]
+
+extern crate suggestion;
+
+use suggestion::three_equals;
+
+fn main() {
+    three_equals!(==); //~ ERROR found 2 equal signs, need exactly 3
+}